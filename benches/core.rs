@@ -0,0 +1,47 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use orangey::{permutation::Dxsm, Orangey32Ctx, OrangeyCore, OrangeyCtx, OrangeyExtCtx};
+use std::hint::black_box;
+
+fn outputs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("outputs");
+    let mut ctx = OrangeyCtx::new();
+    group.bench_function("rand", |b| b.iter(|| black_box(ctx.rand())));
+    group.bench_function("rand_u32", |b| b.iter(|| black_box(ctx.rand_u32())));
+    group.bench_function("uniform_double", |b| {
+        b.iter(|| black_box(ctx.uniform_double()))
+    });
+    group.bench_function("rand_range", |b| {
+        b.iter(|| black_box(ctx.rand_range(0..black_box(1000))))
+    });
+    group.finish();
+}
+
+fn engines(c: &mut Criterion) {
+    let mut group = c.benchmark_group("engines");
+    let mut ctx32 = Orangey32Ctx::new();
+    group.bench_function("orangey32", |b| b.iter(|| black_box(ctx32.rand())));
+    let mut ext = OrangeyExtCtx::new();
+    group.bench_function("ext", |b| b.iter(|| black_box(ext.rand())));
+    let mut dxsm = OrangeyCore::<Dxsm>::new();
+    group.bench_function("dxsm", |b| b.iter(|| black_box(dxsm.rand())));
+    group.finish();
+}
+
+fn bulk(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bulk");
+    let mut ctx = OrangeyCtx::new();
+    let mut buf = vec![0u8; 4096];
+    group.throughput(Throughput::Bytes(buf.len() as u64));
+    group.bench_function("fill_bytes", |b| {
+        b.iter(|| ctx.fill_bytes(black_box(&mut buf)))
+    });
+    group.finish();
+}
+
+fn jumps(c: &mut Criterion) {
+    let mut ctx = OrangeyCtx::new();
+    c.bench_function("skip", |b| b.iter(|| ctx.skip(black_box(u128::MAX / 3))));
+}
+
+criterion_group!(benches, outputs, engines, bulk, jumps);
+criterion_main!(benches);
@@ -0,0 +1,24 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use orangey::OrangeyCtx;
+use std::hint::black_box;
+
+fn gaussian(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gaussian");
+    let mut ctx = OrangeyCtx::new();
+    group.bench_function("polar", |b| b.iter(|| black_box(ctx.gaussian())));
+    group.bench_function("ziggurat", |b| b.iter(|| black_box(ctx.gaussian_fast())));
+    group.finish();
+}
+
+fn exponential(c: &mut Criterion) {
+    let mut group = c.benchmark_group("exponential");
+    let mut ctx = OrangeyCtx::new();
+    group.bench_function("inversion", |b| {
+        b.iter(|| black_box(-(1.0 - ctx.uniform_double()).ln()))
+    });
+    group.bench_function("ziggurat", |b| b.iter(|| black_box(ctx.exponential_fast())));
+    group.finish();
+}
+
+criterion_group!(benches, gaussian, exponential);
+criterion_main!(benches);
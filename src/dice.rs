@@ -0,0 +1,372 @@
+//! Dice notation, as used by tabletop games: `"3d6+2"` rolls three six-sided dice and adds 2.
+//! An expression is a sum of terms separated by `+` or `-`, where each term is a plain number or
+//! a roll of the form `NdM`
+//!
+//! - `N` is the number of dice, 1 if left out, and `M` is the number of sides. `d%` is short for
+//!   `d100`
+//! - `!` after a roll makes its dice explode: each die that lands on its highest face is rolled
+//!   again and the new roll added to it, for as long as it keeps landing there
+//! - `khK` or `kK` after a roll keeps only the `K` highest dice, and `klK` keeps only the `K`
+//!   lowest. With `!`, each die's exploded total counts as one die
+//!
+//! Letters can be either case, and spaces between terms are ignored, so `"4d6 kh3"`, `"2D20!"`
+//! and `"d% - 10"` all parse
+//!
+//! # Examples
+//!
+//! ```
+//! use orangey::{dice::DiceExpr, OrangeyCtx};
+//!
+//! let mut orangey_ctx = OrangeyCtx::new();
+//! let score = orangey_ctx.roll("4d6kh3").unwrap();
+//! assert!((3..=18).contains(&score));
+//!
+//! // Parse once to roll many times
+//! let attack: DiceExpr = "1d20 + 5".parse().unwrap();
+//! let hits = (0..1000)
+//!     .filter(|_| attack.roll(&mut orangey_ctx) >= 15)
+//!     .count();
+//! assert!((500..600).contains(&hits));
+//!
+//! let spaced: DiceExpr = "4d6 kh3".parse().unwrap();
+//! assert_eq!(spaced.to_string(), "4d6kh3");
+//! ```
+
+use crate::{OrangeyCtx, OrangeyEngine};
+use alloc::vec::Vec;
+use core::{fmt, str::FromStr};
+
+/// The most dice a single term can roll, which bounds the memory needed to keep some of them
+pub const MAX_DICE: u32 = 10_000;
+
+/// A parsed dice expression, ready to be rolled any number of times
+///
+/// It displays in a canonical form without spaces, like `"4d6kh3+2"`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DiceExpr {
+    terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Term {
+    negative: bool,
+    kind: TermKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum TermKind {
+    Constant(u32),
+    Roll {
+        count: u32,
+        sides: u32,
+        explode: bool,
+        keep: Option<Keep>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Keep {
+    Highest(u32),
+    Lowest(u32),
+}
+
+impl DiceExpr {
+    /// Rolls the dice and returns the total, saturating if it doesn't fit in an `i64`. This takes
+    /// one bounded integer per die rolled, as `.rand_range()` would, counting the extra rolls of
+    /// exploding dice, and goes through the terms from left to right
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::{dice::DiceExpr, OrangeyCtx};
+    ///
+    /// let damage: DiceExpr = "2d6 - 1d4 + 1".parse().unwrap();
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// for _ in 0..1000 {
+    ///     assert!((-1..=12).contains(&damage.roll(&mut orangey_ctx)));
+    /// }
+    ///
+    /// // Exploding dice have no upper limit, but average M(M + 1)/(2(M - 1)) per die
+    /// let exploding: DiceExpr = "d6!".parse().unwrap();
+    /// let mean = (0..100_000).map(|_| exploding.roll(&mut orangey_ctx)).sum::<i64>() as f64 / 1e5;
+    /// assert!((mean - 4.2).abs() < 0.05);
+    /// ```
+    pub fn roll<E: OrangeyEngine>(&self, ctx: &mut E) -> i64 {
+        self.terms.iter().fold(0i64, |total, term| {
+            let value = match term.kind {
+                TermKind::Constant(n) => n as i64,
+                TermKind::Roll {
+                    count,
+                    sides,
+                    explode,
+                    keep,
+                } => {
+                    let mut die = || {
+                        let mut sum = 0u64;
+                        loop {
+                            let face = ctx.rand_range(0..sides as u64) + 1;
+                            sum += face;
+                            if !explode || face != sides as u64 {
+                                return sum;
+                            }
+                        }
+                    };
+                    let sum = match keep {
+                        None => (0..count).map(|_| die()).fold(0, u64::saturating_add),
+                        Some(keep) => {
+                            let mut dice: Vec<u64> = (0..count).map(|_| die()).collect();
+                            dice.sort_unstable();
+                            let kept = match keep {
+                                Keep::Highest(k) => &dice[(count - k) as usize..],
+                                Keep::Lowest(k) => &dice[..k as usize],
+                            };
+                            kept.iter().copied().fold(0, u64::saturating_add)
+                        }
+                    };
+                    sum.min(i64::MAX as u64) as i64
+                }
+            };
+            if term.negative {
+                total.saturating_sub(value)
+            } else {
+                total.saturating_add(value)
+            }
+        })
+    }
+}
+
+impl FromStr for DiceExpr {
+    type Err = DiceParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            bytes: s.as_bytes(),
+            pos: 0,
+        };
+        let mut terms = Vec::new();
+        parser.skip_spaces();
+        if parser.peek().is_none() {
+            return Err(DiceParseError::Empty);
+        }
+        let mut negative = parser.eat(b'-');
+        if !negative {
+            parser.eat(b'+');
+        }
+        loop {
+            parser.skip_spaces();
+            terms.push(Term {
+                negative,
+                kind: parser.term()?,
+            });
+            parser.skip_spaces();
+            negative = match parser.peek() {
+                None => return Ok(DiceExpr { terms }),
+                Some(b'+') => false,
+                Some(b'-') => true,
+                Some(_) => return Err(DiceParseError::UnexpectedChar(parser.pos)),
+            };
+            parser.pos += 1;
+        }
+    }
+}
+
+impl fmt::Display for DiceExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, term) in self.terms.iter().enumerate() {
+            if term.negative {
+                write!(f, "-")?;
+            } else if i > 0 {
+                write!(f, "+")?;
+            }
+            match term.kind {
+                TermKind::Constant(n) => write!(f, "{}", n)?,
+                TermKind::Roll {
+                    count,
+                    sides,
+                    explode,
+                    keep,
+                } => {
+                    write!(f, "{}d{}", count, sides)?;
+                    if explode {
+                        write!(f, "!")?;
+                    }
+                    match keep {
+                        Some(Keep::Highest(k)) => write!(f, "kh{}", k)?,
+                        Some(Keep::Lowest(k)) => write!(f, "kl{}", k)?,
+                        None => {}
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    /// Steps past `byte`, in either case, if it's next
+    fn eat(&mut self, byte: u8) -> bool {
+        let found = self.peek().is_some_and(|b| b.eq_ignore_ascii_case(&byte));
+        self.pos += found as usize;
+        found
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn unexpected(&self) -> DiceParseError {
+        match self.peek() {
+            Some(_) => DiceParseError::UnexpectedChar(self.pos),
+            None => DiceParseError::UnexpectedEnd,
+        }
+    }
+
+    fn number(&mut self) -> Result<Option<u32>, DiceParseError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Ok(None);
+        }
+        self.bytes[start..self.pos]
+            .iter()
+            .try_fold(0u32, |n, &digit| {
+                n.checked_mul(10)?.checked_add((digit - b'0') as u32)
+            })
+            .map(Some)
+            .ok_or(DiceParseError::Overflow)
+    }
+
+    fn term(&mut self) -> Result<TermKind, DiceParseError> {
+        let count = self.number()?;
+        if !self.eat(b'd') {
+            return count
+                .map(TermKind::Constant)
+                .ok_or_else(|| self.unexpected());
+        }
+        let count = count.unwrap_or(1);
+        let sides = if self.eat(b'%') {
+            100
+        } else {
+            self.number()?.ok_or_else(|| self.unexpected())?
+        };
+        if sides == 0 {
+            return Err(DiceParseError::NoSides);
+        }
+        if count > MAX_DICE {
+            return Err(DiceParseError::TooManyDice);
+        }
+        self.skip_spaces();
+        let explode = self.eat(b'!');
+        if explode && sides == 1 {
+            return Err(DiceParseError::EndlessExplosion);
+        }
+        self.skip_spaces();
+        let keep = if self.eat(b'k') {
+            let lowest = self.eat(b'l');
+            if !lowest {
+                self.eat(b'h');
+            }
+            let k = self.number()?.ok_or_else(|| self.unexpected())?;
+            if k > count {
+                return Err(DiceParseError::KeepTooMany);
+            }
+            Some(if lowest {
+                Keep::Lowest(k)
+            } else {
+                Keep::Highest(k)
+            })
+        } else {
+            None
+        };
+        Ok(TermKind::Roll {
+            count,
+            sides,
+            explode,
+            keep,
+        })
+    }
+}
+
+/// The reason a dice expression couldn't be parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiceParseError {
+    /// The expression has no terms
+    Empty,
+    /// The character starting at this byte offset doesn't belong there
+    UnexpectedChar(usize),
+    /// The expression stops partway through a term, like `"3d"` or `"1d6+"`
+    UnexpectedEnd,
+    /// A number is too big for a `u32`
+    Overflow,
+    /// A die has 0 sides
+    NoSides,
+    /// A term rolls more than [`MAX_DICE`] dice
+    TooManyDice,
+    /// A term keeps more dice than it rolls
+    KeepTooMany,
+    /// A one-sided die explodes, so it would never stop rolling
+    EndlessExplosion,
+}
+
+impl fmt::Display for DiceParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiceParseError::Empty => write!(f, "dice expression is empty"),
+            DiceParseError::UnexpectedChar(pos) => {
+                write!(
+                    f,
+                    "unexpected character at position {} of dice expression",
+                    pos
+                )
+            }
+            DiceParseError::UnexpectedEnd => write!(f, "dice expression ends too soon"),
+            DiceParseError::Overflow => write!(f, "number in dice expression is too big"),
+            DiceParseError::NoSides => write!(f, "dice must have at least one side"),
+            DiceParseError::TooManyDice => {
+                write!(f, "cannot roll more than {} dice at once", MAX_DICE)
+            }
+            DiceParseError::KeepTooMany => write!(f, "cannot keep more dice than are rolled"),
+            DiceParseError::EndlessExplosion => write!(f, "one-sided dice cannot explode"),
+        }
+    }
+}
+
+impl core::error::Error for DiceParseError {}
+
+impl OrangeyCtx {
+    /// Parses a dice expression like `"3d6+2"` and rolls it, returning the total. See the
+    /// [`dice`](crate::dice) module for the notation. To roll the same expression many times, parse
+    /// it into a [`DiceExpr`] once instead
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::{dice::DiceParseError, OrangeyCtx};
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// assert!((3..=20).contains(&orangey_ctx.roll("3d6 + 2").unwrap()));
+    /// assert!((1..=100).contains(&orangey_ctx.roll("d%").unwrap()));
+    /// assert_eq!(orangey_ctx.roll("2d0"), Err(DiceParseError::NoSides));
+    /// assert_eq!(orangey_ctx.roll("3d6 * 2"), Err(DiceParseError::UnexpectedChar(4)));
+    ///
+    /// // Advantage: the higher of two d20s
+    /// let advantage: i64 = (0..10_000).map(|_| orangey_ctx.roll("2d20kh1").unwrap()).sum();
+    /// assert!((advantage as f64 / 1e4 - 13.825).abs() < 0.2);
+    /// ```
+    pub fn roll(&mut self, expr: &str) -> Result<i64, DiceParseError> {
+        Ok(expr.parse::<DiceExpr>()?.roll(self))
+    }
+}
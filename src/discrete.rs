@@ -0,0 +1,493 @@
+// Algorithms for the discrete distributions whose samplers are too long to sit inline in `OrangeyEngine`.
+
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::FloatExt;
+use crate::OrangeyEngine;
+use core::cmp::Ordering;
+
+/// Samples a binomial distribution, using inversion when `n * p` is small and
+/// Kachitvichyanukul & Schmeiser's BTPE otherwise
+pub(crate) fn binomial<E: OrangeyEngine + ?Sized>(ctx: &mut E, n: u64, p: f64) -> u64 {
+    // Both algorithms below are written for p <= 0.5
+    if p > 0.5 {
+        return n - binomial(ctx, n, 1.0 - p);
+    }
+    if n == 0 || p == 0.0 {
+        return 0;
+    }
+    if (n as f64) * p < 10.0 {
+        binomial_inversion(ctx, n, p)
+    } else {
+        binomial_btpe(ctx, n, p)
+    }
+}
+
+fn binomial_inversion<E: OrangeyEngine + ?Sized>(ctx: &mut E, n: u64, p: f64) -> u64 {
+    // Past this the remaining probability mass is negligible and rounding errors dominate
+    const MAX_X: u64 = 110;
+    let q = 1.0 - p;
+    let s = p / q;
+    let a = (n as f64 + 1.0) * s;
+    // q^n, written so that it holds for any n
+    let r_0 = (n as f64 * (-p).ln_1p()).exp();
+    let mut r = r_0;
+    let mut u = ctx.uniform_double();
+    let mut x = 0;
+    while u > r {
+        u -= r;
+        x += 1;
+        if x > MAX_X {
+            x = 0;
+            r = r_0;
+            u = ctx.uniform_double();
+        }
+        r *= a / x as f64 - s;
+    }
+    x
+}
+
+fn binomial_btpe<E: OrangeyEngine + ?Sized>(ctx: &mut E, n: u64, p: f64) -> u64 {
+    // Past this distance from the mode, f(y) is bounded by squeezing instead of the recurrence
+    const SQUEEZE_THRESHOLD: i64 = 20;
+    let q = 1.0 - p;
+    let n_f = n as f64;
+    let np = n_f * p;
+    let npq = np * q;
+    let f_m = np + p;
+    let m = f_m as i64;
+    // The triangle in the middle, the parallelograms either side of it, then the exponential tails
+    let p1 = (2.195 * npq.sqrt() - 4.6 * q).floor() + 0.5;
+    let x_m = m as f64 + 0.5;
+    let x_l = x_m - p1;
+    let x_r = x_m + p1;
+    let c = 0.134 + 20.5 / (15.3 + m as f64);
+    let p2 = p1 * (1.0 + 2.0 * c);
+    let lambda = |a: f64| a * (1.0 + 0.5 * a);
+    let lambda_l = lambda((f_m - x_l) / (f_m - x_l * p));
+    let lambda_r = lambda((x_r - f_m) / (x_r * q));
+    let p3 = p2 + c / lambda_l;
+    let p4 = p3 + c / lambda_r;
+
+    let y = loop {
+        let u = p4 * ctx.uniform_double();
+        let mut v = ctx.uniform_double();
+        let y;
+        if u <= p1 {
+            break (x_m - p1 * v + u) as i64;
+        } else if u <= p2 {
+            let x = x_l + (u - p1) / c;
+            v = v * c + 1.0 - (x - x_m).abs() / p1;
+            if v > 1.0 {
+                continue;
+            }
+            y = x as i64;
+        } else if u <= p3 {
+            y = (x_l + v.ln() / lambda_l) as i64;
+            if y < 0 {
+                continue;
+            }
+            v *= (u - p2) * lambda_l;
+        } else {
+            y = (x_r - v.ln() / lambda_r) as i64;
+            if y > 0 && y as u64 > n {
+                continue;
+            }
+            v *= (u - p3) * lambda_r;
+        }
+
+        let k = (y - m).abs();
+        if k <= SQUEEZE_THRESHOLD || k as f64 >= 0.5 * npq - 1.0 {
+            // Evaluate f(y) / f(m) with the recurrence, starting from the mode
+            let s = p / q;
+            let a = s * (n_f + 1.0);
+            let mut f = 1.0;
+            match m.cmp(&y) {
+                Ordering::Less => {
+                    for i in m + 1..=y {
+                        f *= a / i as f64 - s;
+                    }
+                }
+                Ordering::Greater => {
+                    for i in y + 1..=m {
+                        f /= a / i as f64 - s;
+                    }
+                }
+                Ordering::Equal => {}
+            }
+            if v > f {
+                continue;
+            }
+            break y;
+        }
+
+        // Squeeze ln(v) between bounds of ln(f(y))
+        let k = k as f64;
+        let rho = (k / npq) * ((k * (k / 3.0 + 0.625) + 1.0 / 6.0) / npq + 0.5);
+        let t = -0.5 * k * k / npq;
+        let alpha = v.ln();
+        if alpha < t - rho {
+            break y;
+        }
+        if alpha > t + rho {
+            continue;
+        }
+
+        // Final comparison against Stirling's approximation of ln(f(y))
+        let x1 = (y + 1) as f64;
+        let f1 = (m + 1) as f64;
+        let z = (n as i64 + 1 - m) as f64;
+        let w = (n as i64 - y + 1) as f64;
+        let stirling = |a: f64| {
+            let a2 = a * a;
+            (13860.0 - (462.0 - (132.0 - (99.0 - 140.0 / a2) / a2) / a2) / a2) / a / 166320.0
+        };
+        // These signs follow the GSL implementation, which corrects the original paper
+        let bound = x_m * (f1 / x1).ln()
+            + (n_f - m as f64 + 0.5) * (z / w).ln()
+            + (y - m) as f64 * (w * p / (x1 * q)).ln()
+            + stirling(f1)
+            + stirling(z)
+            - stirling(x1)
+            - stirling(w);
+        if alpha > bound {
+            continue;
+        }
+        break y;
+    };
+    y as u64
+}
+
+/// Samples a zipf distribution over `1..=n` with Hörmann & Derflinger's rejection-inversion method,
+/// which takes constant expected time no matter how large `n` is
+pub(crate) fn zipf<E: OrangeyEngine + ?Sized>(ctx: &mut E, n: u64, s: f64) -> u64 {
+    let n = n as f64;
+    // `t` is the area under the hat function, so `inv_cdf` maps [0, 1) onto [0, n)
+    let q = if s != 1.0 { 1.0 / (1.0 - s) } else { 0.0 };
+    let t = if s != 1.0 {
+        (n.powf(1.0 - s) - s) * q
+    } else {
+        1.0 + n.ln()
+    };
+    let inv_cdf = |p: f64| {
+        let pt = p * t;
+        if pt <= 1.0 {
+            pt
+        } else if s != 1.0 {
+            (pt * (1.0 - s) + s).powf(q)
+        } else {
+            (pt - 1.0).exp()
+        }
+    };
+    loop {
+        let inv_b = inv_cdf(ctx.uniform_double());
+        let x = (inv_b + 1.0).floor();
+        let mut ratio = x.powf(-s);
+        if x > 1.0 {
+            ratio *= inv_b.powf(s);
+        }
+        if ctx.uniform_double() < ratio {
+            return x as u64;
+        }
+    }
+}
+
+/// The setup for sampling a poisson distribution, which can be reused between samples
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum PoissonSampler {
+    /// Multiplies uniforms until they drop below `e^-ev`, which takes `O(ev)` time
+    Multiplication { em: f64 },
+    /// Hörmann's transformed rejection with squeeze (PTRS), which takes constant expected time
+    /// but only works for means above 10
+    Ptrs {
+        ev: f64,
+        a: f64,
+        b: f64,
+        ln_inv_alpha: f64,
+        v_r: f64,
+        ln_ev: f64,
+    },
+}
+
+impl PoissonSampler {
+    pub(crate) fn new(ev: f64) -> Self {
+        if ev <= 10.0 {
+            return PoissonSampler::Multiplication { em: (-ev).exp() };
+        }
+        let b = 0.931 + 2.53 * ev.sqrt();
+        PoissonSampler::Ptrs {
+            ev,
+            a: -0.059 + 0.02483 * b,
+            b,
+            ln_inv_alpha: (1.1239 + 1.1328 / (b - 3.4)).ln(),
+            v_r: 0.9277 - 3.6224 / (b - 2.0),
+            ln_ev: ev.ln(),
+        }
+    }
+
+    pub(crate) fn sample<E: OrangeyEngine + ?Sized>(&self, ctx: &mut E) -> u64 {
+        match *self {
+            PoissonSampler::Multiplication { em } => {
+                let mut n = 0;
+                let mut x = ctx.uniform_double();
+                while x > em {
+                    n += 1;
+                    x *= ctx.uniform_double();
+                }
+                n
+            }
+            PoissonSampler::Ptrs {
+                ev,
+                a,
+                b,
+                ln_inv_alpha,
+                v_r,
+                ln_ev,
+            } => loop {
+                let u = ctx.uniform_double() - 0.5;
+                let v = ctx.uniform_double();
+                let us = 0.5 - u.abs();
+                let k = ((2.0 * a / us + b) * u + ev + 0.43).floor();
+                if us >= 0.07 && v <= v_r {
+                    return k as u64;
+                }
+                if k < 0.0 || (us < 0.013 && v > us) {
+                    continue;
+                }
+                if v.ln() + ln_inv_alpha - (a / (us * us) + b).ln()
+                    <= -ev + k * ln_ev - ln_factorial(k as u64)
+                {
+                    return k as u64;
+                }
+            },
+        }
+    }
+}
+
+/// Computes `ln(k!)`, exactly for small `k` and with Stirling's series otherwise
+pub(crate) fn ln_factorial(k: u64) -> f64 {
+    if k < 10 {
+        return (2..=k).map(|i| (i as f64).ln()).sum();
+    }
+    let x = (k + 1) as f64;
+    let x2 = x * x;
+    (x - 0.5) * x.ln() - x + 0.5 * (2.0 * core::f64::consts::PI).ln() + 1.0 / (12.0 * x)
+        - 1.0 / (360.0 * x * x2)
+        + 1.0 / (1260.0 * x * x2 * x2)
+}
+
+/// Computes `ln(x!)`, or `ln(Γ(x + 1))`, for any real `x > -1`, shifting small `x` up with the
+/// recurrence before using Stirling's series
+fn ln_factorial_real(mut x: f64) -> f64 {
+    let mut shift = 0.0;
+    while x < 10.0 {
+        x += 1.0;
+        shift += x.ln();
+    }
+    let x = x + 1.0;
+    let x2 = x * x;
+    (x - 0.5) * x.ln() - x + 0.5 * (2.0 * core::f64::consts::PI).ln() + 1.0 / (12.0 * x)
+        - 1.0 / (360.0 * x * x2)
+        + 1.0 / (1260.0 * x * x2 * x2)
+        - shift
+}
+
+/// Computes `ln((x + d)!) - ln(x!)` for any real `x, x + d > -1`. Taking the difference of two
+/// `ln_factorial_real`s loses every digit when `x` is large and `d` small, so above 10 this
+/// rearranges Stirling's series around `ln(1 + d / (x + 1))` instead
+fn ln_factorial_shift(x: f64, d: f64) -> f64 {
+    if x.min(x + d) < 10.0 {
+        return ln_factorial_real(x + d) - ln_factorial_real(x);
+    }
+    let y = x + 1.0;
+    let series = |x: f64| {
+        let x2 = x * x;
+        1.0 / (12.0 * x) - 1.0 / (360.0 * x * x2) + 1.0 / (1260.0 * x * x2 * x2)
+    };
+    (y + d - 0.5) * (d / y).ln_1p() + d * y.ln() - d + series(y + d) - series(y)
+}
+
+/// Samples the number of successes in `draws` draws without replacement from `total` items of which
+/// `successes` are successes, using inversion (HIN) when the mode is small and Kachitvichyanukul &
+/// Schmeiser's H2PE otherwise
+pub(crate) fn hypergeometric<E: OrangeyEngine + ?Sized>(
+    ctx: &mut E,
+    total: u64,
+    successes: u64,
+    draws: u64,
+) -> u64 {
+    // Both algorithms are written for the rarer of the two groups being drawn from, with at most
+    // half of the items being drawn, so flip the problem around until that's the case
+    let swapped = successes > total - successes;
+    let (n1, n2) = if swapped {
+        (total - successes, successes)
+    } else {
+        (successes, total - successes)
+    };
+    let complemented = draws > total / 2;
+    let k = if complemented { total - draws } else { draws };
+    let x_l = k.saturating_sub(n2);
+    let x_r = k.min(n1);
+    let m = ((k as f64 + 1.0) * (n1 as f64 + 1.0) / (total as f64 + 2.0)).floor() as u64;
+    let m = m.clamp(x_l, x_r);
+    let x = if m - x_l < 10 {
+        hypergeometric_inversion(ctx, n1, n2, k, x_l, x_r)
+    } else {
+        hypergeometric_h2pe(ctx, n1, n2, k, m, x_l, x_r)
+    };
+    // Undo the flips in the opposite order: the successes left behind, then the other group's
+    let x = if complemented { n1 - x } else { x };
+    if swapped {
+        draws - x
+    } else {
+        x
+    }
+}
+
+fn hypergeometric_inversion<E: OrangeyEngine + ?Sized>(
+    ctx: &mut E,
+    n1: u64,
+    n2: u64,
+    k: u64,
+    x_l: u64,
+    x_r: u64,
+) -> u64 {
+    // C(n1, x_l) C(n2, k - x_l) / C(n1 + n2, k), with the factorials of close numbers paired up
+    let shift = |x: u64, d: u64| ln_factorial_shift(x as f64, d as f64);
+    let p_l = (shift(n1 - x_l, x_l) - ln_factorial(x_l) - shift(n2, n1)
+        + shift(n2 - k + x_l, n1 - x_l)
+        + shift(k - x_l, x_l))
+    .exp();
+    'restart: loop {
+        let mut u = ctx.uniform_double();
+        let mut p = p_l;
+        let mut x = x_l;
+        while u > p {
+            u -= p;
+            p *= (n1 - x) as f64 * (k - x) as f64 / ((x + 1) as f64 * (n2 - k + x + 1) as f64);
+            x += 1;
+            // Rounding errors left some probability over, so try again
+            if x > x_r {
+                continue 'restart;
+            }
+        }
+        return x;
+    }
+}
+
+fn hypergeometric_h2pe<E: OrangeyEngine + ?Sized>(
+    ctx: &mut E,
+    n1: u64,
+    n2: u64,
+    k: u64,
+    m: u64,
+    x_l_bound: u64,
+    x_r_bound: u64,
+) -> u64 {
+    let (n1_f, n2_f, k_f, m_f) = (n1 as f64, n2 as f64, k as f64, m as f64);
+    let total = n1_f + n2_f;
+    // ln(f(m + t) / f(m)) of the mass function, taking the offset `t` from the mode rather than
+    // `m + t` so it keeps its fraction for large `m`. The hat is built at the fractional edges of
+    // the box, so this has to work between the integers too
+    let (m_n1, m_k, m_n2) = ((n1 - m) as f64, (k - m) as f64, (n2 - k + m) as f64);
+    let ln_f = |t: f64| {
+        -(ln_factorial_shift(m_f, t)
+            + ln_factorial_shift(m_n1, -t)
+            + ln_factorial_shift(m_k, -t)
+            + ln_factorial_shift(m_n2, t))
+    };
+    // A box around the mode with an exponential tail on each side
+    let d =
+        1.5 * ((total - k_f) * k_f * n1_f * n2_f / ((total - 1.0) * total * total)).sqrt() + 0.5;
+    let x_l = m_f + 0.5 - d;
+    let x_r = m_f + 0.5 + d;
+    let k_l = ln_f(0.5 - d).exp();
+    let k_r = ln_f(d - 0.5).exp();
+    let lambda_l = -((x_l * (n2_f - k_f + x_l)) / ((n1_f - x_l + 1.0) * (k_f - x_l + 1.0))).ln();
+    let lambda_r = -(((n1_f - x_r + 1.0) * (k_f - x_r + 1.0)) / (x_r * (n2_f - k_f + x_r))).ln();
+    let p1 = 2.0 * d;
+    let p2 = p1 + k_l / lambda_l;
+    let p3 = p2 + k_r / lambda_r;
+
+    loop {
+        let u = p3 * ctx.uniform_double();
+        let mut v = ctx.uniform_double();
+        let y;
+        if u <= p1 {
+            y = (x_l + u).floor();
+        } else if u <= p2 {
+            y = (x_l + v.ln() / lambda_l).floor();
+            if y < x_l_bound as f64 {
+                continue;
+            }
+            v *= (u - p1) * lambda_l;
+        } else {
+            y = (x_r - v.ln() / lambda_r).floor();
+            if y > x_r_bound as f64 {
+                continue;
+            }
+            v *= (u - p2) * lambda_r;
+        }
+
+        if m < 100 || y <= 50.0 {
+            // Evaluate f(y) / f(m) with the recurrence
+            let mut f = 1.0;
+            let y = y as u64;
+            if m < y {
+                for i in m + 1..=y {
+                    f *= (n1 - i + 1) as f64 * (k - i + 1) as f64;
+                    f /= i as f64 * (n2 - k + i) as f64;
+                }
+            } else {
+                for i in y + 1..=m {
+                    f *= i as f64 * (n2 - k + i) as f64;
+                    f /= (n1 - i + 1) as f64 * (k - i + 1) as f64;
+                }
+            }
+            if v <= f {
+                return y;
+            }
+            continue;
+        }
+
+        // Squeeze ln(v) between bounds of ln(f(y) / f(m))
+        let y1 = y + 1.0;
+        let ym = y - m_f;
+        let yn = n1_f - y + 1.0;
+        let yk = k_f - y + 1.0;
+        let nk = n2_f - k_f + y1;
+        let r = -ym / y1;
+        let s = ym / yn;
+        let t = ym / yk;
+        let e = -ym / nk;
+        let g = yn * yk / (y1 * nk) - 1.0;
+        let dg = if g < 0.0 { 1.0 + g } else { 1.0 };
+        let gu = g * (1.0 + g * (-0.5 + g / 3.0));
+        let gl = gu - g.powi(4) / (4.0 * dg);
+        let xm = m_f + 0.5;
+        let xn = n1_f - m_f + 0.5;
+        let xk = k_f - m_f + 0.5;
+        let nm = n2_f - k_f + xm;
+        let series = |z: f64| z * (1.0 + z * (-0.5 + z / 3.0));
+        let ub = xm * series(r) + xn * series(s) + xk * series(t) + nm * series(e) + y * gu
+            - m_f * gl
+            + 0.0034;
+        let av = v.ln();
+        if av > ub {
+            continue;
+        }
+        let remainder = |x: f64, z: f64| {
+            if z < 0.0 {
+                x * z.powi(4) / (1.0 + z)
+            } else {
+                x * z.powi(4)
+            }
+        };
+        let dr = remainder(xm, r) + remainder(xn, s) + remainder(xk, t) + remainder(nm, e);
+        if av < ub - 0.25 * dr + (y + m_f) * (gl - gu) - 0.0078 {
+            return y as u64;
+        }
+        if av <= ln_f(ym) {
+            return y as u64;
+        }
+    }
+}
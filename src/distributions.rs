@@ -0,0 +1,572 @@
+//! Distributions whose parameters are checked and set up once, then sampled from repeatedly with
+//! [`OrangeyCtx::sample`](crate::OrangeyCtx::sample), or with [`OrangeyEngine::sample`] on any
+//! other engine
+
+#[cfg(all(feature = "alloc", not(feature = "std"), not(test)))]
+use crate::math::FloatExt;
+use crate::{discrete::PoissonSampler, OrangeyEngine, ParamError, RangeError};
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+use core::ops::RangeBounds;
+
+/// A distribution that can generate values of type `T`
+pub trait Distribution<T> {
+    /// Generates a value using `ctx`, which may be any engine
+    fn sample<E: OrangeyEngine>(&self, ctx: &mut E) -> T;
+}
+
+impl<T, D: Distribution<T>> Distribution<T> for &D {
+    fn sample<E: OrangeyEngine>(&self, ctx: &mut E) -> T {
+        (*self).sample(ctx)
+    }
+}
+
+/// Integers generated with equal probability from a range, like
+/// [`OrangeyCtx::try_rand_range`](crate::OrangeyCtx::try_rand_range)
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{distributions::Uniform, OrangeyCtx};
+///
+/// let mut orangey_ctx = OrangeyCtx::new();
+/// let die = Uniform::new(1..=6).unwrap();
+/// let roll = orangey_ctx.sample(&die);
+/// assert!((1..=6).contains(&roll));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Uniform {
+    low: u64,
+    // 0 stands for the full 2^64 values
+    distance: u64,
+}
+
+impl Uniform {
+    /// Sets up sampling from `range`, which may be any kind of range
+    pub fn new<R: RangeBounds<u64>>(range: R) -> Result<Self, RangeError> {
+        let (low, high) = RangeError::check_bounds(&range)?;
+        Ok(Uniform {
+            low,
+            distance: (high - low).wrapping_add(1),
+        })
+    }
+}
+
+impl Distribution<u64> for Uniform {
+    fn sample<E: OrangeyEngine>(&self, ctx: &mut E) -> u64 {
+        self.low + crate::bounded(self.distance, || ctx.next_u64())
+    }
+}
+
+/// Floats with gaussian density, like
+/// [`OrangeyCtx::gaussian_with`](crate::OrangeyCtx::gaussian_with)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Normal {
+    mean: f64,
+    std_dev: f64,
+}
+
+impl Normal {
+    /// Sets up sampling centered on `mean` with a standard deviation of `std_dev`
+    pub fn new(mean: f64, std_dev: f64) -> Result<Self, ParamError> {
+        if !(mean.is_finite() && std_dev.is_finite()) {
+            return Err(ParamError::NotFinite);
+        }
+        if std_dev < 0.0 {
+            return Err(ParamError::Negative);
+        }
+        Ok(Normal { mean, std_dev })
+    }
+}
+
+impl Distribution<f64> for Normal {
+    fn sample<E: OrangeyEngine>(&self, ctx: &mut E) -> f64 {
+        ctx.gaussian_with(self.mean, self.std_dev)
+    }
+}
+
+/// Integers matching a poisson distribution, like
+/// [`OrangeyCtx::poisson`](crate::OrangeyCtx::poisson)
+#[derive(Debug, Clone, Copy)]
+pub struct Poisson {
+    sampler: PoissonSampler,
+}
+
+impl Poisson {
+    /// Sets up sampling with an expected value of `ev`
+    pub fn new(ev: f64) -> Result<Self, ParamError> {
+        if !ev.is_finite() {
+            return Err(ParamError::NotFinite);
+        }
+        if ev < 0.0 {
+            return Err(ParamError::Negative);
+        }
+        Ok(Poisson {
+            sampler: PoissonSampler::new(ev),
+        })
+    }
+}
+
+impl Distribution<u64> for Poisson {
+    fn sample<E: OrangeyEngine>(&self, ctx: &mut E) -> u64 {
+        self.sampler.sample(ctx)
+    }
+}
+
+/// Indices into a list of weights, each generated with a probability proportional to its weight.
+/// Sampling takes `O(log n)` time
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{distributions::WeightedIndex, OrangeyCtx};
+///
+/// let mut orangey_ctx = OrangeyCtx::new();
+/// let outcomes = ["common", "rare", "never"];
+/// let weights = WeightedIndex::new(&[9.0, 1.0, 0.0]).unwrap();
+/// let outcome = outcomes[orangey_ctx.sample(&weights)];
+/// assert_ne!(outcome, "never");
+/// assert!(WeightedIndex::new(&[f64::MAX, f64::MAX]).is_err());
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedIndex {
+    cumulative: Vec<f64>,
+    total: f64,
+}
+
+#[cfg(feature = "alloc")]
+impl WeightedIndex {
+    /// Sets up sampling from `weights`, which must be non-negative with a positive, finite total
+    pub fn new(weights: &[f64]) -> Result<Self, ParamError> {
+        if weights.is_empty() {
+            return Err(ParamError::Empty);
+        }
+        if weights.iter().any(|w| !w.is_finite()) {
+            return Err(ParamError::NotFinite);
+        }
+        if weights.iter().any(|&w| w < 0.0) {
+            return Err(ParamError::Negative);
+        }
+        let cumulative: Vec<f64> = weights
+            .iter()
+            .scan(0.0, |total, &w| {
+                *total += w;
+                Some(*total)
+            })
+            .collect();
+        let total = cumulative[cumulative.len() - 1];
+        if !total.is_finite() {
+            return Err(ParamError::NotFinite);
+        }
+        if total <= 0.0 {
+            return Err(ParamError::NotPositive);
+        }
+        Ok(WeightedIndex { cumulative, total })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Distribution<usize> for WeightedIndex {
+    fn sample<E: OrangeyEngine>(&self, ctx: &mut E) -> usize {
+        loop {
+            let x = ctx.uniform_double() * self.total;
+            // Rounding can push `x` up to the total, which belongs to no index
+            let i = self.cumulative.partition_point(|&c| c <= x);
+            if i < self.cumulative.len() {
+                return i;
+            }
+        }
+    }
+}
+
+/// Indices into a list of weights, each generated with a probability proportional to its weight,
+/// using Walker's alias method. Setup takes `O(n)` time and sampling takes constant time, consuming
+/// two outputs of the generator per sample
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{distributions::WeightedAlias, OrangeyCtx};
+///
+/// let mut orangey_ctx = OrangeyCtx::new();
+/// let faces = WeightedAlias::from_counts(&[1, 1, 1, 1, 1, 5]).unwrap();
+/// let sixes = (0..1000).filter(|_| orangey_ctx.sample(&faces) == 5).count();
+/// assert!((450..550).contains(&sixes));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightedAlias {
+    index: Uniform,
+    // An output below `thresholds[i]` picks `i`, otherwise `aliases[i]` is picked
+    thresholds: Vec<u64>,
+    aliases: Vec<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl WeightedAlias {
+    /// Builds the alias table for `weights`, which must be non-negative with a positive total
+    pub fn new(weights: &[f64]) -> Result<Self, ParamError> {
+        if weights.is_empty() {
+            return Err(ParamError::Empty);
+        }
+        if weights.iter().any(|w| !w.is_finite()) {
+            return Err(ParamError::NotFinite);
+        }
+        if weights.iter().any(|&w| w < 0.0) {
+            return Err(ParamError::Negative);
+        }
+        let total: f64 = weights.iter().sum();
+        if !total.is_finite() {
+            return Err(ParamError::NotFinite);
+        }
+        if total <= 0.0 {
+            return Err(ParamError::NotPositive);
+        }
+
+        // Vose's method: pair each underfull column with an overfull one that tops it up
+        let n = weights.len();
+        let mut scaled: Vec<f64> = weights.iter().map(|w| w * n as f64 / total).collect();
+        let mut aliases: Vec<usize> = (0..n).collect();
+        let mut probabilities = vec![1.0; n];
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..n).partition(|&i| scaled[i] < 1.0);
+        while let (Some(&l), Some(&g)) = (small.last(), large.last()) {
+            small.pop();
+            probabilities[l] = scaled[l];
+            aliases[l] = g;
+            scaled[g] += scaled[l] - 1.0;
+            if scaled[g] < 1.0 {
+                large.pop();
+                small.push(g);
+            }
+        }
+        // Whatever is left over is only short of 1 by rounding error, and keeps its own index
+
+        let thresholds = probabilities
+            .iter()
+            .map(|&p| {
+                if p >= 1.0 {
+                    u64::MAX
+                } else {
+                    (p * 2f64.powi(64)) as u64
+                }
+            })
+            .collect();
+        Ok(WeightedAlias {
+            index: Uniform::new(0..n as u64).unwrap(),
+            thresholds,
+            aliases,
+        })
+    }
+
+    /// Builds the alias table for integer `weights`, which must have a positive total
+    pub fn from_counts(weights: &[u64]) -> Result<Self, ParamError> {
+        let weights: Vec<f64> = weights.iter().map(|&w| w as f64).collect();
+        Self::new(&weights)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Distribution<usize> for WeightedAlias {
+    fn sample<E: OrangeyEngine>(&self, ctx: &mut E) -> usize {
+        let i = self.index.sample(ctx) as usize;
+        if ctx.next_u64() < self.thresholds[i] {
+            i
+        } else {
+            self.aliases[i]
+        }
+    }
+}
+
+/// A Markov chain over the states `0..n`, built from a transition matrix whose row `i` gives the
+/// relative weights of moving from state `i` to each state. Every row gets its own alias table,
+/// so each step takes constant time and consumes two outputs of the generator
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{distributions::MarkovSampler, OrangeyCtx};
+///
+/// // Sunny, cloudy and rainy days
+/// let weather = MarkovSampler::new(&[
+///     [0.7, 0.2, 0.1],
+///     [0.3, 0.4, 0.3],
+///     [0.2, 0.4, 0.4],
+/// ])
+/// .unwrap();
+/// let mut orangey_ctx = OrangeyCtx::new();
+/// let days: Vec<usize> = weather.walk(&mut orangey_ctx, 0).take(10_000).collect();
+/// // The chain settles into its stationary distribution, which is sunny 6/13 of the time
+/// let sunny = days.iter().filter(|&&day| day == 0).count() as f64 / 1e4;
+/// assert!((sunny - 6.0 / 13.0).abs() < 0.02);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkovSampler {
+    rows: Vec<WeightedAlias>,
+}
+
+#[cfg(feature = "alloc")]
+impl MarkovSampler {
+    /// Builds the chain for a square transition `matrix`. Each row must be non-negative with a
+    /// positive total, and is scaled to sum to 1, so rows of counts work as well as probabilities
+    pub fn new<R: AsRef<[f64]>>(matrix: &[R]) -> Result<Self, ParamError> {
+        if matrix.iter().any(|row| row.as_ref().len() != matrix.len()) {
+            return Err(ParamError::LengthMismatch);
+        }
+        let rows = matrix
+            .iter()
+            .map(|row| WeightedAlias::new(row.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if rows.is_empty() {
+            return Err(ParamError::Empty);
+        }
+        Ok(MarkovSampler { rows })
+    }
+
+    /// Returns how many states the chain has
+    pub fn states(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Picks the state that follows `state`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `state` is not one of the chain's states
+    pub fn step<E: OrangeyEngine>(&self, ctx: &mut E, state: usize) -> usize {
+        assert!(
+            state < self.rows.len(),
+            "state {} is not in a chain of {} states",
+            state,
+            self.rows.len()
+        );
+        self.rows[state].sample(ctx)
+    }
+
+    /// Walks the chain from `start`, returning an iterator over the states it visits after it
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is not one of the chain's states
+    pub fn walk<'a, E: OrangeyEngine>(&'a self, ctx: &'a mut E, start: usize) -> MarkovWalk<'a, E> {
+        assert!(
+            start < self.rows.len(),
+            "state {} is not in a chain of {} states",
+            start,
+            self.rows.len()
+        );
+        MarkovWalk {
+            sampler: self,
+            ctx,
+            state: start,
+        }
+    }
+}
+
+/// An iterator over the states of a Markov chain, returned by [`MarkovSampler::walk`]
+#[cfg(feature = "alloc")]
+pub struct MarkovWalk<'a, E: OrangeyEngine = crate::OrangeyCtx> {
+    sampler: &'a MarkovSampler,
+    ctx: &'a mut E,
+    state: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<E: OrangeyEngine> Iterator for MarkovWalk<'_, E> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.state = self.sampler.rows[self.state].sample(self.ctx);
+        Some(self.state)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: OrangeyEngine> core::iter::FusedIterator for MarkovWalk<'_, E> {}
+
+#[cfg(feature = "alloc")]
+impl<E: OrangeyEngine> core::fmt::Debug for MarkovWalk<'_, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MarkovWalk")
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A drop table for games: each sample gives the table's guaranteed drops, followed by the
+/// results of a number of weighted rolls. A roll can land on an item, on nothing, or on a nested
+/// table, which is then sampled in turn with its own guaranteed drops and rolls. Each roll
+/// consumes two outputs of the generator, plus whatever a nested table it lands on consumes
+///
+/// Tables are put together with [`LootTable::builder`]
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{distributions::LootTable, OrangeyCtx};
+///
+/// let gems = LootTable::builder()
+///     .item(3.0, "ruby")
+///     .item(1.0, "diamond")
+///     .build()
+///     .unwrap();
+/// let chest = LootTable::builder()
+///     .guaranteed("gold")
+///     .item(5.0, "potion")
+///     .table(1.0, gems)
+///     .nothing(4.0)
+///     .rolls(2)
+///     .build()
+///     .unwrap();
+///
+/// let mut orangey_ctx = OrangeyCtx::seed_from_str("dungeon");
+/// let loot = orangey_ctx.sample(&chest);
+/// assert_eq!(loot[0], "gold");
+/// assert!(loot.len() <= 3);
+///
+/// // A gem turns up on a tenth of the rolls, and a quarter of those are diamonds
+/// let diamonds = (0..10_000)
+///     .flat_map(|_| orangey_ctx.sample(&chest))
+///     .filter(|&item| item == "diamond")
+///     .count();
+/// assert!((400..600).contains(&diamonds));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LootTable<T> {
+    guaranteed: Vec<Entry<T>>,
+    weighted: Vec<Entry<T>>,
+    picker: Option<WeightedAlias>,
+    rolls: u32,
+}
+
+/// Builds a [`LootTable`], returned by [`LootTable::builder`]
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LootTableBuilder<T> {
+    guaranteed: Vec<Entry<T>>,
+    weighted: Vec<Entry<T>>,
+    weights: Vec<f64>,
+    rolls: u32,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+enum Entry<T> {
+    Nothing,
+    Item(T),
+    Table(LootTable<T>),
+}
+
+#[cfg(feature = "alloc")]
+impl<T> LootTable<T> {
+    /// Starts an empty table that rolls once
+    pub fn builder() -> LootTableBuilder<T> {
+        LootTableBuilder {
+            guaranteed: Vec::new(),
+            weighted: Vec::new(),
+            weights: Vec::new(),
+            rolls: 1,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone> LootTable<T> {
+    /// Samples the table, adding its drops to the end of `loot`. This is what `.sample()` does,
+    /// without needing a new vector each time
+    pub fn sample_into<E: OrangeyEngine>(&self, ctx: &mut E, loot: &mut Vec<T>) {
+        for entry in &self.guaranteed {
+            entry.sample_into(ctx, loot);
+        }
+        if let Some(picker) = &self.picker {
+            for _ in 0..self.rolls {
+                self.weighted[picker.sample(ctx)].sample_into(ctx, loot);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone> Entry<T> {
+    fn sample_into<E: OrangeyEngine>(&self, ctx: &mut E, loot: &mut Vec<T>) {
+        match self {
+            Entry::Nothing => {}
+            Entry::Item(item) => loot.push(item.clone()),
+            Entry::Table(table) => table.sample_into(ctx, loot),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone> Distribution<Vec<T>> for LootTable<T> {
+    fn sample<E: OrangeyEngine>(&self, ctx: &mut E) -> Vec<T> {
+        let mut loot = Vec::new();
+        self.sample_into(ctx, &mut loot);
+        loot
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> LootTableBuilder<T> {
+    /// Adds an item that each roll lands on with a probability proportional to `weight`
+    pub fn item(mut self, weight: f64, item: T) -> Self {
+        self.weighted.push(Entry::Item(item));
+        self.weights.push(weight);
+        self
+    }
+
+    /// Adds a nested table that each roll lands on with a probability proportional to `weight`
+    pub fn table(mut self, weight: f64, table: LootTable<T>) -> Self {
+        self.weighted.push(Entry::Table(table));
+        self.weights.push(weight);
+        self
+    }
+
+    /// Adds a chance, proportional to `weight`, of each roll dropping nothing
+    pub fn nothing(mut self, weight: f64) -> Self {
+        self.weighted.push(Entry::Nothing);
+        self.weights.push(weight);
+        self
+    }
+
+    /// Adds an item that every sample drops
+    pub fn guaranteed(mut self, item: T) -> Self {
+        self.guaranteed.push(Entry::Item(item));
+        self
+    }
+
+    /// Adds a nested table that every sample also samples
+    pub fn guaranteed_table(mut self, table: LootTable<T>) -> Self {
+        self.guaranteed.push(Entry::Table(table));
+        self
+    }
+
+    /// Sets how many weighted rolls each sample makes, which is 1 unless set
+    pub fn rolls(mut self, rolls: u32) -> Self {
+        self.rolls = rolls;
+        self
+    }
+
+    /// Finishes the table. Weights must be non-negative with a positive total, unless there are
+    /// no weighted entries at all, in which case the table only gives its guaranteed drops
+    pub fn build(self) -> Result<LootTable<T>, ParamError> {
+        let picker = if self.weights.is_empty() {
+            None
+        } else {
+            Some(WeightedAlias::new(&self.weights)?)
+        };
+        Ok(LootTable {
+            guaranteed: self.guaranteed,
+            weighted: self.weighted,
+            picker,
+            rolls: self.rolls,
+        })
+    }
+}
@@ -0,0 +1,249 @@
+// The extended generator from section 7 of the PCG paper. A table of 64-bit counters, each read
+// through an invertible permutation, is xored into the outputs of an ordinary `OrangeyCtx` stream.
+// The low bits of the base state pick the table entry, so runs of `EXT_SIZE` outputs line up with
+// the table, and the table ticks over like an odometer every `2^TICK_BITS` base steps.
+
+use crate::{OrangeyCtx, OrangeyEngine, RangeError};
+use core::ops::{Range, RangeBounds};
+
+const EXT_BITS: u32 = 5;
+const EXT_SIZE: usize = 1 << EXT_BITS;
+const TICK_BITS: u32 = 16;
+// Each table entry is the state of its own 64-bit LCG, on stream `2 * i + 1`
+const EXT_MUL: u64 = 6364136223846793005;
+
+/// A generator with a period of 2^2064 whose outputs are 32-dimensionally equidistributed: over
+/// the full period, every sequence of 32 outputs starting at a multiple of 32 turns up equally
+/// often. This suits simulations drawing trillions of values, where the 2^128 period of
+/// [`OrangeyCtx`] and the gaps in its high-dimensional tuples might matter
+///
+/// Each output costs about as much as an `OrangeyCtx` output plus a table lookup. The generator
+/// carries 256 bytes of extension table, so it is `Clone` but not `Copy`
+///
+/// # Examples
+///
+/// ```
+/// use orangey::OrangeyExtCtx;
+///
+/// let mut orangey_ctx = OrangeyExtCtx::seed_from_bytes(b"galaxy survey");
+/// let ahead = orangey_ctx.peek(1 << 20);
+/// orangey_ctx.skip(1 << 20);
+/// assert_eq!(orangey_ctx.rand(), ahead);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrangeyExtCtx {
+    state: u128,
+    inc: u128,
+    table: [u64; EXT_SIZE],
+}
+
+impl OrangeyExtCtx {
+    /// Makes a generator from the same fixed seed as [`OrangeyCtx::new`]
+    pub fn new() -> Self {
+        Self::from_base(OrangeyCtx::new())
+    }
+
+    /// Makes a generator seeded from arbitrary bytes, like [`OrangeyCtx::seed_from_bytes`]
+    pub fn seed_from_bytes(bytes: &[u8]) -> Self {
+        Self::from_base(OrangeyCtx::seed_from_bytes(bytes))
+    }
+
+    /// Seeds the generator with new initial state and sequence values. The extension table is
+    /// filled from the first outputs of the reseeded base stream
+    pub fn srand(&mut self, initstate: u128, initseq: u128) {
+        let mut base = OrangeyCtx::new();
+        base.srand(initstate, initseq);
+        *self = Self::from_base(base);
+    }
+
+    fn from_base(mut base: OrangeyCtx) -> Self {
+        let mut table = [0; EXT_SIZE];
+        table.iter_mut().for_each(|entry| *entry = base.rand());
+        OrangeyExtCtx {
+            state: base.state,
+            inc: base.inc,
+            table,
+        }
+    }
+
+    /// Returns a random 64-bit number
+    #[inline]
+    pub fn rand(&mut self) -> u64 {
+        self.state = OrangeyCtx::advance(self.state, 1, OrangeyCtx::MUL, self.inc);
+        if self.state & ((1 << TICK_BITS) - 1) == 0 {
+            self.advance_table(1);
+        }
+        let entry = self.table[self.state as usize % EXT_SIZE];
+        OrangeyCtx::output(self.state) ^ Self::permute(entry)
+    }
+
+    /// Runs the generator twice and returns a 128-bit random number, with the first output in the high bits
+    pub fn rand_u128(&mut self) -> u128 {
+        let high = self.rand() as u128;
+        (high << 64) | self.rand() as u128
+    }
+
+    /// Jumps `delta` values ahead in the rng stream, advancing the extension table by however many
+    /// times it would have ticked over on the way. This takes `O(log delta)` time
+    pub fn skip(&mut self, delta: u128) {
+        // The distance to the next state with its low bits clear, settled bit by bit like
+        // `OrangeyCtx::distance` but stopping once those bits match
+        let mut cur_state = self.state;
+        let mut cur_mult = OrangeyCtx::MUL;
+        let mut cur_plus = self.inc;
+        let mut to_tick = 0;
+        for bit in 0..TICK_BITS {
+            if cur_state & (1 << bit) != 0 {
+                cur_state = cur_state.wrapping_mul(cur_mult).wrapping_add(cur_plus);
+                to_tick |= 1 << bit;
+            }
+            cur_plus = cur_plus.wrapping_mul(cur_mult.wrapping_add(1));
+            cur_mult = cur_mult.wrapping_mul(cur_mult);
+        }
+        if to_tick == 0 {
+            to_tick = 1 << TICK_BITS;
+        }
+        if delta >= to_tick {
+            self.advance_table(1 + ((delta - to_tick) >> TICK_BITS));
+        }
+        self.state = OrangeyCtx::advance(self.state, delta, OrangeyCtx::MUL, self.inc);
+    }
+
+    /// Peeks at the `delta`-th value ahead in the rng stream, so `.peek(0)` is what `.rand()` returns next.
+    /// This doesn't modify the rng state
+    pub fn peek(&self, delta: u128) -> u64 {
+        let mut ahead = self.clone();
+        ahead.skip(delta);
+        ahead.rand()
+    }
+
+    /// Fills `buf` with random bytes, taken from the little-endian bytes of consecutive outputs
+    pub fn fill_bytes(&mut self, buf: &mut [u8]) {
+        for chunk in buf.chunks_mut(8) {
+            chunk.copy_from_slice(&self.rand().to_le_bytes()[..chunk.len()]);
+        }
+    }
+
+    /// Generates a number in the range given
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty. Use `.try_rand_range()` to handle that case instead
+    pub fn rand_range(&mut self, range: Range<u64>) -> u64 {
+        match self.try_rand_range(range) {
+            Ok(r) => r,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Generates a number in the range given, which may be any kind of range,
+    /// including the full `0..=u64::MAX`
+    pub fn try_rand_range<R: RangeBounds<u64>>(&mut self, range: R) -> Result<u64, RangeError> {
+        let (low, high) = RangeError::check_bounds(&range)?;
+        Ok(low + crate::bounded((high - low).wrapping_add(1), || self.rand()))
+    }
+
+    /// Generates a float in the range [0, 1) with uniform density
+    pub fn uniform_double(&mut self) -> f64 {
+        const MASK: u64 = 0x000FFFFFFFFFFFFF;
+        const S_EXP: u64 = 0x3FF0000000000000;
+        f64::from_bits((self.rand() & MASK) | S_EXP) - 1.0
+    }
+
+    /// Ticks the table over `ticks` times. Entry `i` carries into entry `i + 1` each time its
+    /// LCG passes through 0, which happens exactly once per 2^64 steps
+    fn advance_table(&mut self, ticks: u128) {
+        let mut carry = ticks;
+        for (i, entry) in self.table.iter_mut().enumerate() {
+            if carry == 0 {
+                break;
+            }
+            let plus = 2 * i as u64 + 1;
+            let to_zero = match crate::pcg32::distance(*entry, 0, EXT_MUL, plus) {
+                0 => 1 << 64,
+                d => d as u128,
+            };
+            let next_carry = if carry >= to_zero {
+                1 + ((carry - to_zero) >> 64)
+            } else {
+                0
+            };
+            *entry = crate::pcg32::advance(*entry, carry as u64, EXT_MUL, plus);
+            carry = next_carry;
+        }
+    }
+
+    /// PCG's RXS-M-XS permutation, which is invertible so every table state gives a distinct value
+    fn permute(entry: u64) -> u64 {
+        let word = ((entry >> ((entry >> 59) + 5)) ^ entry).wrapping_mul(12605985483714917081);
+        (word >> 43) ^ word
+    }
+}
+
+impl core::fmt::Debug for OrangeyExtCtx {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OrangeyExtCtx")
+            .field("state", &format_args!("{:#034x}", self.state))
+            .field("inc", &format_args!("{:#034x}", self.inc))
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for OrangeyExtCtx {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl OrangeyExtCtx {
+    /// Overwrites the generator's state and extension table with zeros, like
+    /// [`OrangeyCtx::zeroize`]. With the `zeroize` feature this also happens when the generator is
+    /// dropped
+    pub fn zeroize(&mut self) {
+        crate::wipe(&mut self.state);
+        crate::wipe(&mut self.inc);
+        crate::wipe(&mut self.table);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for OrangeyExtCtx {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl OrangeyEngine for OrangeyExtCtx {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rand()
+    }
+
+    fn skip(&mut self, delta: u128) {
+        OrangeyExtCtx::skip(self, delta)
+    }
+
+    fn peek_raw(&self, delta: u128) -> u64 {
+        self.peek(delta)
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::RngCore for OrangeyExtCtx {
+    /// The low half of one output, as [`OrangeyEngine::next_u32`] takes
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.rand() as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rand()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        OrangeyExtCtx::fill_bytes(self, dst)
+    }
+}
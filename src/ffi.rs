@@ -0,0 +1,310 @@
+//! A C interface shaped like the original Orangey C header, so C and C++ projects using it can
+//! link against this crate instead. The generator is a plain struct the caller owns, on the stack
+//! or anywhere else, and every function takes a pointer to it. Its layout is fixed by `#[repr(C)]`
+//! and only changes along with [`STREAM_VERSION`](crate::STREAM_VERSION)
+//!
+//! C has no portable 128-bit integer, so 128-bit values are passed as a high and a low `uint64_t`.
+//! On 64-bit targets, where GCC and Clang have `__uint128_t` and Rust's `u128` matches it, the
+//! functions that take 128-bit values also come in a version ending in `128` that takes them
+//! whole, like `orangey_skip128`
+//!
+//! Every pointer must be null or valid. A null generator is ignored, and the functions given one
+//! return 0. A panic can't unwind into C, so the functions that panic in Rust, like
+//! `orangey_rand_range` on an empty range, abort the process instead
+//!
+//! To build a library, enable the `ffi` feature along with `std`, which provides the panic handler
+//! a C library needs, and ask for a C crate type. A header can then be generated with cbindgen:
+//!
+//! ```text
+//! cargo rustc --lib --release --features ffi --crate-type cdylib
+//! cbindgen --lang c --output orangey.h
+//! ```
+//!
+//! # Examples
+//!
+//! The functions are ordinary Rust functions too, which is how these examples call them
+//!
+//! ```
+//! use orangey::{ffi::*, OrangeyCtx};
+//!
+//! let mut ctx = orangey_new();
+//! let mut same = OrangeyCtx::new();
+//! // SAFETY: `ctx` is a valid generator
+//! unsafe {
+//!     orangey_srand(&mut ctx, 0, 42, 0, 54);
+//!     same.srand(42, 54);
+//!     assert_eq!(orangey_rand(&mut ctx), same.rand());
+//!     assert_eq!(orangey_gaussian(&mut ctx), same.gaussian());
+//!     assert_eq!(orangey_gaussian(&mut ctx), same.gaussian());
+//!     assert_eq!(orangey_rand(core::ptr::null_mut()), 0);
+//! }
+//! ```
+
+use crate::OrangeyCtx;
+
+/// The generator's state, as C sees it
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct orangey_ctx {
+    /// The high 64 bits of the LCG state
+    pub state_hi: u64,
+    /// The low 64 bits of the LCG state
+    pub state_lo: u64,
+    /// The high 64 bits of the stream increment
+    pub inc_hi: u64,
+    /// The low 64 bits of the stream increment, which is always odd
+    pub inc_lo: u64,
+    /// The second deviate of the last pair `orangey_gaussian` made, if `has_gaussian_spare` is set
+    pub gaussian_spare: f64,
+    /// Whether `orangey_gaussian` has a deviate saved for its next call
+    pub has_gaussian_spare: bool,
+}
+
+fn join(hi: u64, lo: u64) -> u128 {
+    (hi as u128) << 64 | lo as u128
+}
+
+impl From<&orangey_ctx> for OrangeyCtx {
+    fn from(ctx: &orangey_ctx) -> Self {
+        let mut orangey_ctx = OrangeyCtx::from_seed_parts(
+            join(ctx.state_hi, ctx.state_lo),
+            join(ctx.inc_hi, ctx.inc_lo),
+        );
+        orangey_ctx.gaussian_spare = ctx.has_gaussian_spare.then_some(ctx.gaussian_spare);
+        orangey_ctx
+    }
+}
+
+impl From<&OrangeyCtx> for orangey_ctx {
+    /// Takes the state, stream and gaussian spare of `orangey_ctx`. Bits kept back by the narrow
+    /// `rand_u*` methods have nowhere to go, and are dropped
+    fn from(orangey_ctx: &OrangeyCtx) -> Self {
+        orangey_ctx {
+            state_hi: (orangey_ctx.state >> 64) as u64,
+            state_lo: orangey_ctx.state as u64,
+            inc_hi: (orangey_ctx.inc >> 64) as u64,
+            inc_lo: orangey_ctx.inc as u64,
+            gaussian_spare: orangey_ctx.gaussian_spare.unwrap_or(0.0),
+            has_gaussian_spare: orangey_ctx.gaussian_spare.is_some(),
+        }
+    }
+}
+
+/// Runs `f` on the generator `ctx` holds, then stores where it left off back in `ctx`. A null
+/// `ctx` is skipped, returning the default value of `T`
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid `orangey_ctx`
+unsafe fn with<T: Default>(ctx: *mut orangey_ctx, f: impl FnOnce(&mut OrangeyCtx) -> T) -> T {
+    // SAFETY: the caller promises `ctx` is null or valid
+    let Some(ctx) = (unsafe { ctx.as_mut() }) else {
+        return T::default();
+    };
+    let mut orangey_ctx = OrangeyCtx::from(&*ctx);
+    let result = f(&mut orangey_ctx);
+    *ctx = orangey_ctx::from(&orangey_ctx);
+    result
+}
+
+/// Returns a generator with the same fixed seed as [`OrangeyCtx::new`]
+#[no_mangle]
+pub extern "C" fn orangey_new() -> orangey_ctx {
+    orangey_ctx::from(&OrangeyCtx::new())
+}
+
+/// Seeds the generator like [`OrangeyCtx::srand`]
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid `orangey_ctx`
+#[no_mangle]
+pub unsafe extern "C" fn orangey_srand(
+    ctx: *mut orangey_ctx,
+    initstate_hi: u64,
+    initstate_lo: u64,
+    initseq_hi: u64,
+    initseq_lo: u64,
+) {
+    // SAFETY: the caller's promise is passed on
+    unsafe {
+        orangey_srand128(
+            ctx,
+            join(initstate_hi, initstate_lo),
+            join(initseq_hi, initseq_lo),
+        )
+    }
+}
+
+/// Returns a random 64-bit number
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid `orangey_ctx`
+#[no_mangle]
+pub unsafe extern "C" fn orangey_rand(ctx: *mut orangey_ctx) -> u64 {
+    // SAFETY: the caller's promise is passed on
+    unsafe { with(ctx, OrangeyCtx::rand) }
+}
+
+/// Jumps `delta` values ahead in the stream, like [`OrangeyCtx::skip`]
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid `orangey_ctx`
+#[no_mangle]
+pub unsafe extern "C" fn orangey_skip(ctx: *mut orangey_ctx, delta_hi: u64, delta_lo: u64) {
+    // SAFETY: the caller's promise is passed on
+    unsafe { orangey_skip128(ctx, join(delta_hi, delta_lo)) }
+}
+
+/// Jumps `delta` values back in the stream, like [`OrangeyCtx::skip_back`]
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid `orangey_ctx`
+#[no_mangle]
+pub unsafe extern "C" fn orangey_skip_back(ctx: *mut orangey_ctx, delta_hi: u64, delta_lo: u64) {
+    // SAFETY: the caller's promise is passed on
+    unsafe { orangey_skip_back128(ctx, join(delta_hi, delta_lo)) }
+}
+
+/// Returns the `delta`-th value ahead without moving the generator, like [`OrangeyCtx::peek`]
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid `orangey_ctx`
+#[no_mangle]
+pub unsafe extern "C" fn orangey_peek(
+    ctx: *const orangey_ctx,
+    delta_hi: u64,
+    delta_lo: u64,
+) -> u64 {
+    // SAFETY: the caller's promise is passed on
+    unsafe { orangey_peek128(ctx, join(delta_hi, delta_lo)) }
+}
+
+/// Returns a number from `start` up to but not including `end`, like [`OrangeyCtx::rand_range`]
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid `orangey_ctx`
+///
+/// # Panics
+///
+/// Aborts the process if the range is empty
+#[no_mangle]
+pub unsafe extern "C" fn orangey_rand_range(ctx: *mut orangey_ctx, start: u64, end: u64) -> u64 {
+    // SAFETY: the caller's promise is passed on
+    unsafe { with(ctx, |orangey_ctx| orangey_ctx.rand_range(start..end)) }
+}
+
+/// Returns a float in [0, 1), like [`OrangeyCtx::uniform_double`]
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid `orangey_ctx`
+#[no_mangle]
+pub unsafe extern "C" fn orangey_uniform_double(ctx: *mut orangey_ctx) -> f64 {
+    // SAFETY: the caller's promise is passed on
+    unsafe { with(ctx, OrangeyCtx::uniform_double) }
+}
+
+/// Returns a float in [0, 1) that can be any float in that range, like
+/// [`OrangeyCtx::all_doubles`]
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid `orangey_ctx`
+#[no_mangle]
+pub unsafe extern "C" fn orangey_all_doubles(ctx: *mut orangey_ctx) -> f64 {
+    // SAFETY: the caller's promise is passed on
+    unsafe { with(ctx, OrangeyCtx::all_doubles) }
+}
+
+/// Returns a float with standard gaussian density, like [`OrangeyCtx::gaussian`]
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid `orangey_ctx`
+#[no_mangle]
+pub unsafe extern "C" fn orangey_gaussian(ctx: *mut orangey_ctx) -> f64 {
+    // SAFETY: the caller's promise is passed on
+    unsafe { with(ctx, OrangeyCtx::gaussian) }
+}
+
+/// Returns a count with a poisson distribution, like [`OrangeyCtx::poisson`]
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid `orangey_ctx`
+#[no_mangle]
+pub unsafe extern "C" fn orangey_poisson(ctx: *mut orangey_ctx, ev: f64) -> u64 {
+    // SAFETY: the caller's promise is passed on
+    unsafe { with(ctx, |orangey_ctx| orangey_ctx.poisson(ev)) }
+}
+
+/// Fills `len` bytes at `buf` with random bytes, like [`OrangeyCtx::fill_bytes`]
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid `orangey_ctx`, and `buf` must point to `len` bytes that
+/// can be written, unless `len` is 0, when it may be null
+#[no_mangle]
+pub unsafe extern "C" fn orangey_fill_bytes(ctx: *mut orangey_ctx, buf: *mut u8, len: usize) {
+    if len == 0 {
+        return;
+    }
+    // SAFETY: the caller promises `buf` is valid for `len` bytes
+    let buf = unsafe { core::slice::from_raw_parts_mut(buf, len) };
+    // SAFETY: the caller's promise is passed on
+    unsafe { with(ctx, |orangey_ctx| orangey_ctx.fill_bytes(buf)) }
+}
+
+/// Seeds the generator like [`OrangeyCtx::srand`], from whole 128-bit values
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid `orangey_ctx`
+#[cfg_attr(target_pointer_width = "64", no_mangle)]
+pub unsafe extern "C" fn orangey_srand128(ctx: *mut orangey_ctx, initstate: u128, initseq: u128) {
+    // SAFETY: the caller's promise is passed on
+    unsafe { with(ctx, |orangey_ctx| orangey_ctx.srand(initstate, initseq)) }
+}
+
+/// Jumps `delta` values ahead in the stream, like [`OrangeyCtx::skip`], with a whole 128-bit
+/// `delta`
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid `orangey_ctx`
+#[cfg_attr(target_pointer_width = "64", no_mangle)]
+pub unsafe extern "C" fn orangey_skip128(ctx: *mut orangey_ctx, delta: u128) {
+    // SAFETY: the caller's promise is passed on
+    unsafe { with(ctx, |orangey_ctx| orangey_ctx.skip(delta)) }
+}
+
+/// Jumps `delta` values back in the stream, like [`OrangeyCtx::skip_back`], with a whole 128-bit
+/// `delta`
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid `orangey_ctx`
+#[cfg_attr(target_pointer_width = "64", no_mangle)]
+pub unsafe extern "C" fn orangey_skip_back128(ctx: *mut orangey_ctx, delta: u128) {
+    // SAFETY: the caller's promise is passed on
+    unsafe { with(ctx, |orangey_ctx| orangey_ctx.skip_back(delta)) }
+}
+
+/// Returns the `delta`-th value ahead without moving the generator, like [`OrangeyCtx::peek`],
+/// with a whole 128-bit `delta`
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid `orangey_ctx`
+#[cfg_attr(target_pointer_width = "64", no_mangle)]
+pub unsafe extern "C" fn orangey_peek128(ctx: *const orangey_ctx, delta: u128) -> u64 {
+    // SAFETY: the caller promises `ctx` is null or valid
+    unsafe { ctx.as_ref() }.map_or(0, |ctx| OrangeyCtx::from(ctx).peek(delta))
+}
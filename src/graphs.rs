@@ -0,0 +1,176 @@
+//! Random graphs, for generating benchmark inputs and test cases. Each function returns an edge
+//! list over the nodes `0..n`, with every edge given once as `(u, v)` where `u < v`, so the same
+//! seed always gives the same graph in the same order. Every function takes any [`OrangeyEngine`]
+
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::FloatExt;
+use crate::OrangeyEngine;
+use alloc::{vec, vec::Vec};
+
+/// Generates an Erdős–Rényi graph on `n` nodes, where each of the `n(n - 1)/2` possible edges is
+/// present independently with probability `p`. This uses Batagelj and Brandes' method, which jumps
+/// straight from one edge to the next, so it takes time and outputs in proportion to the number
+/// of edges rather than the number of pairs: one output per edge, plus one. Edges come out sorted
+/// by `v`, then by `u`
+///
+/// # Panics
+///
+/// Panics if `p` is not in the range [0, 1]
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{graphs, OrangeyCtx};
+///
+/// let mut orangey_ctx = OrangeyCtx::new();
+/// let edges = graphs::erdos_renyi(&mut orangey_ctx, 1000, 0.01);
+/// // About p * n * (n - 1) / 2 of them
+/// assert!((4700..5300).contains(&edges.len()));
+/// assert!(edges.iter().all(|&(u, v)| u < v && v < 1000));
+/// ```
+pub fn erdos_renyi<E: OrangeyEngine>(ctx: &mut E, n: usize, p: f64) -> Vec<(usize, usize)> {
+    assert!(
+        (0.0..=1.0).contains(&p),
+        "probability {} is not in [0, 1]",
+        p
+    );
+    let mut edges = Vec::new();
+    if p == 0.0 {
+        return edges;
+    }
+    let ln_q = (-p).ln_1p();
+    // The next candidate edge is `(w, v)`, with pairs ordered by `v` and then by `w`
+    let (mut v, mut w) = (1, 0u64);
+    loop {
+        // The number of pairs skipped before the next edge is geometric. Casting saturates, and
+        // a huge skip just runs off the end
+        let skip = ((1.0 - ctx.uniform_double()).ln() / ln_q).floor();
+        w = w.saturating_add(skip as u64);
+        while w >= v as u64 && v < n {
+            w -= v as u64;
+            v += 1;
+        }
+        if v >= n {
+            return edges;
+        }
+        edges.push((w as usize, v));
+        w += 1;
+    }
+}
+
+/// Generates a spanning tree of the complete graph on `n` nodes, picked uniformly from all
+/// `n^(n - 2)` of them, by decoding a random Prüfer sequence. The tree has `n - 1` edges, or none
+/// if `n` is below 2. This takes `n - 2` bounded integers, as `.rand_range()` would
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{graphs, OrangeyCtx};
+///
+/// let mut orangey_ctx = OrangeyCtx::new();
+/// let edges = graphs::random_spanning_tree(&mut orangey_ctx, 100);
+/// assert_eq!(edges.len(), 99);
+///
+/// // Joining up the ends of every edge leaves a single component
+/// let mut parent: Vec<usize> = (0..100).collect();
+/// fn root(parent: &mut [usize], mut i: usize) -> usize {
+///     while parent[i] != i {
+///         i = parent[i];
+///     }
+///     i
+/// }
+/// for (u, v) in edges {
+///     let (ru, rv) = (root(&mut parent, u), root(&mut parent, v));
+///     assert_ne!(ru, rv, "a tree has no cycles");
+///     parent[ru] = rv;
+/// }
+/// ```
+pub fn random_spanning_tree<E: OrangeyEngine>(ctx: &mut E, n: usize) -> Vec<(usize, usize)> {
+    if n < 2 {
+        return Vec::new();
+    }
+    let sequence: Vec<usize> = (0..n - 2)
+        .map(|_| ctx.rand_range(0..n as u64) as usize)
+        .collect();
+    // Each node appears in the sequence one time fewer than its degree
+    let mut degree = vec![1; n];
+    sequence.iter().for_each(|&x| degree[x] += 1);
+
+    // Repeatedly join the smallest leaf to the next node of the sequence
+    let mut edges = Vec::with_capacity(n - 1);
+    let mut next = degree.iter().position(|&d| d == 1).unwrap();
+    let mut leaf = next;
+    for &x in &sequence {
+        edges.push((leaf.min(x), leaf.max(x)));
+        degree[x] -= 1;
+        if degree[x] == 1 && x < next {
+            // Removing the leaf made `x` a leaf below the scan, so it's the smallest one now
+            leaf = x;
+        } else {
+            next += 1;
+            while degree[next] != 1 {
+                next += 1;
+            }
+            leaf = next;
+        }
+    }
+    edges.push((leaf, n - 1));
+    edges
+}
+
+/// Generates a Barabási–Albert graph on `n` nodes, whose degrees follow a power law like many
+/// real networks do. Starting from `m` unconnected nodes, each new node joins up with `m` distinct
+/// earlier nodes, picked with probability proportional to their degree, giving `m * (n - m)` edges
+/// in all. The first new node joins every starting node without using the generator, and each
+/// later pick takes one bounded integer, redrawn if it lands on a node already picked
+///
+/// # Panics
+///
+/// Panics if `m` is 0 or not less than `n`
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{graphs, OrangeyCtx};
+///
+/// let mut orangey_ctx = OrangeyCtx::new();
+/// let edges = graphs::barabasi_albert(&mut orangey_ctx, 10_000, 3);
+/// assert_eq!(edges.len(), 3 * (10_000 - 3));
+///
+/// let mut degree = vec![0; 10_000];
+/// for (u, v) in edges {
+///     degree[u] += 1;
+///     degree[v] += 1;
+/// }
+/// // Early nodes become hubs
+/// assert!(degree.iter().max().unwrap() > &100);
+/// ```
+pub fn barabasi_albert<E: OrangeyEngine>(ctx: &mut E, n: usize, m: usize) -> Vec<(usize, usize)> {
+    assert!(m > 0, "new nodes must join at least 1 other node");
+    assert!(
+        m < n,
+        "cannot join {} nodes to each new node with only {} nodes",
+        m,
+        n
+    );
+    let mut edges = Vec::with_capacity(m * (n - m));
+    // Every node appears here once per edge it has, so a uniform pick is proportional to degree
+    let mut ends: Vec<usize> = Vec::with_capacity(2 * m * (n - m));
+    let mut targets: Vec<usize> = (0..m).collect();
+    for node in m..n {
+        if node > m {
+            targets.clear();
+            while targets.len() < m {
+                let pick = ends[ctx.rand_range(0..ends.len() as u64) as usize];
+                if !targets.contains(&pick) {
+                    targets.push(pick);
+                }
+            }
+        }
+        for &target in &targets {
+            edges.push((target, node));
+            ends.extend([target, node]);
+        }
+    }
+    edges
+}
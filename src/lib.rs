@@ -7,23 +7,378 @@
 // You should have received a copy of the GNU General Public License along with this library.
 // If not, see <https://www.gnu.org/licenses/>.
 
-use std::{num::Wrapping, ops::Range};
+#![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("orangey needs either the `std` or the `libm` feature for floating point math");
+
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String, vec, vec::Vec};
+use core::{
+    num::Wrapping,
+    ops::{Bound, Range, RangeBounds, RangeInclusive},
+};
+use distributions::Distribution;
+#[cfg(all(not(feature = "std"), not(test)))]
+use math::FloatExt;
+use permutation::OutputPermutation;
+
+#[cfg(feature = "alloc")]
+pub mod dice;
+mod discrete;
+pub mod distributions;
+mod ext;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "alloc")]
+pub mod graphs;
+// The test harness links std, whose inherent float methods then shadow these
+#[cfg(all(not(feature = "std"), not(test)))]
+mod math;
+pub mod monte_carlo;
+#[cfg(feature = "alloc")]
+pub mod namegen;
+pub mod noise;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "alloc")]
+pub mod password;
+mod pcg32;
+pub mod permutation;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "alloc")]
+pub mod quasi;
+#[cfg(feature = "alloc")]
+pub mod resample;
+#[cfg(feature = "alloc")]
+pub mod sampling;
+#[cfg(feature = "secure")]
+mod secure;
+mod seed;
+#[cfg(feature = "simd")]
+mod simd;
+pub mod test_vectors;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod ziggurat;
+
+pub use ext::OrangeyExtCtx;
+pub use pcg32::Orangey32Ctx;
+pub use permutation::OrangeyCore;
+#[cfg(feature = "secure")]
+pub use secure::SecureOrangeyCtx;
+
+/// The version of the sequences the generators produce. It goes up whenever a release changes the
+/// results any method gives from a given seed, including by changing how many outputs it consumes,
+/// and [`test_vectors`] holds the sequences of the current version. Store it alongside saved seeds
+/// to tell whether they will still replay the same run
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{OrangeyCtx, STREAM_VERSION};
+///
+/// let saved = (STREAM_VERSION, *b"level seed");
+/// if saved.0 == STREAM_VERSION {
+///     let mut orangey_ctx = OrangeyCtx::seed_from_bytes(&saved.1);
+///     let _replayed = orangey_ctx.rand();
+/// }
+/// ```
+pub const STREAM_VERSION: u32 = 1;
+
+/// A PCG generator with 128 bits of state. Generators compare equal when all of their state
+/// matches, including buffered values and saved checkpoints
+///
+/// # Stream consumption
+///
+/// Every method takes a fixed number of outputs from the stream, or a fixed number per attempt
+/// for the ones that reject and retry, and says which in its docs. These counts are kept stable
+/// between releases like the values themselves, since changing one shifts every value drawn
+/// after it. In short:
+///
+/// - `.rand()`, `.uniform_double()` and the samplers built on a single uniform, such as
+///   `.exponential()` and `.angle()`, take exactly one output
+/// - `.rand_u32()`, `.rand_u16()`, `.rand_u8()` and `.rand_bool()` share a buffer of bits and take
+///   one output each time it runs dry
+/// - bounded integers, from `.rand_range()`, `.gen_range()` and the choosing and shuffling methods,
+///   take one output per attempt and almost always succeed on the first
+/// - `.gaussian()` takes two outputs per attempt for the first deviate of each pair and none for
+///   the second
+///
+/// `.draws_consumed()` measures it for any call
+///
+/// # Examples
+///
+/// ```
+/// use orangey::OrangeyCtx;
+///
+/// let mut orangey_ctx = OrangeyCtx::new();
+/// let snapshot = orangey_ctx.clone();
+/// orangey_ctx.rand();
+/// assert_ne!(orangey_ctx, snapshot);
+/// orangey_ctx.skip_back(1);
+/// assert_eq!(orangey_ctx, snapshot);
+/// println!("{:?}", snapshot);
+///
+/// // Jumping drops the bits `.rand_u8()` kept, so these end up in the same place
+/// let (mut a, mut b) = (OrangeyCtx::new(), OrangeyCtx::new());
+/// a.rand_u8();
+/// a.skip(1);
+/// b.rand();
+/// b.skip(1);
+/// assert_eq!(a, b);
+/// ```
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OrangeyCtx {
     state: u128,
     inc: u128,
+    // Unused high bits of the last output drawn by the narrow `rand_u*` methods
+    buffer: u64,
+    buffered_bits: u32,
+    // The second deviate of the last pair generated by `gaussian`
+    gaussian_spare: Option<f64>,
+    // States saved by `push_state`, newest last
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    checkpoints: Vec<OrangeyCtx>,
+    // The state when the generator was last seeded, which `values_generated` counts from
+    origin: u128,
 }
 
 impl OrangeyCtx {
-    pub fn new() -> Self {
+    const NEW_STATE: u128 = 0xce84809586cf8d1f17e1e9805a1b4141;
+    const NEW_INC: u128 = 0xb0a3e85a992afe5a280af6fdeecf029f;
+
+    pub const fn new() -> Self {
+        Self::from_seed_parts(Self::NEW_STATE, Self::NEW_INC)
+    }
+
+    /// Makes a generator with exactly the given state and stream increment, without the mixing
+    /// `.srand()` does. The increment must be odd, so its lowest bit is always taken as set
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// // Worked out by the compiler, so startup doesn't pay for the jump
+    /// const LEVEL_3: OrangeyCtx = OrangeyCtx::from_seed_parts(0x5eed, 0x0dd).advanced(1 << 40);
+    ///
+    /// let mut rng = LEVEL_3;
+    /// let mut slow = OrangeyCtx::from_seed_parts(0x5eed, 0x0dd);
+    /// slow.skip(1 << 40);
+    /// assert_eq!(rng.rand(), slow.rand());
+    /// ```
+    pub const fn from_seed_parts(state: u128, inc: u128) -> Self {
         OrangeyCtx {
-            state: 0xce84809586cf8d1f17e1e9805a1b4141,
-            inc: 0xb0a3e85a992afe5a280af6fdeecf029f,
+            state,
+            inc: inc | 1,
+            buffer: 0,
+            buffered_bits: 0,
+            gaussian_spare: None,
+            #[cfg(feature = "alloc")]
+            checkpoints: Vec::new(),
+            origin: state,
+        }
+    }
+
+    /// Returns the generator jumped `delta` values ahead, like `.skip()`, in a form that can run
+    /// at compile time
+    pub const fn advanced(mut self, delta: u128) -> Self {
+        if delta != 0 {
+            self.buffered_bits = 0;
+            self.gaussian_spare = None;
+        }
+        self.state = Self::advance(self.state, delta, Self::MUL, self.inc);
+        self
+    }
+
+    /// Makes a generator seeded with `seed`, whose first 16 bytes are the little-endian `initstate`
+    /// and last 16 bytes the little-endian `initseq` given to `.srand()`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut seed = [0u8; 32];
+    /// seed[0] = 42;
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// orangey_ctx.srand(42, 0);
+    /// assert_eq!(OrangeyCtx::from_seed(seed).rand(), orangey_ctx.rand());
+    /// ```
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        let mut ctx = OrangeyCtx::new();
+        ctx.srand_bytes(seed);
+        ctx
+    }
+
+    /// Returns the generator's position in its stream: the little-endian state in the first 16 bytes
+    /// and the little-endian stream increment in the last 16. Values buffered from earlier outputs,
+    /// like the spare kept by `.gaussian()`, aren't included, so use the `serde` feature to save those too
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::seed_from_str("long simulation");
+    /// orangey_ctx.skip(1_000_000);
+    /// let checkpoint = orangey_ctx.state_bytes();
+    /// let mut resumed = OrangeyCtx::from_state_bytes(checkpoint);
+    /// assert_eq!(resumed.rand(), orangey_ctx.rand());
+    /// ```
+    pub fn state_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        bytes[..16].copy_from_slice(&self.state.to_le_bytes());
+        bytes[16..].copy_from_slice(&self.inc.to_le_bytes());
+        bytes
+    }
+
+    /// Makes a generator at the position saved by `.state_bytes()`. The increment must be odd, so its
+    /// lowest bit is always taken as set
+    pub fn from_state_bytes(bytes: [u8; 32]) -> Self {
+        let (state, inc) = bytes.split_at(16);
+        Self::from_seed_parts(
+            u128::from_le_bytes(state.try_into().unwrap()),
+            u128::from_le_bytes(inc.try_into().unwrap()) | 1,
+        )
+    }
+
+    /// Returns the generator's position as a short string that `OrangeyCtx::restore_hex` can read
+    /// back: a version prefix, then the state and stream increment in hex. Like `.state_bytes()`, it
+    /// leaves out buffered values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::{OrangeyCtx, ParseError};
+    ///
+    /// let saved = OrangeyCtx::new().save_hex();
+    /// assert_eq!(
+    ///     saved,
+    ///     "o1:ce84809586cf8d1f17e1e9805a1b4141b0a3e85a992afe5a280af6fdeecf029f"
+    /// );
+    /// let mut restored = OrangeyCtx::restore_hex(&saved).unwrap();
+    /// assert_eq!(restored.rand(), OrangeyCtx::new().rand());
+    /// let mistyped = OrangeyCtx::restore_hex("o9:00").err();
+    /// assert_eq!(mistyped, Some(ParseError::UnknownVersion));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn save_hex(&self) -> String {
+        format!("{}{:032x}{:032x}", Self::HEX_VERSION, self.state, self.inc)
+    }
+
+    /// Makes a generator at the position saved by `.save_hex()`
+    pub fn restore_hex(s: &str) -> Result<Self, ParseError> {
+        let digits = s
+            .trim()
+            .strip_prefix(Self::HEX_VERSION)
+            .ok_or(ParseError::UnknownVersion)?;
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ParseError::InvalidDigit);
+        }
+        if digits.len() != 64 {
+            return Err(ParseError::WrongLength);
         }
+        let state = u128::from_str_radix(&digits[..32], 16).unwrap();
+        let inc = u128::from_str_radix(&digits[32..], 16).unwrap();
+        if inc % 2 == 0 {
+            return Err(ParseError::EvenIncrement);
+        }
+        Ok(Self::from_seed_parts(state, inc))
+    }
+
+    /// Makes a generator seeded by hashing `bytes`, so any label can name a reproducible stream.
+    /// Similar inputs still give unrelated streams
+    pub fn seed_from_bytes(bytes: &[u8]) -> Self {
+        Self::from_seed(seed::expand(bytes))
+    }
+
+    /// Makes a generator seeded by hashing `s`, like `OrangeyCtx::seed_from_bytes`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut case_41 = OrangeyCtx::seed_from_str("test-case-41");
+    /// let mut case_42 = OrangeyCtx::seed_from_str("test-case-42");
+    /// assert_eq!(case_42.rand(), OrangeyCtx::seed_from_str("test-case-42").rand());
+    /// assert_ne!(case_41.rand(), OrangeyCtx::seed_from_str("test-case-42").rand());
+    /// ```
+    pub fn seed_from_str(s: &str) -> Self {
+        Self::seed_from_bytes(s.as_bytes())
+    }
+
+    /// Makes a generator seeded from the current time, the process ID and a count of calls so far,
+    /// so every call gives a different stream. This is fine for games and casual use, but the seed
+    /// is easy to guess, so use `OrangeyCtx::from_entropy` when that matters
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut a = OrangeyCtx::from_time();
+    /// let mut b = OrangeyCtx::from_time();
+    /// assert_ne!(a.rand(), b.rand());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_time() -> Self {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::time::{SystemTime, UNIX_EPOCH};
+        static CALLS: AtomicU64 = AtomicU64::new(0);
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        let mut bytes = [0; 32];
+        bytes[..16].copy_from_slice(&nanos.to_le_bytes());
+        bytes[16..20].copy_from_slice(&std::process::id().to_le_bytes());
+        bytes[24..].copy_from_slice(&CALLS.fetch_add(1, Ordering::Relaxed).to_le_bytes());
+        Self::seed_from_bytes(&bytes)
+    }
+
+    /// Makes a generator seeded by the operating system, so its stream can't be predicted
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system can't provide randomness. Use `OrangeyCtx::try_from_entropy`
+    /// to handle that case instead
+    #[cfg(feature = "getrandom")]
+    pub fn from_entropy() -> Self {
+        Self::try_from_entropy().expect("the operating system failed to provide a seed")
+    }
+
+    /// Makes a generator seeded by the operating system, or returns the reason it couldn't get a seed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut a = OrangeyCtx::try_from_entropy().unwrap();
+    /// let mut b = OrangeyCtx::try_from_entropy().unwrap();
+    /// assert_ne!(a.rand_u128(), b.rand_u128());
+    /// ```
+    #[cfg(feature = "getrandom")]
+    pub fn try_from_entropy() -> Result<Self, getrandom::Error> {
+        Ok(Self::from_seed(OsEntropy::try_seed()?))
     }
 
     /// Jumps `delta` values ahead in the rng stream
     ///
+    /// Jumping discards any values buffered from earlier outputs, such as the unused bits kept
+    /// by `.rand_u32()` and the spare deviate kept by `.gaussian()`
+    ///
     /// # Examples
     ///
     /// ```
@@ -35,9 +390,30 @@ impl OrangeyCtx {
     /// // 2947149625353530425
     /// ```
     pub fn skip(&mut self, delta: u128) {
+        if delta != 0 {
+            self.clear_buffers();
+        }
         self.state = Self::advance(self.state, delta, Self::MUL, self.inc);
     }
 
+    /// Jumps `delta` values back in the rng stream, undoing a `.skip(delta)` or `delta` calls to `.rand()`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let first = orangey_ctx.rand();
+    /// orangey_ctx.rand();
+    /// orangey_ctx.skip_back(2);
+    /// assert_eq!(orangey_ctx.rand(), first);
+    /// ```
+    pub fn skip_back(&mut self, delta: u128) {
+        // The stream has a period of 2^128, so going back is going forward the rest of the way around
+        self.skip(delta.wrapping_neg());
+    }
+
     /// Peeks at the `delta`-th value ahead in the rng stream. Unlike `.skip()`, this doesn't modify the rng state
     ///
     /// # Examples
@@ -51,8 +427,36 @@ impl OrangeyCtx {
     /// println!("{}", orangey_ctx.rand());
     /// // 18017628057179154148, as if peek were never called
     /// ```
-    pub fn peek(&mut self, delta: u128) -> u64 {
-        Self::output(Self::advance(self.state, delta + 1, Self::MUL, self.inc))
+    pub fn peek(&self, delta: u128) -> u64 {
+        Self::output(Self::advance(
+            self.state,
+            delta.wrapping_add(1),
+            Self::MUL,
+            self.inc,
+        ))
+    }
+
+    /// Peeks at the value `delta` steps back in the rng stream, so `.peek_back(0)` is the most recent
+    /// result of `.rand()`. Like `.peek()`, this doesn't modify the rng state
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let first = orangey_ctx.rand();
+    /// let second = orangey_ctx.rand();
+    /// assert_eq!(orangey_ctx.peek_back(0), second);
+    /// assert_eq!(orangey_ctx.peek_back(1), first);
+    /// ```
+    pub fn peek_back(&self, delta: u128) -> u64 {
+        Self::output(Self::advance(
+            self.state,
+            delta.wrapping_neg(),
+            Self::MUL,
+            self.inc,
+        ))
     }
 
     /// Seeds the generator with new initial state and sequence values
@@ -70,220 +474,3835 @@ impl OrangeyCtx {
     pub fn srand(&mut self, initstate: u128, initseq: u128) {
         self.state = 0;
         self.inc = (initseq << 1) | 1;
+        self.clear_buffers();
         self.step();
-        self.state += initstate;
+        self.state = self.state.wrapping_add(initstate);
         self.step();
+        self.origin = self.state;
     }
 
-    /// Runs the generator and return a random number
+    /// Returns how many steps the generator has taken since it was seeded, restored or moved to a
+    /// new stream, counting jumps from `.skip()` and `.skip_back()`. This is worked out from the
+    /// state rather than counted as it goes, so it costs nothing until it's called
     ///
     /// # Examples
     ///
     /// ```
     /// use orangey::OrangeyCtx;
     ///
-    /// let mut orangey_ctx = OrangeyCtx::new();
-    /// println!("{}", orangey_ctx.rand());
-    /// // 18017628057179154148
+    /// let mut orangey_ctx = OrangeyCtx::seed_from_str("draw counting");
+    /// orangey_ctx.shuffle(&mut [1, 2, 3, 4, 5]);
+    /// assert_eq!(orangey_ctx.values_generated(), 4);
+    /// orangey_ctx.skip(100);
+    /// assert_eq!(orangey_ctx.values_generated(), 104);
     /// ```
-    pub fn rand(&mut self) -> u64 {
-        self.step();
-        Self::output(self.state)
-    }
-
-    /// Generates a number in the range given
-    pub fn rand_range(&mut self, range: Range<u64>) -> u64 {
-        let distance = range.end - range.start;
-        if range.end == range.start {
-            return range.start;
-        }
-        if distance.count_ones() == 1 {
-            return (self.rand() & (distance - 1)) + range.start;
-        }
-        let limit = distance.wrapping_neg() % distance;
-        let mut r = 0;
-        for i in 0.. {
-            r = self.peek(i);
-            if r >= limit {
-                break;
-            }
-        }
-        r %= distance;
-        r + range.start
+    pub fn values_generated(&self) -> u128 {
+        Self::distance(self.origin, self.state, Self::MUL, self.inc)
     }
 
-    /// Generates a float in the range [0, 1) with uniform density.
-    /// This does not have an equal chance of hitting every float
-    /// in range, but you usually don't want that.
+    /// Runs `f` on the generator and returns its result along with how many outputs it consumed,
+    /// worked out from the state like `.values_generated()`. Jumps back count as wrapping around
+    /// the period, and the count means nothing if `f` reseeds the generator or changes its stream
     ///
-    /// For those who need that functionality, use .all_doubles()
-    pub fn uniform_double(&mut self) -> f64 {
-        const MASK: u64 = 0x000FFFFFFFFFFFFF;
-        const S_EXP: u64 = 0x3FF0000000000000;
-        let mut i = self.rand();
-        i &= MASK;
-        i |= S_EXP;
-        f64::from_bits(i) - 1.0
-    }
-
-    /// Has an equal chance of generating any representable float in the range [0, 1).
-    /// This is biased towards lower values.
+    /// # Examples
     ///
-    /// For those who need an even distribution of float values, use .uniform_double()
-    pub fn all_doubles(&mut self) -> f64 {
-        let mut exponent = -64;
-        let mut significand;
-        loop {
-            exponent -= 64;
-            if exponent < -1074 {
-                return 0.0;
-            }
-            significand = self.rand();
-            if significand != 0 {
-                break;
-            }
-        }
-        let shift = significand.leading_zeros();
-        if shift != 0 {
-            exponent -= shift as i32;
-            significand <<= shift;
-            significand |= self.peek(1) >> (64 - shift);
-        }
-        significand |= 1;
-        (significand as f64) * (exponent as f64).exp2()
-    }
-
-    /// Generates floats with standard gaussian density.
-    pub fn gaussian(&mut self) -> f64 {
-        let mut rsq;
-        loop {
-            rsq = self.uniform_double();
-            if rsq != 0.0 {
-                break;
-            }
-        }
-        self.peek_uniform_double(1) * (-2.0 * rsq.ln() / rsq).sqrt()
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// assert_eq!(orangey_ctx.draws_consumed(|ctx| ctx.rand_u128()).1, 2);
+    /// assert_eq!(orangey_ctx.draws_consumed(|ctx| ctx.unit_vector_3d()).1, 2);
+    /// assert_eq!(orangey_ctx.draws_consumed(|ctx| ctx.uuid_v4()).1, 2);
+    /// assert_eq!(orangey_ctx.draws_consumed(|ctx| ctx.negative_binomial(5, 0.5)).1, 5);
+    /// for _ in 0..100 {
+    ///     assert_eq!(orangey_ctx.draws_consumed(|ctx| ctx.exponential(2.0)).1, 1);
+    ///     assert_eq!(orangey_ctx.draws_consumed(|ctx| ctx.weibull(1.5, 1.0)).1, 1);
+    ///     assert_eq!(orangey_ctx.draws_consumed(|ctx| ctx.shuffle(&mut [0; 8])).1, 7);
+    ///     let (_, outputs) = orangey_ctx.draws_consumed(|ctx| ctx.in_unit_disk());
+    ///     assert!(outputs >= 2 && outputs % 2 == 0);
+    /// }
+    ///
+    /// // A mixed run from a fixed seed, pinned so that a change to any method's consumption shows up
+    /// let mut orangey_ctx = OrangeyCtx::seed_from_str("consumption");
+    /// let (_, outputs) = orangey_ctx.draws_consumed(|ctx| {
+    ///     for _ in 0..50 {
+    ///         ctx.rand_u32();
+    ///         ctx.rand_range(0..1000);
+    ///         ctx.gaussian();
+    ///         ctx.gaussian_fast();
+    ///         ctx.gamma(2.5, 1.0);
+    ///         ctx.poisson(40.0);
+    ///         ctx.binomial(100, 0.3);
+    ///         ctx.all_doubles();
+    ///         ctx.choose(&[1, 2, 3]);
+    ///     }
+    /// });
+    /// assert_eq!(outputs, 670);
+    /// assert_eq!(orangey_ctx.rand(), 10672794912925574732);
+    /// ```
+    pub fn draws_consumed<T>(&mut self, f: impl FnOnce(&mut OrangeyCtx) -> T) -> (T, u128) {
+        let before = self.state;
+        let result = f(self);
+        (
+            result,
+            Self::distance(before, self.state, Self::MUL, self.inc),
+        )
     }
 
-    /// Generates floats matching a poisson distribution with an expected value of `ev`
-    pub fn poisson(&mut self, ev: f64) -> u64 {
-        let mut n = 0;
-        let em = (-ev).exp();
-        let mut x = self.uniform_double();
-        while x > em {
-            n += 1;
-            x *= self.peek_uniform_double(1);
-        }
-        n
+    /// Returns the stream the generator is on, as the `initseq` given to `.srand()` would set it.
+    /// Only the low 127 bits of `initseq` pick the stream
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// orangey_ctx.srand(7, 12);
+    /// assert_eq!(orangey_ctx.stream(), 12);
+    /// ```
+    pub fn stream(&self) -> u128 {
+        self.inc >> 1
     }
 
-    /// Peeks at the `delta`-th future result of `.rand_range(range)` without changing the rng state
-    pub fn peek_range(&self, delta: u128, range: Range<u64>) -> u64 {
-        let mut new_self = OrangeyCtx { ..*self };
-        new_self.skip(delta);
-        new_self.rand_range(range)
+    /// Moves the generator to stream `seq` without changing its state, which gives an unrelated
+    /// sequence of values. This discards buffered values, like `.skip()`
+    pub fn set_stream(&mut self, seq: u128) {
+        self.inc = (seq << 1) | 1;
+        self.origin = self.state;
+        self.clear_buffers();
     }
 
-    /// Peeks at the `delta`-th future result of `.uniform_double()` without changing the rng state
-    pub fn peek_uniform_double(&self, delta: u128) -> f64 {
-        let mut new_self = OrangeyCtx { ..*self };
-        new_self.skip(delta);
-        new_self.uniform_double()
+    /// Makes a generator like `OrangeyCtx::new`, but on stream `seq`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut entities: Vec<OrangeyCtx> = (0..3).map(OrangeyCtx::with_stream).collect();
+    /// assert_eq!(entities[2].stream(), 2);
+    /// assert_ne!(entities[0].rand(), entities[1].rand());
+    /// ```
+    pub fn with_stream(seq: u128) -> Self {
+        let mut ctx = OrangeyCtx::new();
+        ctx.set_stream(seq);
+        ctx
     }
 
-    /// Peeks at the `delta`-th future result of `.all_doubles()` without changing the rng state
-    pub fn peek_all_doubles(&self, delta: u128) -> f64 {
-        let mut new_self = OrangeyCtx { ..*self };
-        new_self.skip(delta);
-        new_self.all_doubles()
+    /// Returns copies of this generator on every stream in turn, starting from stream 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let base = OrangeyCtx::seed_from_str("world");
+    /// for (id, ctx) in base.streams().enumerate().take(4) {
+    ///     assert_eq!(ctx.stream(), id as u128);
+    /// }
+    /// ```
+    pub fn streams(&self) -> impl Iterator<Item = OrangeyCtx> + '_ {
+        (0..=u128::MAX >> 1).map(move |seq| {
+            let mut ctx = self.snapshot();
+            ctx.set_stream(seq);
+            ctx
+        })
     }
 
-    /// Peeks at the `delta`-th future result of `.gaussian()` without changing the rng state
-    pub fn peek_gaussian(&self, delta: u128) -> f64 {
-        let mut new_self = OrangeyCtx { ..*self };
-        new_self.skip(delta);
-        new_self.gaussian()
+    /// Returns how many steps ahead of this generator `other` is, so `.skip()`ping that many would
+    /// put this one in `other`'s state. Returns `None` if the two are on different streams. The
+    /// result wraps around the 2^128 period, so a generator just behind `other` comes out far ahead
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let before = OrangeyCtx::new();
+    /// let mut after = before.clone();
+    /// for _ in 0..10 {
+    ///     after.gaussian();
+    /// }
+    /// println!("ten gaussians used {} outputs", before.distance_to(&after).unwrap());
+    /// assert_eq!(after.distance_to(&before), Some(before.distance_to(&after).unwrap().wrapping_neg()));
+    /// assert_eq!(before.distance_to(&OrangeyCtx::with_stream(1)), None);
+    /// ```
+    pub fn distance_to(&self, other: &OrangeyCtx) -> Option<u128> {
+        if self.inc != other.inc {
+            return None;
+        }
+        Some(Self::distance(self.state, other.state, Self::MUL, self.inc))
     }
 
-    /// Peeks at the `delta`-th future result of `.poisson(ev)` without changing the rng state
-    pub fn peek_poisson(&self, delta: u128, ev: f64) -> u64 {
-        let mut new_self = OrangeyCtx { ..*self };
-        new_self.skip(delta);
-        new_self.poisson(ev)
+    /// Makes a new generator seeded from the next four outputs of this one. The child runs on a
+    /// different sequence, so its stream is unrelated to the parent's
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let workers: Vec<OrangeyCtx> = (0..4).map(|_| orangey_ctx.split()).collect();
+    /// let handles: Vec<_> = workers
+    ///     .into_iter()
+    ///     .map(|mut ctx| std::thread::spawn(move || ctx.gaussian()))
+    ///     .collect();
+    /// let results: Vec<f64> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    /// ```
+    pub fn split(&mut self) -> OrangeyCtx {
+        let initstate = self.rand_u128();
+        let initseq = self.rand_u128();
+        let mut child = OrangeyCtx::new();
+        child.srand(initstate, initseq);
+        child
     }
 
-    const MUL: u128 = 0x2360ed051fc65da44385df649fccf645;
-
-    fn output(state: u128) -> u64 {
-        ((state >> 64) as u64 ^ state as u64).rotate_right((state >> 122) as _)
+    /// Hands out `n_blocks` generators that each start `block_size` values after the one before,
+    /// so each one owns a disjoint block of this stream as long as it takes no more than `block_size`
+    /// values. This generator then jumps past all of the blocks, so it won't overlap with them either
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let serial = OrangeyCtx::new().rand_batch(6);
+    /// let blocks = orangey_ctx.jumped(3, 2);
+    /// let parallel: Vec<u64> = blocks.into_iter().flat_map(|mut ctx| ctx.rand_batch(2)).collect();
+    /// assert_eq!(parallel, serial);
+    /// assert_eq!(orangey_ctx.rand(), OrangeyCtx::new().peek(6));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn jumped(&mut self, n_blocks: usize, block_size: u128) -> Vec<OrangeyCtx> {
+        let mut blocks = Vec::with_capacity(n_blocks);
+        for _ in 0..n_blocks {
+            let mut block = self.snapshot();
+            block.clear_buffers();
+            blocks.push(block);
+            self.skip(block_size);
+        }
+        blocks
     }
 
-    fn step(&mut self) {
-        self.state = (Wrapping(self.state) * Wrapping(Self::MUL) + Wrapping(self.inc)).0;
+    /// Saves the generator's current state, including buffered values, on a stack of checkpoints
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// orangey_ctx.push_state();
+    /// let guess = orangey_ctx.rand();
+    /// assert!(orangey_ctx.pop_state());
+    /// assert_eq!(orangey_ctx.rand(), guess);
+    /// assert!(!orangey_ctx.pop_state());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn push_state(&mut self) {
+        let checkpoint = self.snapshot();
+        self.checkpoints.push(checkpoint);
     }
 
-    fn advance(state: u128, delta: u128, cur_mult: u128, cur_plus: u128) -> u128 {
-        let state = Wrapping(state);
-        let mut delta = Wrapping(delta);
-        let mut cur_mult = Wrapping(cur_mult);
-        let mut cur_plus = Wrapping(cur_plus);
-
-        let mut acc_mult = Wrapping(1);
-        let mut acc_plus = Wrapping(0);
-        while delta > Wrapping(0) {
-            if delta & Wrapping(1) != Wrapping(0) {
-                acc_mult *= cur_mult;
-                acc_plus = acc_plus * cur_mult + cur_plus;
+    /// Goes back to the state saved by the last `.push_state()` and removes it from the stack.
+    /// Returns `false` and leaves the generator alone if there is nothing to go back to
+    #[cfg(feature = "alloc")]
+    pub fn pop_state(&mut self) -> bool {
+        match self.checkpoints.pop() {
+            Some(checkpoint) => {
+                let checkpoints = core::mem::take(&mut self.checkpoints);
+                *self = OrangeyCtx {
+                    checkpoints,
+                    ..checkpoint
+                };
+                true
             }
-            cur_plus *= cur_mult + Wrapping(1);
-            cur_mult *= cur_mult;
-            delta /= 2;
+            None => false,
         }
-        (acc_mult * state + acc_plus).0
-    }
-}
-
-impl Default for OrangeyCtx {
-    fn default() -> Self {
-        Self::new()
     }
-}
 
-macro_rules! iter_wrapper {
-    (fn $name:ident(&mut self $(, $arg:ident: $type:ty)* $(,)?) -> $ret:ty, $struct_name:ident, $method_name:ident) => {
+    /// Runs `f` on the generator, then puts the generator back the way it was before `f`. Any
+    /// checkpoints `f` pushed and didn't pop are dropped too
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let lookahead = orangey_ctx.with_saved_state(|ctx| ctx.gaussian());
+    /// assert_eq!(orangey_ctx.gaussian(), lookahead);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn with_saved_state<T>(&mut self, f: impl FnOnce(&mut OrangeyCtx) -> T) -> T {
+        let depth = self.checkpoints.len();
+        self.push_state();
+        let result = f(self);
+        self.checkpoints.truncate(depth + 1);
+        self.pop_state();
+        result
+    }
+
+    /// Runs the generator and return a random number
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// println!("{}", orangey_ctx.rand());
+    /// // 18017628057179154148
+    /// ```
+    #[inline]
+    pub fn rand(&mut self) -> u64 {
+        self.step();
+        Self::output(self.state)
+    }
+
+    /// Runs the generator twice and returns a 128-bit random number, with the first output in the high bits
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let wide = orangey_ctx.rand_u128();
+    /// let mut other_ctx = OrangeyCtx::new();
+    /// assert_eq!((wide >> 64) as u64, other_ctx.rand());
+    /// assert_eq!(wide as u64, other_ctx.rand());
+    /// ```
+    #[inline]
+    pub fn rand_u128(&mut self) -> u128 {
+        let high = self.rand() as u128;
+        (high << 64) | self.rand() as u128
+    }
+
+    /// Returns a random 32-bit number. Each output of the generator is split into two of these,
+    /// so the generator only steps on every other call
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let r = OrangeyCtx::new().rand();
+    /// assert_eq!(orangey_ctx.rand_u32(), r as u32);
+    /// assert_eq!(orangey_ctx.rand_u32(), (r >> 32) as u32);
+    /// ```
+    #[inline]
+    pub fn rand_u32(&mut self) -> u32 {
+        self.rand_bits(32) as u32
+    }
+
+    /// Returns a random 16-bit number, sharing the buffered bits of `.rand_u32()`
+    #[inline]
+    pub fn rand_u16(&mut self) -> u16 {
+        self.rand_bits(16) as u16
+    }
+
+    /// Returns a random 8-bit number, sharing the buffered bits of `.rand_u32()`
+    #[inline]
+    pub fn rand_u8(&mut self) -> u8 {
+        self.rand_bits(8) as u8
+    }
+
+    /// Returns `true` with probability `p`, using an average of two bits from the `.rand_u32()` buffer.
+    /// `.rand_bool(0.5)` always uses exactly one bit
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in the range [0, 1]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let heads = (0..1000).filter(|_| orangey_ctx.rand_bool(0.7)).count();
+    /// assert!((600..800).contains(&heads));
+    /// ```
+    pub fn rand_bool(&mut self, p: f64) -> bool {
+        assert!(
+            (0.0..=1.0).contains(&p),
+            "probability {} is not in [0, 1]",
+            p
+        );
+        if p == 1.0 {
+            return true;
+        }
+        self.bernoulli_bits((p * 2f64.powi(64)) as u128, 1 << 64)
+    }
+
+    /// Returns `true` with the exact probability `numerator / denominator`, using an average of
+    /// two bits from the `.rand_u32()` buffer
+    ///
+    /// # Panics
+    ///
+    /// Panics if `denominator` is 0 or `numerator` is greater than `denominator`
+    pub fn rand_ratio(&mut self, numerator: u64, denominator: u64) -> bool {
+        assert!(denominator != 0, "denominator must not be 0");
+        assert!(
+            numerator <= denominator,
+            "ratio {}/{} is greater than 1",
+            numerator,
+            denominator
+        );
+        if numerator == denominator {
+            return true;
+        }
+        self.bernoulli_bits(numerator as u128, denominator as u128)
+    }
+
+    /// Compares random bits against the binary expansion of `numerator / denominator` until they differ
+    fn bernoulli_bits(&mut self, numerator: u128, denominator: u128) -> bool {
+        let mut remainder = numerator;
+        while remainder != 0 {
+            remainder <<= 1;
+            let digit = remainder >= denominator;
+            if digit {
+                remainder -= denominator;
+            }
+            let bit = self.rand_bits(1) == 1;
+            if bit != digit {
+                return digit;
+            }
+        }
+        false
+    }
+
+    /// Takes the low `bits` bits of the buffer, refilling it from a new output if it runs short
+    #[inline]
+    fn rand_bits(&mut self, bits: u32) -> u64 {
+        if self.buffered_bits < bits {
+            self.buffer = self.rand();
+            self.buffered_bits = 64;
+        }
+        let r = self.buffer & (u64::MAX >> (64 - bits));
+        self.buffer >>= bits;
+        self.buffered_bits -= bits;
+        r
+    }
+
+    /// Fills `buf` with random bytes, using one step of the generator for every 8 bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let mut key = [0u8; 12];
+    /// orangey_ctx.fill_bytes(&mut key);
+    /// assert_eq!(key[..8], OrangeyCtx::new().rand().to_le_bytes());
+    /// ```
+    pub fn fill_bytes(&mut self, buf: &mut [u8]) {
+        self.fill_slice(buf);
+    }
+
+    /// Fills `dest` with random integers, splitting each 64-bit output into as many values as fit
+    pub fn fill_slice<T: Fillable>(&mut self, dest: &mut [T]) {
+        T::fill(self, dest);
+    }
+
+    /// Generates `n` results of `.rand()` into a new `Vec`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let batch = orangey_ctx.rand_batch(4);
+    /// assert_eq!(batch, OrangeyCtx::new().rand_iter().take(4).collect::<Vec<_>>());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn rand_batch(&mut self, n: usize) -> Vec<u64> {
+        let mut batch = Vec::with_capacity(n);
+        self.extend_rand(&mut batch, n);
+        batch
+    }
+
+    /// Appends `n` results of `.rand()` to `dest`, reserving the space for them up front
+    #[cfg(feature = "alloc")]
+    pub fn extend_rand(&mut self, dest: &mut Vec<u64>, n: usize) {
+        dest.reserve(n);
+        for _ in 0..n {
+            dest.push(self.rand());
+        }
+    }
+
+    /// Generates `n` results of `.gaussian()` into a new `Vec`
+    #[cfg(feature = "alloc")]
+    pub fn gaussian_batch(&mut self, n: usize) -> Vec<f64> {
+        let mut batch = Vec::with_capacity(n);
+        self.extend_gaussian(&mut batch, n);
+        batch
+    }
+
+    /// Appends `n` results of `.gaussian()` to `dest`, reserving the space for them up front
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let mut samples = Vec::with_capacity(1000);
+    /// for _ in 0..10 {
+    ///     orangey_ctx.extend_gaussian(&mut samples, 100);
+    /// }
+    /// assert_eq!((samples.len(), samples.capacity()), (1000, 1000));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn extend_gaussian(&mut self, dest: &mut Vec<f64>, n: usize) {
+        dest.reserve(n);
+        for _ in 0..n {
+            dest.push(self.gaussian());
+        }
+    }
+
+    /// Generates a number in the range given
+    ///
+    /// Each attempt consumes exactly one output of the generator, and the call returns at the first
+    /// attempt that is accepted, so the generator ends up just past the last output it looked at.
+    /// An attempt can only be rejected when the size of the range doesn't divide 2^64, and then
+    /// with a probability below `size / 2^64`, so small ranges almost always take a single output
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty. Use `.try_rand_range()` to handle that case instead
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let digits: Vec<u64> = (0..8).map(|_| orangey_ctx.rand_range(0..10)).collect();
+    /// assert_eq!(digits, [9, 8, 8, 1, 3, 2, 1, 8]);
+    /// assert_eq!(orangey_ctx.values_generated(), 8);
+    /// ```
+    ///
+    /// About half of all attempts are rejected for a range of size 2^63 + 1, and each rejection
+    /// costs one more output:
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let size = (1 << 63) + 1;
+    /// let draws: Vec<u64> = (0..4).map(|_| orangey_ctx.rand_range(0..size)).collect();
+    /// assert_eq!(
+    ///     draws,
+    ///     [9008814028589577074, 8052174280790154211, 8042214443895191137, 2880702105084452845]
+    /// );
+    /// assert_eq!(orangey_ctx.values_generated(), 5);
+    /// ```
+    #[inline]
+    pub fn rand_range(&mut self, range: Range<u64>) -> u64 {
+        match self.try_rand_range(range) {
+            Ok(r) => r,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Generates a number in the range given, which may be any kind of range,
+    /// including the full `0..=u64::MAX`. This uses Lemire's multiply-shift method, which only
+    /// divides in the rare case that a draw might have to be redone
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::{OrangeyCtx, RangeError};
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// assert!(orangey_ctx.try_rand_range(64..=128).is_ok());
+    /// assert!(orangey_ctx.try_rand_range(0..=u64::MAX).is_ok());
+    /// assert_eq!(orangey_ctx.try_rand_range(3..3), Err(RangeError::Empty));
+    /// assert_eq!(orangey_ctx.try_rand_range(5..3), Err(RangeError::Inverted));
+    /// ```
+    pub fn try_rand_range<R: RangeBounds<u64>>(&mut self, range: R) -> Result<u64, RangeError> {
+        let (low, high) = RangeError::check_bounds(&range)?;
+        Ok(low + bounded((high - low).wrapping_add(1), || self.rand()))
+    }
+
+    /// Generates an integer of any primitive type in the range given, which may be any kind of range
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let roll = orangey_ctx.gen_range(-10i32..=10);
+    /// assert!((-10..=10).contains(&roll));
+    /// let byte: u8 = orangey_ctx.gen_range(..);
+    /// ```
+    pub fn gen_range<T: SampleUniform, R: RangeBounds<T>>(&mut self, range: R) -> T {
+        T::sample_range(self, range)
+    }
+
+    /// Generates a number in `[0, span]`
+    #[inline]
+    fn sample_span_u64(&mut self, span: u64) -> u64 {
+        bounded(span.wrapping_add(1), || self.rand())
+    }
+
+    /// Generates a number in `[0, span]`
+    fn sample_span_u128(&mut self, span: u128) -> u128 {
+        if let Ok(span) = u64::try_from(span) {
+            return self.sample_span_u64(span) as u128;
+        }
+        let distance = span.wrapping_add(1);
+        let limit = distance.wrapping_neg().checked_rem(distance).unwrap_or(0);
+        loop {
+            let r = self.rand_u128();
+            if r >= limit {
+                return r.checked_rem(distance).unwrap_or(r);
+            }
+        }
+    }
+
+    /// Generates a float in the range [0, 1) with uniform density, from one output.
+    /// The result is one of the 2^52 multiples of 2^-52 in the range, including 0.
+    /// This does not have an equal chance of hitting every float
+    /// in range, but you usually don't want that.
+    ///
+    /// For a finer grid use `.uniform_double_53()`, to avoid 0 use `.open01()` or
+    /// `.open_closed01()`, and for every float in range use `.all_doubles()`
+    #[inline]
+    pub fn uniform_double(&mut self) -> f64 {
+        const MASK: u64 = 0x000FFFFFFFFFFFFF;
+        const S_EXP: u64 = 0x3FF0000000000000;
+        f64::from_bits((self.rand() & MASK) | S_EXP) - 1.0
+    }
+
+    /// Generates a float in the range [0, 1) with uniform density, from the top 53 bits of one
+    /// output. The result is one of the 2^53 multiples of 2^-53 in the range, including 0, which is
+    /// every value a double can hold in [0.5, 1) and twice as fine as `.uniform_double()` below that
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let top = OrangeyCtx::new().rand() >> 11;
+    /// assert_eq!(orangey_ctx.uniform_double_53(), top as f64 / (1u64 << 53) as f64);
+    /// ```
+    #[inline]
+    pub fn uniform_double_53(&mut self) -> f64 {
+        (self.rand() >> 11) as f64 * f64::EPSILON / 2.0
+    }
+
+    /// Generates a float in the open range (0, 1) with uniform density, from one output. The result
+    /// is the midpoint of one of 2^52 equal slices of the range, so it is never 0 or 1 and
+    /// is safe to pass to `ln` or to divide by
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let u = orangey_ctx.open01();
+    /// assert!(u > 0.0 && u < 1.0);
+    /// assert!(u.ln().is_finite());
+    /// ```
+    #[inline]
+    pub fn open01(&mut self) -> f64 {
+        ((self.rand() >> 12) as f64 + 0.5) * f64::EPSILON
+    }
+
+    /// Generates a float in the half-open range (0, 1] with uniform density, from the top 53 bits
+    /// of one output. The result is one of the 2^53 multiples of 2^-53 in the range, including 1
+    #[inline]
+    pub fn open_closed01(&mut self) -> f64 {
+        ((self.rand() >> 11) + 1) as f64 * f64::EPSILON / 2.0
+    }
+
+    /// Generates a float in the range [`range.start`, `range.end`) with uniform density.
+    /// Results that round up to `range.end` are redrawn, so `range.end` is never returned
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty or either end is not finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let x = orangey_ctx.rand_range_f64(-1.5..2.5);
+    /// assert!((-1.5..2.5).contains(&x));
+    ///
+    /// // Ranges wider than the largest float still work
+    /// let huge = orangey_ctx.rand_range_f64(-f64::MAX..f64::MAX);
+    /// assert!(huge.is_finite());
+    /// ```
+    pub fn rand_range_f64(&mut self, range: Range<f64>) -> f64 {
+        assert!(
+            range.start.is_finite() && range.end.is_finite() && range.start < range.end,
+            "cannot sample from the range {:?}",
+            range
+        );
+        let width = range.end - range.start;
+        loop {
+            let u = self.uniform_double();
+            let r = if width.is_finite() {
+                range.start + width * u
+            } else {
+                // The width of a range like -f64::MAX..f64::MAX overflows, but half of it doesn't
+                let step = (range.end * 0.5 - range.start * 0.5) * u;
+                range.start + step + step
+            };
+            if r < range.end {
+                return r;
+            }
+        }
+    }
+
+    /// Generates a float in the range [`range.start`, `range.end`) with uniform density.
+    /// Results that round up to `range.end` are redrawn, so `range.end` is never returned
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty or either end is not finite
+    pub fn rand_range_f32(&mut self, range: Range<f32>) -> f32 {
+        assert!(
+            range.start.is_finite() && range.end.is_finite() && range.start < range.end,
+            "cannot sample from the range {:?}",
+            range
+        );
+        let width = range.end as f64 - range.start as f64;
+        loop {
+            let r = (range.start as f64 + width * self.uniform_double()) as f32;
+            if r < range.end {
+                return r;
+            }
+        }
+    }
+
+    /// Generates a float in the range [0, 1) that can be any double in that range, including the
+    /// subnormals. Each float comes up with probability equal to the gap between it and the next
+    /// float up, so the results are as evenly spread as `.uniform_double()`'s but much finer
+    /// near 0. For evenly spaced results, use `.uniform_double()`
+    ///
+    /// This is Downey's algorithm, rounding down rather than to nearest so that 1 is never
+    /// returned. The leading zeros of the output pick the binade, [2^-(z+1), 2^-z) for `z` zeros,
+    /// and the bits below the leading one give the significand. That takes one output, plus
+    /// another for the significand when the first has 12 or more leading zeros (1 in 2048 draws),
+    /// plus one for every output that is all zeros
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let ahead = orangey_ctx.peek_all_doubles(0);
+    /// assert_eq!(orangey_ctx.all_doubles(), ahead);
+    ///
+    /// // Each binade turns up half as often as the one above it
+    /// let draws: Vec<f64> = orangey_ctx.all_doubles_iter().take(100_000).collect();
+    /// for z in 0..8 {
+    ///     let share = draws.iter().filter(|&&x| x < 0.5f64.powi(z)).count() as f64 / 1e5;
+    ///     assert!((share - 0.5f64.powi(z)).abs() < 0.01);
+    /// }
+    /// ```
+    ///
+    /// The far tail, reached here by starting from a state whose next outputs are 1 and then 0:
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// // 63 leading zeros, so the result lies in [2^-64, 2^-63)
+    /// let mut orangey_ctx = OrangeyCtx::from_seed_parts(1, 1);
+    /// orangey_ctx.skip_back(1);
+    /// let x = orangey_ctx.all_doubles();
+    /// assert!(x >= 2f64.powi(-64) && x < 2f64.powi(-63));
+    /// // A second output was drawn for the significand
+    /// assert_eq!(orangey_ctx.rand(), OrangeyCtx::from_seed_parts(1, 1).peek(1));
+    ///
+    /// // An all-zero output moves the search down 64 binades
+    /// let mut orangey_ctx = OrangeyCtx::from_seed_parts(0, 1);
+    /// orangey_ctx.skip_back(1);
+    /// let x = orangey_ctx.all_doubles();
+    /// assert!(x >= 2f64.powi(-128) && x < 2f64.powi(-127));
+    /// assert_eq!(orangey_ctx.rand(), OrangeyCtx::from_seed_parts(0, 1).peek(2));
+    /// ```
+    pub fn all_doubles(&mut self) -> f64 {
+        const MANTISSA_BITS: u32 = 52;
+        const MASK: u64 = (1 << MANTISSA_BITS) - 1;
+        // [2^exponent, 2^(exponent + 1)) is the binade containing the result
+        let mut exponent = -1;
+        let mut r;
+        loop {
+            r = self.rand();
+            if r != 0 {
+                break;
+            }
+            exponent -= 64;
+            if exponent < -1074 {
+                return 0.0;
+            }
+        }
+        exponent -= r.leading_zeros() as i32;
+        // The bits below the leading one are still uniform, so reuse them if there are enough
+        let mantissa = if r.leading_zeros() < 64 - MANTISSA_BITS {
+            r & MASK
+        } else {
+            self.rand() & MASK
+        };
+        if exponent >= -1022 {
+            f64::from_bits(((exponent + 1023) as u64) << MANTISSA_BITS | mantissa)
+        } else if exponent >= -1074 {
+            f64::from_bits(((1 << MANTISSA_BITS) | mantissa) >> (-1022 - exponent))
+        } else {
+            0.0
+        }
+    }
+
+    /// The `f32` counterpart of `.uniform_double()`, generating a float in the range [0, 1) with uniform density,
+    /// from one output
+    #[inline]
+    pub fn uniform_float(&mut self) -> f32 {
+        const MASK: u32 = 0x007FFFFF;
+        const S_EXP: u32 = 0x3F800000;
+        let mut i = self.rand() as u32;
+        i &= MASK;
+        i |= S_EXP;
+        f32::from_bits(i) - 1.0
+    }
+
+    /// The `f32` counterpart of `.all_doubles()`, able to generate any representable float in the range [0, 1).
+    /// This picks a uniformly random real number and rounds it down, so smaller values are more likely.
+    /// It takes one output, or two when the first has 41 or more leading zeros, plus one for every
+    /// output that is all zeros
+    pub fn all_floats(&mut self) -> f32 {
+        const MANTISSA_BITS: u32 = 23;
+        const MASK: u64 = (1 << MANTISSA_BITS) - 1;
+        // [2^exponent, 2^(exponent + 1)) is the binade containing the result
+        let mut exponent = -1;
+        let mut r;
+        loop {
+            r = self.rand();
+            if r != 0 {
+                break;
+            }
+            exponent -= 64;
+            if exponent < -149 {
+                return 0.0;
+            }
+        }
+        exponent -= r.leading_zeros() as i32;
+        // Reuse the bits below the leading one if there are enough of them
+        let mantissa = if r.leading_zeros() < 64 - MANTISSA_BITS {
+            r & MASK
+        } else {
+            self.rand() & MASK
+        } as u32;
+        if exponent >= -126 {
+            f32::from_bits(((exponent + 127) as u32) << MANTISSA_BITS | mantissa)
+        } else if exponent >= -149 {
+            f32::from_bits(((1 << MANTISSA_BITS) | mantissa) >> (-126 - exponent))
+        } else {
+            0.0
+        }
+    }
+
+    /// Generates floats with standard gaussian density, negative as often as positive
+    ///
+    /// This uses the Marsaglia polar method, which picks a point `(u, v)` uniformly in the square
+    /// [-1, 1)², rejects it unless it lies inside the unit circle, and scales both coordinates
+    /// into a pair of independent deviates. The signs of `u` and `v` carry through, so the
+    /// results are symmetric about 0 without needing the sine and cosine of Box–Muller
+    ///
+    /// Each attempt at a point consumes exactly two outputs, `u` then `v`, and succeeds with
+    /// probability π/4. The first call of each pair returns `u`'s deviate after however many
+    /// attempts that takes, an average of 8/π ≈ 2.55 outputs, and the second returns `v`'s
+    /// without consuming any. Anything that moves the stream, like `.skip()` or `.srand()`,
+    /// throws the spare away
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::seed_from_str("bell curve");
+    /// let draws: Vec<f64> = orangey_ctx.gaussian_iter().take(100_000).collect();
+    /// let n = draws.len() as f64;
+    /// let mean = draws.iter().sum::<f64>() / n;
+    /// let variance = draws.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+    /// let skew = draws.iter().map(|x| (x - mean).powi(3)).sum::<f64>() / n / variance.powf(1.5);
+    /// assert!(mean.abs() < 0.02);
+    /// assert!((variance - 1.0).abs() < 0.02);
+    /// assert!(skew.abs() < 0.05);
+    ///
+    /// let negative = draws.iter().filter(|&&x| x < 0.0).count() as f64 / n;
+    /// assert!((negative - 0.5).abs() < 0.01);
+    /// ```
+    ///
+    /// Pairs always consume an even number of outputs:
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// for _ in 0..100 {
+    ///     let before = orangey_ctx.values_generated();
+    ///     orangey_ctx.gaussian();
+    ///     let used = orangey_ctx.values_generated() - before;
+    ///     assert!(used >= 2 && used % 2 == 0);
+    ///     orangey_ctx.gaussian();
+    ///     assert_eq!(orangey_ctx.values_generated() - before, used);
+    /// }
+    /// ```
+    pub fn gaussian(&mut self) -> f64 {
+        if let Some(spare) = self.gaussian_spare.take() {
+            return spare;
+        }
+        let (u, v) = gaussian_pair(self);
+        self.gaussian_spare = Some(v);
+        u
+    }
+
+    /// Generates floats with gaussian density centered on `mean` with a standard deviation of `std_dev`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let height = orangey_ctx.gaussian_with(170.0, 7.5);
+    /// assert_eq!(height, 170.0 + 7.5 * OrangeyCtx::new().gaussian());
+    /// ```
+    pub fn gaussian_with(&mut self, mean: f64, std_dev: f64) -> f64 {
+        mean + std_dev * self.gaussian()
+    }
+
+    /// The `f32` counterpart of `.gaussian()`, consuming the same outputs
+    pub fn gaussian_f32(&mut self) -> f32 {
+        self.gaussian() as f32
+    }
+
+    /// Generates floats with standard gaussian density using the ziggurat method. This is
+    /// noticeably faster than `.gaussian()` and consumes one output of the generator for the vast
+    /// majority of calls, but follows a different sequence
+    pub fn gaussian_fast(&mut self) -> f64 {
+        OrangeyEngine::gaussian_fast(self)
+    }
+
+    /// Generates floats with a standard exponential density (a rate of 1) using the ziggurat method.
+    /// This consumes one output of the generator for the vast majority of calls
+    pub fn exponential_fast(&mut self) -> f64 {
+        OrangeyEngine::exponential_fast(self)
+    }
+
+    /// Generates floats with a gamma density with the given `shape` and `scale`, using the
+    /// Marsaglia–Tsang method. Each attempt takes one `.gaussian_fast()` draw and then one more
+    /// output unless the draw is rejected outright, and shapes below 1 take one output up front
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shape` or `scale` is not positive
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let mean = orangey_ctx.gamma_iter(3.0, 2.0).take(10000).sum::<f64>() / 10000.0;
+    /// assert!((5.8..6.2).contains(&mean));
+    /// ```
+    pub fn gamma(&mut self, shape: f64, scale: f64) -> f64 {
+        OrangeyEngine::gamma(self, shape, scale)
+    }
+
+    /// Generates floats in the range [0, 1] with a beta density with shape parameters `a` and `b`,
+    /// from a `.gamma()` draw for each of them
+    ///
+    /// Shapes so small that both draws underflow are compared through their logarithms instead.
+    /// Below about 1e-300, where even those run out of range, the result is 1 with probability
+    /// `a / (a + b)` and 0 otherwise, which takes one more output
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is not positive and finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let p = orangey_ctx.beta(2.0, 3.0);
+    /// assert!((0.0..=1.0).contains(&p));
+    /// // Tiny shapes put nearly all of the density at the ends
+    /// let end = orangey_ctx.beta(1e-300, 1e-300);
+    /// assert!(end == 0.0 || end == 1.0);
+    /// let end = orangey_ctx.beta(f64::MIN_POSITIVE / 1e6, f64::MIN_POSITIVE / 1e6);
+    /// assert!(end == 0.0 || end == 1.0);
+    /// ```
+    pub fn beta(&mut self, a: f64, b: f64) -> f64 {
+        OrangeyEngine::beta(self, a, b)
+    }
+
+    /// Generates floats with a chi-squared density with `k` degrees of freedom, from one `.gamma()` draw
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is not positive
+    pub fn chi_squared(&mut self, k: f64) -> f64 {
+        OrangeyEngine::chi_squared(self, k)
+    }
+
+    /// Generates floats whose natural logarithm has gaussian density with a mean of `mu` and a standard deviation of `sigma`.
+    /// This consumes the same outputs as `.gaussian()`
+    pub fn log_normal(&mut self, mu: f64, sigma: f64) -> f64 {
+        OrangeyEngine::log_normal(self, mu, sigma)
+    }
+
+    /// Generates an angle in radians in the range [0, 2π) with uniform density.
+    /// This consumes exactly one output of the generator
+    pub fn angle(&mut self) -> f64 {
+        core::f64::consts::TAU * self.uniform_double()
+    }
+
+    /// Generates a point on the unit circle with uniform density.
+    /// This consumes exactly one output of the generator
+    pub fn unit_vector_2d(&mut self) -> [f64; 2] {
+        let (sin, cos) = self.angle().sin_cos();
+        [cos, sin]
+    }
+
+    /// Generates a point on the unit sphere with uniform density, by picking the height uniformly
+    /// and then an angle around it. This consumes exactly two outputs of the generator
+    pub fn unit_vector_3d(&mut self) -> [f64; 3] {
+        let z = 2.0 * self.uniform_double() - 1.0;
+        let (sin, cos) = self.angle().sin_cos();
+        let r = (1.0 - z * z).sqrt();
+        [r * cos, r * sin, z]
+    }
+
+    /// Generates a point inside the unit disk with uniform density, by rejection from the enclosing
+    /// square. This consumes two outputs of the generator per attempt, and 4/π attempts on average
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let [x, y] = orangey_ctx.in_unit_disk();
+    /// assert!(x * x + y * y < 1.0);
+    /// ```
+    pub fn in_unit_disk(&mut self) -> [f64; 2] {
+        loop {
+            let x = 2.0 * self.uniform_double() - 1.0;
+            let y = 2.0 * self.uniform_double() - 1.0;
+            if x * x + y * y < 1.0 {
+                return [x, y];
+            }
+        }
+    }
+
+    /// Generates a point inside the unit ball with uniform density, by rejection from the enclosing
+    /// cube. This consumes three outputs of the generator per attempt, and 6/π attempts on average
+    pub fn in_unit_sphere(&mut self) -> [f64; 3] {
+        loop {
+            let x = 2.0 * self.uniform_double() - 1.0;
+            let y = 2.0 * self.uniform_double() - 1.0;
+            let z = 2.0 * self.uniform_double() - 1.0;
+            if x * x + y * y + z * z < 1.0 {
+                return [x, y, z];
+            }
+        }
+    }
+
+    /// Generates a point on the unit sphere with uniform density using Marsaglia's method, which
+    /// avoids trig functions. This consumes two outputs of the generator per attempt, and 4/π attempts
+    /// on average. Use `.unit_vector_3d()` when a fixed number of outputs matters more
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let [x, y, z] = orangey_ctx.on_unit_sphere();
+    /// assert!((x * x + y * y + z * z - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn on_unit_sphere(&mut self) -> [f64; 3] {
+        let [x, y] = self.in_unit_disk();
+        let s = x * x + y * y;
+        let scale = 2.0 * (1.0 - s).sqrt();
+        [x * scale, y * scale, 1.0 - 2.0 * s]
+    }
+
+    /// Generates angles in radians in the range [`mu` - π, `mu` + π] with a von mises density centered
+    /// on `mu` with concentration `kappa`, using Best & Fisher's method. A `kappa` of 0 is uniform
+    ///
+    /// This consumes two outputs per attempt and one more for the sign, or exactly one output
+    /// when `kappa` is below 1e-6. Above 1e6, where Best & Fisher's method runs out of precision
+    /// and the density is all but a gaussian with variance `1 / kappa`, this returns
+    /// `mu + .gaussian() / kappa.sqrt()` instead
+    ///
+    /// # Panics
+    ///
+    /// Panics if `kappa` is negative or NaN
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let heading = orangey_ctx.von_mises(1.0, 4.0);
+    /// assert!((1.0 - core::f64::consts::PI..=1.0 + core::f64::consts::PI).contains(&heading));
+    /// let steady = orangey_ctx.von_mises(1.0, 1e50);
+    /// assert!((steady - 1.0).abs() < 1e-20);
+    /// ```
+    pub fn von_mises(&mut self, mu: f64, kappa: f64) -> f64 {
+        OrangeyEngine::von_mises(self, mu, kappa)
+    }
+
+    /// Generates floats with a student's t density with `df` degrees of freedom, from a `.gaussian()`
+    /// draw followed by a `.chi_squared()` one
+    ///
+    /// # Panics
+    ///
+    /// Panics if `df` is not positive
+    pub fn student_t(&mut self, df: f64) -> f64 {
+        OrangeyEngine::student_t(self, df)
+    }
+
+    /// Generates floats with a fisher–snedecor F density with `d1` and `d2` degrees of freedom,
+    /// from a `.chi_squared()` draw for each of them
+    ///
+    /// # Panics
+    ///
+    /// Panics if `d1` or `d2` is not positive
+    pub fn fisher_f(&mut self, d1: f64, d2: f64) -> f64 {
+        OrangeyEngine::fisher_f(self, d1, d2)
+    }
+
+    /// Generates floats with a weibull density with the given `shape` and `scale`.
+    /// This consumes exactly one output of the generator
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shape` or `scale` is not positive
+    pub fn weibull(&mut self, shape: f64, scale: f64) -> f64 {
+        OrangeyEngine::weibull(self, shape, scale)
+    }
+
+    /// Generates floats of at least `scale` with a pareto density with tail index `alpha`.
+    /// This consumes exactly one output of the generator
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` or `alpha` is not positive
+    pub fn pareto(&mut self, scale: f64, alpha: f64) -> f64 {
+        OrangeyEngine::pareto(self, scale, alpha)
+    }
+
+    /// Generates floats with a cauchy density centered on `median` with the half-width `scale`.
+    /// This consumes exactly one output of the generator
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is not positive
+    pub fn cauchy(&mut self, median: f64, scale: f64) -> f64 {
+        OrangeyEngine::cauchy(self, median, scale)
+    }
+
+    /// Generates floats in the range [`min`, `max`] with a triangular density peaking at `mode`.
+    /// This consumes exactly one output of the generator
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::{OrangeyCtx, ParamError};
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let days = orangey_ctx.triangular(2.0, 3.0, 7.0).unwrap();
+    /// assert!((2.0..=7.0).contains(&days));
+    /// assert_eq!(orangey_ctx.triangular(2.0, 8.0, 7.0), Err(ParamError::Unordered));
+    /// ```
+    pub fn triangular(&mut self, min: f64, mode: f64, max: f64) -> Result<f64, ParamError> {
+        OrangeyEngine::triangular(self, min, mode, max)
+    }
+
+    /// Generates floats in the range [`min`, `max`] with a PERT density, a smooth beta density
+    /// peaking at `mode`. This consumes the same outputs as one `.beta()` draw
+    pub fn pert(&mut self, min: f64, mode: f64, max: f64) -> Result<f64, ParamError> {
+        OrangeyEngine::pert(self, min, mode, max)
+    }
+
+    /// Generates a vector of probabilities summing to 1 with a dirichlet density with concentration
+    /// parameters `alphas`, built from one `.gamma()` sample per parameter
+    ///
+    /// When every sample underflows for tiny concentrations they are scaled through their
+    /// logarithms instead. If even those run out of range, all of the probability goes to one
+    /// index, picked with probability proportional to its concentration, which takes one more output
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::{OrangeyCtx, ParamError};
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let mix = orangey_ctx.dirichlet(&[1.0, 2.0, 3.0]).unwrap();
+    /// assert!((mix.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    /// assert_eq!(orangey_ctx.dirichlet(&[1.0, 0.0]), Err(ParamError::NotPositive));
+    ///
+    /// let sparse = orangey_ctx.dirichlet(&[1e-300, 1e-300, 1e-300]).unwrap();
+    /// assert!((sparse.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn dirichlet(&mut self, alphas: &[f64]) -> Result<Vec<f64>, ParamError> {
+        OrangeyEngine::dirichlet(self, alphas)
+    }
+
+    /// Generates how many of `n` independent trials land in each category, where trials land in
+    /// category `i` with probability `probs[i]`. This uses one `.binomial()` sample per category
+    ///
+    /// `probs` must be non-negative and sum to 1, allowing for rounding error
+    #[cfg(feature = "alloc")]
+    pub fn multinomial(&mut self, n: u64, probs: &[f64]) -> Result<Vec<u64>, ParamError> {
+        OrangeyEngine::multinomial(self, n, probs)
+    }
+
+    /// Generates integers matching a poisson distribution with an expected value of `ev`.
+    /// Means above 10 use the PTRS algorithm, so this takes constant expected time even for very large `ev`.
+    /// Means up to 10 take one more output than the count returned, and larger ones two per attempt
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ev` is negative or not finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let arrivals = orangey_ctx.poisson(1e6);
+    /// assert!((995_000..1_005_000).contains(&arrivals));
+    /// ```
+    pub fn poisson(&mut self, ev: f64) -> u64 {
+        OrangeyEngine::poisson(self, ev)
+    }
+
+    /// Generates floats with an exponential density with a rate of `lambda`, so the expected value is `1 / lambda`.
+    /// This consumes exactly one output of the generator
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lambda` is not positive and finite
+    pub fn exponential(&mut self, lambda: f64) -> f64 {
+        OrangeyEngine::exponential(self, lambda)
+    }
+
+    /// Generates the number of successes in `n` independent trials that each succeed with probability `p`.
+    /// This takes constant expected time even for very large `n`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in the range [0, 1]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let successes = orangey_ctx.binomial(10_000_000, 0.3);
+    /// assert!((2_990_000..3_010_000).contains(&successes));
+    ///
+    /// // Rare events over a huge number of trials, with a mean of 3
+    /// let n = 100;
+    /// let mean = (0..n)
+    ///     .map(|_| orangey_ctx.binomial(3_000_000_000, 1e-9))
+    ///     .sum::<u64>() as f64
+    ///     / n as f64;
+    /// assert!((2.0..4.0).contains(&mean));
+    /// ```
+    pub fn binomial(&mut self, n: u64, p: f64) -> u64 {
+        OrangeyEngine::binomial(self, n, p)
+    }
+
+    /// Generates the number of failures before the first success in a series of independent trials
+    /// that each succeed with probability `p`. This consumes exactly one output of the generator
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in the range (0, 1]
+    pub fn geometric(&mut self, p: f64) -> u64 {
+        OrangeyEngine::geometric(self, p)
+    }
+
+    /// Generates the number of failures before the `r`-th success in a series of independent trials
+    /// that each succeed with probability `p`. This consumes exactly `r` outputs of the generator
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in the range (0, 1]
+    pub fn negative_binomial(&mut self, r: u64, p: f64) -> u64 {
+        OrangeyEngine::negative_binomial(self, r, p)
+    }
+
+    /// Generates the number of successes in `draws` draws without replacement from `total` items,
+    /// `successes` of which count as a success. This takes constant expected time for large parameters
+    ///
+    /// # Panics
+    ///
+    /// Panics if `total` is greater than 2^53, past which floats no longer count every item, or if
+    /// `successes` or `draws` is greater than `total`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// // How many aces are in a 5 card hand?
+    /// let aces = orangey_ctx.hypergeometric(52, 4, 5);
+    /// assert!(aces <= 4);
+    ///
+    /// // Large draws average out to `draws * successes / total`
+    /// let n = 20_000;
+    /// let mean = (0..n)
+    ///     .map(|_| orangey_ctx.hypergeometric(100_000, 30_000, 5_000) as f64)
+    ///     .sum::<f64>()
+    ///     / n as f64;
+    /// assert!((mean - 1500.0).abs() < 1.0);
+    ///
+    /// // The largest population, where a quarter of the items are drawn on average
+    /// let drawn = orangey_ctx.hypergeometric(1 << 53, 1 << 51, 1 << 51);
+    /// assert!(drawn.abs_diff(1 << 49) < 1 << 30);
+    /// ```
+    pub fn hypergeometric(&mut self, total: u64, successes: u64, draws: u64) -> u64 {
+        OrangeyEngine::hypergeometric(self, total, successes, draws)
+    }
+
+    /// Generates an integer in the range [1, `n`] with a zipf distribution with exponent `s`, so that
+    /// `k` is generated with a probability proportional to `k^-s`. This takes constant expected time
+    /// even for very large `n`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0 or `s` is negative
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let key = orangey_ctx.zipf(1 << 40, 1.1);
+    /// assert!((1..=1 << 40).contains(&key));
+    /// ```
+    pub fn zipf(&mut self, n: u64, s: f64) -> u64 {
+        OrangeyEngine::zipf(self, n, s)
+    }
+
+    /// Generates a value from a distribution whose parameters were set up ahead of time,
+    /// which saves redoing that setup on every call
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::{distributions::Poisson, OrangeyCtx};
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let arrivals = Poisson::new(250.0).unwrap();
+    /// let total: u64 = (0..100).map(|_| orangey_ctx.sample(&arrivals)).sum();
+    /// ```
+    pub fn sample<T, D: Distribution<T>>(&mut self, distribution: &D) -> T {
+        distribution.sample(self)
+    }
+
+    /// Picks a random element of `slice`, or returns `None` if it is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let suits = ["hearts", "diamonds", "clubs", "spades"];
+    /// let suit = orangey_ctx.choose(&suits).unwrap();
+    /// assert!(suits.contains(suit));
+    /// assert_eq!(orangey_ctx.choose::<u8>(&[]), None);
+    /// ```
+    pub fn choose<'a, T>(&mut self, slice: &'a [T]) -> Option<&'a T> {
+        if slice.is_empty() {
+            return None;
+        }
+        Some(&slice[self.gen_range(0..slice.len())])
+    }
+
+    /// Picks a random element of `slice` that can be modified in place, or returns `None` if it is empty.
+    /// This consumes the same outputs as `.choose()`
+    pub fn choose_mut<'a, T>(&mut self, slice: &'a mut [T]) -> Option<&'a mut T> {
+        if slice.is_empty() {
+            return None;
+        }
+        let i = self.gen_range(0..slice.len());
+        Some(&mut slice[i])
+    }
+
+    /// Picks `amount` distinct elements of `slice` in random order, or every element if `amount` is
+    /// larger than the slice
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let players = ["ana", "bo", "cy", "dee", "eli"];
+    /// let team = orangey_ctx.choose_multiple(&players, 3);
+    /// assert_eq!(team.len(), 3);
+    /// assert!(team.iter().all(|p| team.iter().filter(|q| p == *q).count() == 1));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn choose_multiple<'a, T>(&mut self, slice: &'a [T], amount: usize) -> Vec<&'a T> {
+        self.sample_indices(slice.len(), amount.min(slice.len()))
+            .into_iter()
+            .map(|i| &slice[i])
+            .collect()
+    }
+
+    /// Generates a random ordering of `0..n`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let mut order = orangey_ctx.permutation(10);
+    /// order.sort();
+    /// assert_eq!(order, (0..10).collect::<Vec<_>>());
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn permutation(&mut self, n: usize) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..n).collect();
+        self.shuffle(&mut indices);
+        indices
+    }
+
+    /// Picks `amount` distinct indices from `0..len` in random order. Only `O(amount)` memory is
+    /// used when `amount` is small next to `len`, following Floyd's algorithm
+    ///
+    /// # Panics
+    ///
+    /// Panics if `amount` is larger than `len`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let rows = orangey_ctx.sample_indices(usize::MAX, 3);
+    /// assert_eq!(rows.len(), 3);
+    /// assert!(rows[0] != rows[1] && rows[1] != rows[2] && rows[0] != rows[2]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn sample_indices(&mut self, len: usize, amount: usize) -> Vec<usize> {
+        assert!(
+            amount <= len,
+            "cannot pick {} indices out of {}",
+            amount,
+            len
+        );
+        if amount > len / 2 {
+            let mut indices: Vec<usize> = (0..len).collect();
+            self.partial_shuffle(&mut indices, amount);
+            indices.truncate(amount);
+            return indices;
+        }
+        // Each step picks from one more index than the last, and an index that was already picked
+        // stands in for the newest one. Swapping the newcomer into its slot keeps the order random
+        let mut indices = Vec::with_capacity(amount);
+        let mut positions = alloc::collections::BTreeMap::new();
+        for j in len - amount..len {
+            let t = self.gen_range(0..=j);
+            match positions.get(&t).copied() {
+                Some(pos) => {
+                    indices[pos] = j;
+                    positions.insert(j, pos);
+                    positions.insert(t, indices.len());
+                    indices.push(t);
+                }
+                None => {
+                    positions.insert(t, indices.len());
+                    indices.push(t);
+                }
+            }
+        }
+        indices
+    }
+
+    /// Shuffles `slice` in place so that items with larger weights tend to come first. Each position
+    /// is filled by picking from the remaining items with probability proportional to their weights,
+    /// and items of weight zero end up last in uniformly random order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::{OrangeyCtx, ParamError};
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let mut playlist = ["hit", "deep cut", "b-side", "banned"];
+    /// orangey_ctx.weighted_shuffle(&mut playlist, &[10.0, 2.0, 1.0, 0.0]).unwrap();
+    /// assert_eq!(playlist[3], "banned");
+    /// assert_eq!(orangey_ctx.weighted_shuffle(&mut playlist, &[1.0]), Err(ParamError::LengthMismatch));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn weighted_shuffle<T>(
+        &mut self,
+        slice: &mut [T],
+        weights: &[f64],
+    ) -> Result<(), ParamError> {
+        let order = self.weighted_order(weights, slice.len())?;
+        // Apply the permutation in place, following each cycle back to where its element now lives
+        for i in 0..order.len() {
+            let mut j = order[i];
+            while j < i {
+                j = order[j];
+            }
+            slice.swap(i, j);
+        }
+        let zeros = weights.iter().filter(|&&w| w == 0.0).count();
+        let len = slice.len();
+        self.shuffle(&mut slice[len - zeros..]);
+        Ok(())
+    }
+
+    /// Picks `amount` distinct items of `slice`, each pick made from the remaining items with
+    /// probability proportional to their weights. Items of weight zero are never picked, so fewer
+    /// than `amount` items are returned if there aren't enough with positive weight
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let loot = ["sword", "shield", "potion", "rock"];
+    /// let drops = orangey_ctx.choose_multiple_weighted(&loot, &[1.0, 1.0, 5.0, 0.0], 2).unwrap();
+    /// assert_eq!(drops.len(), 2);
+    /// assert!(!drops.contains(&&"rock"));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn choose_multiple_weighted<'a, T>(
+        &mut self,
+        slice: &'a [T],
+        weights: &[f64],
+        amount: usize,
+    ) -> Result<Vec<&'a T>, ParamError> {
+        let order = self.weighted_order(weights, slice.len())?;
+        let positive = weights.iter().filter(|&&w| w > 0.0).count();
+        Ok(order[..amount.min(positive)]
+            .iter()
+            .map(|&i| &slice[i])
+            .collect())
+    }
+
+    /// Orders the indices of `weights` by Efraimidis-Spirakis keys, putting weights of zero last
+    #[cfg(feature = "alloc")]
+    fn weighted_order(&mut self, weights: &[f64], len: usize) -> Result<Vec<usize>, ParamError> {
+        if weights.len() != len {
+            return Err(ParamError::LengthMismatch);
+        }
+        if weights.iter().any(|w| !w.is_finite()) {
+            return Err(ParamError::NotFinite);
+        }
+        if weights.iter().any(|&w| w < 0.0) {
+            return Err(ParamError::Negative);
+        }
+        // Sorting by `u^(1 / w)` takes items in weighted order, and its log is easier to keep in range
+        let keys: Vec<(bool, f64)> = weights
+            .iter()
+            .map(|&w| (w > 0.0, (1.0 - self.uniform_double()).ln() / w))
+            .collect();
+        let mut order: Vec<usize> = (0..len).collect();
+        order.sort_by(|&a, &b| {
+            keys[b]
+                .0
+                .cmp(&keys[a].0)
+                .then(keys[b].1.total_cmp(&keys[a].1))
+        });
+        Ok(order)
+    }
+
+    /// Picks `amount` items from `iter` without needing to know its length in advance, using
+    /// reservoir sampling (algorithm L). Every item has the same chance of being picked, but the picks
+    /// are not returned in random order. Fewer than `amount` items are returned if the iterator runs dry
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let text = "one\ntwo\nthree\nfour\nfive";
+    /// let lines = orangey_ctx.sample_iter(text.lines(), 2);
+    /// assert_eq!(lines.len(), 2);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn sample_iter<I: Iterator>(&mut self, mut iter: I, amount: usize) -> Vec<I::Item> {
+        let mut reservoir: Vec<I::Item> = iter.by_ref().take(amount).collect();
+        if reservoir.len() < amount || amount == 0 {
+            return reservoir;
+        }
+        // Rather than rolling for every item, jump straight to the next one that enters the reservoir
+        let k = amount as f64;
+        let mut w = ((1.0 - self.uniform_double()).ln() / k).exp();
+        loop {
+            let skip = ((1.0 - self.uniform_double()).ln() / (1.0 - w).ln()).floor();
+            match iter.nth(skip as usize) {
+                Some(item) => reservoir[self.gen_range(0..amount)] = item,
+                None => return reservoir,
+            }
+            w *= ((1.0 - self.uniform_double()).ln() / k).exp();
+        }
+    }
+
+    /// Shuffles `slice` in place with the Fisher-Yates algorithm, so that every ordering is equally likely.
+    /// This takes one bounded integer per element but the last, as `.rand_range()` would
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// orangey_ctx.srand(52, 0);
+    /// let mut deck: Vec<u32> = (0..52).collect();
+    /// orangey_ctx.shuffle(&mut deck);
+    ///
+    /// let mut replay = OrangeyCtx::new();
+    /// replay.srand(52, 0);
+    /// let mut same_deck: Vec<u32> = (0..52).collect();
+    /// replay.shuffle(&mut same_deck);
+    /// assert_eq!(deck, same_deck);
+    /// ```
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        let len = slice.len();
+        self.partial_shuffle(slice, len);
+    }
+
+    /// Moves `amount` randomly chosen elements of `slice` to its front in random order, and returns
+    /// them along with the rest of the slice. The whole slice is shuffled if `amount` is at least its length
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let mut deck: Vec<u32> = (0..52).collect();
+    /// let (hand, rest) = orangey_ctx.partial_shuffle(&mut deck, 5);
+    /// assert_eq!((hand.len(), rest.len()), (5, 47));
+    /// ```
+    pub fn partial_shuffle<'a, T>(
+        &mut self,
+        slice: &'a mut [T],
+        amount: usize,
+    ) -> (&'a mut [T], &'a mut [T]) {
+        let amount = amount.min(slice.len());
+        // Once one element is left it has nowhere else to go
+        for i in 0..amount.min(slice.len().saturating_sub(1)) {
+            let j = self.gen_range(i..slice.len());
+            slice.swap(i, j);
+        }
+        slice.split_at_mut(amount)
+    }
+
+    /// Generates a string of `len` characters picked uniformly from `0-9`, `A-Z` and `a-z`, taking
+    /// one bounded integer per character
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let id = orangey_ctx.string_alphanumeric(12);
+    /// assert_eq!(id.len(), 12);
+    /// assert!(id.chars().all(|c| c.is_ascii_alphanumeric()));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn string_alphanumeric(&mut self, len: usize) -> String {
+        (0..len)
+            .map(|_| ALPHANUMERIC[self.gen_range(0..ALPHANUMERIC.len())] as char)
+            .collect()
+    }
+
+    /// Generates a string of `len` lowercase hexadecimal digits
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let token = orangey_ctx.string_hex(32);
+    /// assert!(token.chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn string_hex(&mut self, len: usize) -> String {
+        const HEX: &[u8] = b"0123456789abcdef";
+        (0..len)
+            .map(|_| HEX[self.rand_bits(4) as usize] as char)
+            .collect()
+    }
+
+    /// Generates a string of `len` characters picked uniformly from `alphabet`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alphabet` is empty and `len` is not zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let dna = orangey_ctx.string_from_alphabet(&['A', 'C', 'G', 'T'], 20);
+    /// assert_eq!(dna.len(), 20);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn string_from_alphabet(&mut self, alphabet: &[char], len: usize) -> String {
+        assert!(
+            !alphabet.is_empty() || len == 0,
+            "cannot pick characters from an empty alphabet"
+        );
+        (0..len)
+            .map(|_| alphabet[self.gen_range(0..alphabet.len())])
+            .collect()
+    }
+
+    /// Generates the bytes of a random (version 4) UUID, with the version and variant bits set.
+    /// This consumes exactly two outputs of the generator
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let uuid = orangey_ctx.uuid_v4();
+    /// assert_eq!(uuid[6] >> 4, 4);
+    /// assert_eq!(uuid[8] >> 6, 0b10);
+    /// ```
+    pub fn uuid_v4(&mut self) -> [u8; 16] {
+        let mut bytes = self.rand_u128().to_be_bytes();
+        bytes[6] = (bytes[6] & 0x0F) | 0x40;
+        bytes[8] = (bytes[8] & 0x3F) | 0x80;
+        bytes
+    }
+
+    /// Generates a random UUID in its usual hyphenated lowercase form
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let uuid = orangey_ctx.uuid_v4_string();
+    /// assert_eq!(uuid.len(), 36);
+    /// assert_eq!(&uuid[14..15], "4");
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn uuid_v4_string(&mut self) -> String {
+        format_uuid(self.uuid_v4())
+    }
+
+    /// Generates the bytes of a ULID: `timestamp_ms` as a 48-bit big-endian prefix followed by
+    /// 80 random bits, so ULIDs sort by the time they were made. This consumes exactly two outputs
+    /// of the generator
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timestamp_ms` doesn't fit in 48 bits
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let earlier = orangey_ctx.ulid(1_700_000_000_000);
+    /// let later = orangey_ctx.ulid(1_700_000_000_001);
+    /// assert!(earlier < later);
+    /// ```
+    pub fn ulid(&mut self, timestamp_ms: u64) -> [u8; 16] {
+        assert!(
+            timestamp_ms < 1 << 48,
+            "ULID timestamps must fit in 48 bits, got {}",
+            timestamp_ms
+        );
+        let random = self.rand_u128() & (u128::MAX >> 48);
+        ((timestamp_ms as u128) << 80 | random).to_be_bytes()
+    }
+
+    /// Generates a ULID in its usual 26 character Crockford base32 form
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timestamp_ms` doesn't fit in 48 bits
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let ulid = orangey_ctx.ulid_string(1_700_000_000_000);
+    /// assert_eq!(ulid.len(), 26);
+    /// assert!(ulid.starts_with("01HF"));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn ulid_string(&mut self, timestamp_ms: u64) -> String {
+        const CROCKFORD: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+        let value = u128::from_be_bytes(self.ulid(timestamp_ms));
+        // 26 digits of 5 bits hold 130 bits, so the leading digit only carries the top 3
+        (0..26)
+            .rev()
+            .map(|i| CROCKFORD[(value >> (i * 5)) as usize & 0x1F] as char)
+            .collect()
+    }
+
+    /// Generates a char in `range` with uniform probability, skipping the surrogate code points
+    /// that no char can hold
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let letter = orangey_ctx.char_range('a'..='z');
+    /// assert!(letter.is_ascii_lowercase());
+    /// let near_gap = orangey_ctx.char_range('\u{D7FF}'..='\u{E000}');
+    /// assert!(near_gap == '\u{D7FF}' || near_gap == '\u{E000}');
+    /// ```
+    pub fn char_range(&mut self, range: RangeInclusive<char>) -> char {
+        const GAP_START: u32 = 0xD800;
+        const GAP_LEN: u32 = 0x800;
+        let (start, end) = (*range.start() as u32, *range.end() as u32);
+        assert!(start <= end, "cannot sample from the range {:?}", range);
+        // Close up the gap, pick from the contiguous range, then open it back up
+        let squash = |c: u32| if c >= GAP_START { c - GAP_LEN } else { c };
+        let c = self.gen_range(squash(start)..=squash(end));
+        let c = if c >= GAP_START { c + GAP_LEN } else { c };
+        char::from_u32(c).unwrap()
+    }
+
+    /// Generates any Unicode scalar value with uniform probability
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let fuzz: String = (0..16).map(|_| orangey_ctx.any_char()).collect();
+    /// assert_eq!(fuzz.chars().count(), 16);
+    /// ```
+    pub fn any_char(&mut self) -> char {
+        self.char_range('\0'..=char::MAX)
+    }
+
+    /// Peeks at the `delta`-th future result of `.rand_u128()` without changing the rng state
+    pub fn peek_u128(&self, delta: u128) -> u128 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.rand_u128()
+    }
+
+    /// Peeks at the `delta`-th future result of `.rand_range(range)` without changing the rng state
+    pub fn peek_range(&self, delta: u128, range: Range<u64>) -> u64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.rand_range(range)
+    }
+
+    /// Peeks at the `delta`-th future result of `.uniform_double()` without changing the rng state
+    pub fn peek_uniform_double(&self, delta: u128) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.uniform_double()
+    }
+
+    /// Peeks at the `delta`-th future result of `.rand_range_f64(range)` without changing the rng state
+    pub fn peek_range_f64(&self, delta: u128, range: Range<f64>) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.rand_range_f64(range)
+    }
+
+    /// Peeks at the `delta`-th future result of `.rand_range_f32(range)` without changing the rng state
+    pub fn peek_range_f32(&self, delta: u128, range: Range<f32>) -> f32 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.rand_range_f32(range)
+    }
+
+    /// Peeks at the `delta`-th future result of `.all_doubles()` without changing the rng state.
+    /// `delta` counts outputs, and `.all_doubles()` sometimes takes more than one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::seed_from_str("peek consistency");
+    /// for _ in 0..10_000 {
+    ///     let ahead = orangey_ctx.peek_all_doubles(0);
+    ///     let outputs = if orangey_ctx.peek(0).leading_zeros() < 12 { 1 } else { 2 };
+    ///     let after = orangey_ctx.peek(outputs);
+    ///     assert_eq!(orangey_ctx.all_doubles(), ahead);
+    ///     assert_eq!(orangey_ctx.peek(0), after);
+    /// }
+    /// ```
+    pub fn peek_all_doubles(&self, delta: u128) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.all_doubles()
+    }
+
+    /// Peeks at the `delta`-th future result of `.gaussian()` without changing the rng state
+    pub fn peek_gaussian(&self, delta: u128) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.gaussian()
+    }
+
+    /// Peeks at the `delta`-th future result of `.uniform_float()` without changing the rng state
+    pub fn peek_uniform_float(&self, delta: u128) -> f32 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.uniform_float()
+    }
+
+    /// Peeks at the `delta`-th future result of `.all_floats()` without changing the rng state
+    pub fn peek_all_floats(&self, delta: u128) -> f32 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.all_floats()
+    }
+
+    /// Peeks at the `delta`-th future result of `.gaussian_with(mean, std_dev)` without changing the rng state
+    pub fn peek_gaussian_with(&self, delta: u128, mean: f64, std_dev: f64) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.gaussian_with(mean, std_dev)
+    }
+
+    /// Peeks at the `delta`-th future result of `.gaussian_f32()` without changing the rng state
+    pub fn peek_gaussian_f32(&self, delta: u128) -> f32 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.gaussian_f32()
+    }
+
+    /// Peeks at the `delta`-th future result of `.exponential(lambda)` without changing the rng state
+    pub fn peek_exponential(&self, delta: u128, lambda: f64) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.exponential(lambda)
+    }
+
+    /// Peeks at the `delta`-th future result of `.binomial(n, p)` without changing the rng state
+    pub fn peek_binomial(&self, delta: u128, n: u64, p: f64) -> u64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.binomial(n, p)
+    }
+
+    /// Peeks at the `delta`-th future result of `.geometric(p)` without changing the rng state
+    pub fn peek_geometric(&self, delta: u128, p: f64) -> u64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.geometric(p)
+    }
+
+    /// Peeks at the `delta`-th future result of `.negative_binomial(r, p)` without changing the rng state
+    pub fn peek_negative_binomial(&self, delta: u128, r: u64, p: f64) -> u64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.negative_binomial(r, p)
+    }
+
+    /// Peeks at the `delta`-th future result of `.hypergeometric(total, successes, draws)` without changing the rng state
+    pub fn peek_hypergeometric(&self, delta: u128, total: u64, successes: u64, draws: u64) -> u64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.hypergeometric(total, successes, draws)
+    }
+
+    /// Peeks at the `delta`-th future result of `.zipf(n, s)` without changing the rng state
+    pub fn peek_zipf(&self, delta: u128, n: u64, s: f64) -> u64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.zipf(n, s)
+    }
+
+    /// Peeks at the `delta`-th future result of `.gaussian_fast()` without changing the rng state
+    pub fn peek_gaussian_fast(&self, delta: u128) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.gaussian_fast()
+    }
+
+    /// Peeks at the `delta`-th future result of `.exponential_fast()` without changing the rng state
+    pub fn peek_exponential_fast(&self, delta: u128) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.exponential_fast()
+    }
+
+    /// Peeks at the `delta`-th future result of `.gamma(shape, scale)` without changing the rng state
+    pub fn peek_gamma(&self, delta: u128, shape: f64, scale: f64) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.gamma(shape, scale)
+    }
+
+    /// Peeks at the `delta`-th future result of `.beta(a, b)` without changing the rng state
+    pub fn peek_beta(&self, delta: u128, a: f64, b: f64) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.beta(a, b)
+    }
+
+    /// Peeks at the `delta`-th future result of `.chi_squared(k)` without changing the rng state
+    pub fn peek_chi_squared(&self, delta: u128, k: f64) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.chi_squared(k)
+    }
+
+    /// Peeks at the `delta`-th future result of `.log_normal(mu, sigma)` without changing the rng state
+    pub fn peek_log_normal(&self, delta: u128, mu: f64, sigma: f64) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.log_normal(mu, sigma)
+    }
+
+    /// Peeks at the `delta`-th future result of `.angle()` without changing the rng state
+    pub fn peek_angle(&self, delta: u128) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.angle()
+    }
+
+    /// Peeks at the `delta`-th future result of `.von_mises(mu, kappa)` without changing the rng state
+    pub fn peek_von_mises(&self, delta: u128, mu: f64, kappa: f64) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.von_mises(mu, kappa)
+    }
+
+    /// Peeks at the `delta`-th future result of `.student_t(df)` without changing the rng state
+    pub fn peek_student_t(&self, delta: u128, df: f64) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.student_t(df)
+    }
+
+    /// Peeks at the `delta`-th future result of `.fisher_f(d1, d2)` without changing the rng state
+    pub fn peek_fisher_f(&self, delta: u128, d1: f64, d2: f64) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.fisher_f(d1, d2)
+    }
+
+    /// Peeks at the `delta`-th future result of `.weibull(shape, scale)` without changing the rng state
+    pub fn peek_weibull(&self, delta: u128, shape: f64, scale: f64) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.weibull(shape, scale)
+    }
+
+    /// Peeks at the `delta`-th future result of `.pareto(scale, alpha)` without changing the rng state
+    pub fn peek_pareto(&self, delta: u128, scale: f64, alpha: f64) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.pareto(scale, alpha)
+    }
+
+    /// Peeks at the `delta`-th future result of `.cauchy(median, scale)` without changing the rng state
+    pub fn peek_cauchy(&self, delta: u128, median: f64, scale: f64) -> f64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.cauchy(median, scale)
+    }
+
+    /// Peeks at the `delta`-th future result of `.triangular(min, mode, max)` without changing the rng state
+    pub fn peek_triangular(
+        &self,
+        delta: u128,
+        min: f64,
+        mode: f64,
+        max: f64,
+    ) -> Result<f64, ParamError> {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.triangular(min, mode, max)
+    }
+
+    /// Peeks at the `delta`-th future result of `.pert(min, mode, max)` without changing the rng state
+    pub fn peek_pert(&self, delta: u128, min: f64, mode: f64, max: f64) -> Result<f64, ParamError> {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.pert(min, mode, max)
+    }
+
+    /// Peeks at the `delta`-th future result of `.poisson(ev)` without changing the rng state
+    pub fn peek_poisson(&self, delta: u128, ev: f64) -> u64 {
+        let mut new_self = self.snapshot();
+        new_self.skip(delta);
+        new_self.poisson(ev)
+    }
+
+    const MUL: u128 = 0x2360ed051fc65da44385df649fccf645;
+
+    // Bumped whenever the meaning of a saved state changes
+    const HEX_VERSION: &'static str = "o1:";
+
+    /// Seeds from the little-endian `initstate` in the first 16 bytes and `initseq` in the last 16
+    fn srand_bytes(&mut self, seed: [u8; 32]) {
+        let (initstate, initseq) = seed.split_at(16);
+        self.srand(
+            u128::from_le_bytes(initstate.try_into().unwrap()),
+            u128::from_le_bytes(initseq.try_into().unwrap()),
+        );
+    }
+
+    /// Copies everything about the generator except its saved checkpoints
+    fn snapshot(&self) -> OrangeyCtx {
+        OrangeyCtx {
+            #[cfg(feature = "alloc")]
+            checkpoints: Vec::new(),
+            ..*self
+        }
+    }
+
+    fn checkpoints(&self) -> &[OrangeyCtx] {
+        #[cfg(feature = "alloc")]
+        return &self.checkpoints;
+        #[cfg(not(feature = "alloc"))]
+        return &[];
+    }
+
+    fn clear_buffers(&mut self) {
+        self.buffered_bits = 0;
+        self.gaussian_spare = None;
+    }
+
+    #[inline]
+    fn output(state: u128) -> u64 {
+        permutation::XslRr::permute(state)
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        self.state = self.state.wrapping_mul(Self::MUL).wrapping_add(self.inc);
+    }
+
+    // Written with `wrapping_*` calls rather than `Wrapping` so it can run at compile time
+    const fn advance(state: u128, delta: u128, cur_mult: u128, cur_plus: u128) -> u128 {
+        let mut delta = delta;
+        let mut cur_mult = cur_mult;
+        let mut cur_plus = cur_plus;
+
+        let mut acc_mult: u128 = 1;
+        let mut acc_plus: u128 = 0;
+        while delta > 0 {
+            if delta & 1 != 0 {
+                acc_mult = acc_mult.wrapping_mul(cur_mult);
+                acc_plus = acc_plus.wrapping_mul(cur_mult).wrapping_add(cur_plus);
+            }
+            cur_plus = cur_plus.wrapping_mul(cur_mult.wrapping_add(1));
+            cur_mult = cur_mult.wrapping_mul(cur_mult);
+            delta /= 2;
+        }
+        acc_mult.wrapping_mul(state).wrapping_add(acc_plus)
+    }
+
+    /// Finds how many steps take `cur_state` to `new_state`, settling one bit of the distance at a
+    /// time from the lowest up. Bit `k` of the state only depends on the low `k` bits of the distance
+    fn distance(cur_state: u128, new_state: u128, cur_mult: u128, cur_plus: u128) -> u128 {
+        let mut cur_state = Wrapping(cur_state);
+        let new_state = Wrapping(new_state);
+        let mut cur_mult = Wrapping(cur_mult);
+        let mut cur_plus = Wrapping(cur_plus);
+
+        let mut the_bit = Wrapping(1);
+        let mut distance = Wrapping(0);
+        while cur_state != new_state {
+            if cur_state & the_bit != new_state & the_bit {
+                cur_state = cur_state * cur_mult + cur_plus;
+                distance |= the_bit;
+            }
+            the_bit <<= 1;
+            cur_plus *= cur_mult + Wrapping(1);
+            cur_mult *= cur_mult;
+        }
+        distance.0
+    }
+}
+
+/// Integer types that can be generated in bulk by [`OrangeyCtx::fill_slice`]
+pub trait Fillable: Sized {
+    /// Fills `dest` from the little-endian bytes of consecutive outputs of `ctx`
+    fn fill(ctx: &mut OrangeyCtx, dest: &mut [Self]);
+}
+
+macro_rules! fillable_impl {
+    ($($type:ty),*) => {$(
+        impl Fillable for $type {
+            fn fill(ctx: &mut OrangeyCtx, dest: &mut [Self]) {
+                for chunk in dest.chunks_mut(8 / core::mem::size_of::<$type>()) {
+                    let r = ctx.rand();
+                    for (i, v) in chunk.iter_mut().enumerate() {
+                        *v = (r >> (i as u32 * <$type>::BITS)) as $type;
+                    }
+                }
+            }
+        }
+    )*};
+}
+
+fillable_impl!(u8, u16, u32, u64);
+
+/// The reason a range could not be sampled from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeError {
+    /// The range contains no values, like `3..3`
+    Empty,
+    /// The start of the range is past its end, like `5..3`
+    Inverted,
+}
+
+impl RangeError {
+    /// Turns any kind of range into its inclusive `(low, high)` bounds, if it isn't empty
+    fn check_bounds<R: RangeBounds<u64>>(range: &R) -> Result<(u64, u64), RangeError> {
+        let low = match range.start_bound() {
+            Bound::Included(&low) => low,
+            Bound::Excluded(&low) => low.checked_add(1).ok_or(RangeError::Empty)?,
+            Bound::Unbounded => 0,
+        };
+        let high = match range.end_bound() {
+            Bound::Included(&high) => high,
+            Bound::Excluded(&high) => high.checked_sub(1).ok_or(RangeError::Empty)?,
+            Bound::Unbounded => u64::MAX,
+        };
+        if low > high {
+            return Err(if low - high == 1 {
+                RangeError::Empty
+            } else {
+                RangeError::Inverted
+            });
+        }
+        Ok((low, high))
+    }
+}
+
+#[cfg(feature = "alloc")]
+const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Writes out the bytes of a UUID in its usual hyphenated lowercase form
+#[cfg(feature = "alloc")]
+fn format_uuid(bytes: [u8; 16]) -> String {
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// Overwrites `value` with its default in a way the compiler can't leave out as a dead store
+#[cfg(feature = "zeroize")]
+fn wipe<T: Copy + Default>(value: &mut T) {
+    // SAFETY: `value` is a valid, aligned and exclusive reference, and `T` has no destructor that
+    // overwriting could skip
+    unsafe { core::ptr::write_volatile(value, T::default()) };
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
+/// Generates a number in `[0, distance)` from the outputs of `next`, with 0 standing for the full
+/// 2^64 values. This is Lemire's method: the high half of `r * distance` is the result, and `r` is
+/// redrawn while the low half lands in the few values that would make some results more likely.
+/// Each draw costs one output, with at most one division and that only when a redraw is possible
+#[inline]
+fn bounded(distance: u64, mut next: impl FnMut() -> u64) -> u64 {
+    if distance == 0 {
+        return next();
+    }
+    let mut m = next() as u128 * distance as u128;
+    if (m as u64) < distance {
+        let threshold = distance.wrapping_neg() % distance;
+        while (m as u64) < threshold {
+            m = next() as u128 * distance as u128;
+        }
+    }
+    (m >> 64) as u64
+}
+
+/// Picks a point uniformly in the unit disk by rejection from the square [-1, 1)², two outputs per
+/// attempt, and scales it into a pair of independent standard gaussian deviates
+fn gaussian_pair<E: OrangeyEngine + ?Sized>(ctx: &mut E) -> (f64, f64) {
+    loop {
+        let u = 2.0 * ctx.uniform_double() - 1.0;
+        let v = 2.0 * ctx.uniform_double() - 1.0;
+        let s = u * u + v * v;
+        if s > 0.0 && s < 1.0 {
+            let m = (-2.0 * s.ln() / s).sqrt();
+            return (u * m, v * m);
+        }
+    }
+}
+
+/// Generates a `.gamma(shape, 1.0)` draw along with its natural logarithm, which stays finite for
+/// tiny shapes where the draw itself underflows to 0
+fn gamma_with_ln<E: OrangeyEngine + ?Sized>(ctx: &mut E, shape: f64) -> (f64, f64) {
+    if shape < 1.0 {
+        let u = 1.0 - ctx.uniform_double();
+        let g = ctx.gamma(shape + 1.0, 1.0);
+        return (g * u.powf(1.0 / shape), g.ln() + u.ln() / shape);
+    }
+    let x = ctx.gamma(shape, 1.0);
+    (x, x.ln())
+}
+
+impl core::fmt::Display for RangeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RangeError::Empty => write!(f, "cannot sample from an empty range"),
+            RangeError::Inverted => {
+                write!(f, "cannot sample from a range whose start is past its end")
+            }
+        }
+    }
+}
+
+impl core::error::Error for RangeError {}
+
+/// The reason a string from `.save_hex()` couldn't be restored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// The string doesn't start with a version prefix this build knows
+    UnknownVersion,
+    /// The string has the wrong number of digits
+    WrongLength,
+    /// The string contains something that isn't a hex digit
+    InvalidDigit,
+    /// The stream increment is even, which no generator can have
+    EvenIncrement,
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseError::UnknownVersion => write!(f, "unknown saved state version"),
+            ParseError::WrongLength => write!(f, "saved state must have 64 hex digits"),
+            ParseError::InvalidDigit => write!(f, "saved state contains a non-hex character"),
+            ParseError::EvenIncrement => write!(f, "saved stream increment must be odd"),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// The reason the parameters of a distribution were rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamError {
+    /// A parameter is NaN or infinite
+    NotFinite,
+    /// The parameters are not in ascending order, like a `mode` past `max`
+    Unordered,
+    /// The distribution has no width, like `min` being equal to `max`
+    Degenerate,
+    /// No parameters were given where at least one is needed
+    Empty,
+    /// A parameter that must be positive is zero or negative
+    NotPositive,
+    /// Probabilities that must sum to 1 do not
+    NotNormalized,
+    /// A parameter that must not be negative is
+    Negative,
+    /// A list of weights is not the same length as the items it weighs
+    LengthMismatch,
+}
+
+impl ParamError {
+    /// Checks that `min <= mode <= max` describes a distribution of nonzero width
+    fn check_bounded(min: f64, mode: f64, max: f64) -> Result<(), ParamError> {
+        if !(min.is_finite() && mode.is_finite() && max.is_finite()) {
+            Err(ParamError::NotFinite)
+        } else if !(min <= mode && mode <= max) {
+            Err(ParamError::Unordered)
+        } else if min == max {
+            Err(ParamError::Degenerate)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl core::fmt::Display for ParamError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParamError::NotFinite => write!(f, "distribution parameters must be finite"),
+            ParamError::Unordered => write!(f, "distribution parameters are out of order"),
+            ParamError::Degenerate => write!(f, "distribution has no width"),
+            ParamError::Empty => write!(f, "distribution needs at least one parameter"),
+            ParamError::NotPositive => write!(f, "distribution parameters must be positive"),
+            ParamError::NotNormalized => write!(f, "probabilities must sum to 1"),
+            ParamError::Negative => write!(f, "distribution parameters must not be negative"),
+            ParamError::LengthMismatch => write!(f, "there must be exactly one weight per item"),
+        }
+    }
+}
+
+impl core::error::Error for ParamError {}
+
+/// Integer types that can be generated by [`OrangeyCtx::gen_range`]
+pub trait SampleUniform: Sized {
+    /// Generates a value in `range` with uniform probability, panicking if it is empty
+    fn sample_range<R: RangeBounds<Self>>(ctx: &mut OrangeyCtx, range: R) -> Self;
+}
+
+macro_rules! sample_uniform_impl {
+    ($sampler:ident, $wide:ty, $($type:ty => $unsigned:ty),*) => {$(
+        impl SampleUniform for $type {
+            fn sample_range<R: RangeBounds<Self>>(ctx: &mut OrangeyCtx, range: R) -> Self {
+                let low = match range.start_bound() {
+                    Bound::Included(&low) => Some(low),
+                    Bound::Excluded(&low) => low.checked_add(1),
+                    Bound::Unbounded => Some(<$type>::MIN),
+                };
+                let high = match range.end_bound() {
+                    Bound::Included(&high) => Some(high),
+                    Bound::Excluded(&high) => high.checked_sub(1),
+                    Bound::Unbounded => Some(<$type>::MAX),
+                };
+                let (low, high) = match (low, high) {
+                    (Some(low), Some(high)) if low <= high => (low, high),
+                    _ => panic!("cannot sample from an empty range"),
+                };
+                let span = (high as $unsigned).wrapping_sub(low as $unsigned) as $wide;
+                low.wrapping_add(ctx.$sampler(span) as $type)
+            }
+        }
+    )*};
+}
+
+sample_uniform_impl!(sample_span_u64, u64, u8 => u8, u16 => u16, u32 => u32, u64 => u64, usize => usize);
+sample_uniform_impl!(sample_span_u64, u64, i8 => u8, i16 => u16, i32 => u32, i64 => u64, isize => usize);
+sample_uniform_impl!(sample_span_u128, u128, u128 => u128, i128 => u128);
+
+/// The operations every generator in the crate supports, so code can be written once and run on
+/// [`OrangeyCtx`], [`Orangey32Ctx`], [`OrangeyExtCtx`], any [`OrangeyCore`] or, with the `secure`
+#[cfg_attr(
+    feature = "secure",
+    doc = "feature, [`SecureOrangeyCtx`]. The samplers"
+)]
+#[cfg_attr(
+    not(feature = "secure"),
+    doc = "feature, `SecureOrangeyCtx`. The samplers"
+)]
+/// here work like [`OrangeyCtx`]'s methods of the same names, and the helpers in
+/// [`distributions`], [`sampling`] and [`quasi`] take any engine
+///
+/// Only `next_u64`, `skip` and `peek_raw` have to be written; the rest are built on `next_u64`,
+/// and engines with their own versions of them use those instead
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{Orangey32Ctx, OrangeyCtx, OrangeyEngine};
+///
+/// fn coin_flips(engine: &mut impl OrangeyEngine, n: usize) -> usize {
+///     (0..n).filter(|_| engine.uniform_double() < 0.5).count()
+/// }
+///
+/// let heads_64 = coin_flips(&mut OrangeyCtx::new(), 1000);
+/// let heads_32 = coin_flips(&mut Orangey32Ctx::new(), 1000);
+/// assert!((400..600).contains(&heads_64) && (400..600).contains(&heads_32));
+/// ```
+///
+/// Engines on the same stream give the same values through the trait:
+///
+/// ```
+/// use orangey::{
+///     distributions::Poisson, permutation::XslRr, OrangeyCore, OrangeyCtx, OrangeyEngine,
+/// };
+///
+/// let (mut ctx, mut core) = (OrangeyCtx::new(), OrangeyCore::<XslRr>::new());
+/// assert_eq!(OrangeyEngine::next_u32(&mut ctx), core.next_u32());
+/// let arrivals = Poisson::new(250.0).unwrap();
+/// assert_eq!(ctx.sample(&arrivals), core.sample(&arrivals));
+/// assert_eq!(ctx.gamma(2.5, 1.0), core.gamma(2.5, 1.0));
+/// ```
+pub trait OrangeyEngine {
+    /// Returns the next 64 random bits
+    fn next_u64(&mut self) -> u64;
+
+    /// Jumps `delta` results of `.next_u64()` ahead
+    fn skip(&mut self, delta: u128);
+
+    /// Returns what the `delta`-th next call to `.next_u64()` would, without moving the engine
+    fn peek_raw(&self, delta: u128) -> u64;
+
+    /// Returns the low 32 bits of the next `.next_u64()` result. Every engine takes a whole result
+    /// for it, so the same seed gives the same values on engines with the same `.next_u64()`, and
+    /// their `rand_core::RngCore::next_u32` gives the same values too. `OrangeyCtx::rand_u32`,
+    /// which keeps the high half for the next call, and `Orangey32Ctx::rand`, which takes a single
+    /// 32-bit output, are the ways to spend fewer bits
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    /// Generates a float in the range [0, 1) with uniform density
+    fn uniform_double(&mut self) -> f64 {
+        const MASK: u64 = 0x000FFFFFFFFFFFFF;
+        const S_EXP: u64 = 0x3FF0000000000000;
+        f64::from_bits((self.next_u64() & MASK) | S_EXP) - 1.0
+    }
+
+    /// Generates a number in the range given
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty
+    fn rand_range(&mut self, range: Range<u64>) -> u64 {
+        assert!(!range.is_empty(), "{}", RangeError::Empty);
+        range.start + bounded(range.end - range.start, || self.next_u64())
+    }
+
+    /// Shuffles `slice` into a uniformly random order
+    fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in 0..slice.len().saturating_sub(1) {
+            let j = self.rand_range(i as u64..slice.len() as u64) as usize;
+            slice.swap(i, j);
+        }
+    }
+
+    /// Generates a value from a distribution whose parameters were set up ahead of time, like
+    /// [`OrangeyCtx::sample`]
+    fn sample<T, D: Distribution<T>>(&mut self, distribution: &D) -> T
+    where
+        Self: Sized,
+    {
+        distribution.sample(self)
+    }
+
+    /// Generates floats with standard gaussian density with the Marsaglia polar method, like
+    /// [`OrangeyCtx::gaussian`]. Engines with nowhere to keep the second deviate of each pair
+    /// throw it away, so every call takes two outputs per attempt
+    fn gaussian(&mut self) -> f64 {
+        gaussian_pair(self).0
+    }
+
+    /// Generates floats with gaussian density centered on `mean` with a standard deviation of
+    /// `std_dev`, like [`OrangeyCtx::gaussian_with`]
+    fn gaussian_with(&mut self, mean: f64, std_dev: f64) -> f64 {
+        mean + std_dev * self.gaussian()
+    }
+
+    /// Generates floats with standard gaussian density using the ziggurat method, like
+    /// [`OrangeyCtx::gaussian_fast`]
+    fn gaussian_fast(&mut self) -> f64 {
+        ziggurat::sample(
+            self,
+            true,
+            &ziggurat::NORM_X,
+            &ziggurat::NORM_F,
+            ziggurat::normal_pdf,
+            ziggurat::normal_tail,
+        )
+    }
+
+    /// Generates floats with an exponential density with a rate of `lambda`, like
+    /// [`OrangeyCtx::exponential`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lambda` is not positive and finite
+    fn exponential(&mut self, lambda: f64) -> f64 {
+        assert!(
+            lambda > 0.0 && lambda.is_finite(),
+            "rate {} is not positive and finite",
+            lambda
+        );
+        -(1.0 - self.uniform_double()).ln() / lambda
+    }
+
+    /// Generates floats with a standard exponential density using the ziggurat method, like
+    /// [`OrangeyCtx::exponential_fast`]
+    fn exponential_fast(&mut self) -> f64 {
+        ziggurat::sample(
+            self,
+            false,
+            &ziggurat::EXP_X,
+            &ziggurat::EXP_F,
+            ziggurat::exp_pdf,
+            ziggurat::exp_tail,
+        )
+    }
+
+    /// Generates floats with a gamma density with the given `shape` and `scale`, like
+    /// [`OrangeyCtx::gamma`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shape` or `scale` is not positive
+    fn gamma(&mut self, shape: f64, scale: f64) -> f64 {
+        assert!(shape > 0.0, "shape {} is not positive", shape);
+        assert!(scale > 0.0, "scale {} is not positive", scale);
+        if shape < 1.0 {
+            // Boost the shape above 1 and correct for it with a power of a uniform
+            let u = 1.0 - self.uniform_double();
+            return self.gamma(shape + 1.0, scale) * u.powf(1.0 / shape);
+        }
+        let d = shape - 1.0 / 3.0;
+        let c = 1.0 / (9.0 * d).sqrt();
+        loop {
+            let x = self.gaussian_fast();
+            let v = 1.0 + c * x;
+            if v <= 0.0 {
+                continue;
+            }
+            let v = v * v * v;
+            let u = self.uniform_double();
+            let x2 = x * x;
+            if u < 1.0 - 0.0331 * x2 * x2 || u.ln() < 0.5 * x2 + d * (1.0 - v + v.ln()) {
+                return d * v * scale;
+            }
+        }
+    }
+
+    /// Generates floats in the range [0, 1] with a beta density with shape parameters `a` and
+    /// `b`, like [`OrangeyCtx::beta`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` is not positive and finite
+    fn beta(&mut self, a: f64, b: f64) -> f64 {
+        assert!(
+            a > 0.0 && a.is_finite() && b > 0.0 && b.is_finite(),
+            "shapes {} and {} are not both positive and finite",
+            a,
+            b
+        );
+        let (x, ln_x) = gamma_with_ln(self, a);
+        let (y, ln_y) = gamma_with_ln(self, b);
+        if x + y > 0.0 {
+            return x / (x + y);
+        }
+        // Both underflowed, so compare them as logarithms
+        let ln_ratio = ln_y - ln_x;
+        if ln_ratio.is_nan() {
+            return if self.uniform_double() * (a + b) < a {
+                1.0
+            } else {
+                0.0
+            };
+        }
+        1.0 / (1.0 + ln_ratio.exp())
+    }
+
+    /// Generates floats with a chi-squared density with `k` degrees of freedom, like
+    /// [`OrangeyCtx::chi_squared`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is not positive
+    fn chi_squared(&mut self, k: f64) -> f64 {
+        self.gamma(k / 2.0, 2.0)
+    }
+
+    /// Generates floats whose natural logarithm has gaussian density with a mean of `mu` and a
+    /// standard deviation of `sigma`, like [`OrangeyCtx::log_normal`]
+    fn log_normal(&mut self, mu: f64, sigma: f64) -> f64 {
+        self.gaussian_with(mu, sigma).exp()
+    }
+
+    /// Generates angles in radians with a von mises density centered on `mu` with concentration
+    /// `kappa`, like [`OrangeyCtx::von_mises`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `kappa` is negative or NaN
+    fn von_mises(&mut self, mu: f64, kappa: f64) -> f64 {
+        use core::f64::consts::PI;
+        assert!(kappa >= 0.0, "concentration {} is negative", kappa);
+        if kappa < 1e-6 {
+            return mu + PI * (2.0 * self.uniform_double() - 1.0);
+        }
+        if kappa > 1e6 {
+            return mu + self.gaussian() / kappa.sqrt();
+        }
+        let tau = 1.0 + (1.0 + 4.0 * kappa * kappa).sqrt();
+        let rho = (tau - (2.0 * tau).sqrt()) / (2.0 * kappa);
+        let r = (1.0 + rho * rho) / (2.0 * rho);
+        let f = loop {
+            let z = (PI * self.uniform_double()).cos();
+            let f = (1.0 + r * z) / (r + z);
+            let c = kappa * (r - f);
+            let u = 1.0 - self.uniform_double();
+            if c * (2.0 - c) > u || (c / u).ln() + 1.0 >= c {
+                break f;
+            }
+        };
+        let theta = f.clamp(-1.0, 1.0).acos();
+        if self.uniform_double() < 0.5 {
+            mu - theta
+        } else {
+            mu + theta
+        }
+    }
+
+    /// Generates floats with a student's t density with `df` degrees of freedom, like
+    /// [`OrangeyCtx::student_t`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `df` is not positive
+    fn student_t(&mut self, df: f64) -> f64 {
+        let z = self.gaussian();
+        z / (self.chi_squared(df) / df).sqrt()
+    }
+
+    /// Generates floats with a fisher–snedecor F density with `d1` and `d2` degrees of freedom,
+    /// like [`OrangeyCtx::fisher_f`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `d1` or `d2` is not positive
+    fn fisher_f(&mut self, d1: f64, d2: f64) -> f64 {
+        let x = self.chi_squared(d1) / d1;
+        x / (self.chi_squared(d2) / d2)
+    }
+
+    /// Generates floats with a weibull density with the given `shape` and `scale`, like
+    /// [`OrangeyCtx::weibull`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shape` or `scale` is not positive
+    fn weibull(&mut self, shape: f64, scale: f64) -> f64 {
+        assert!(shape > 0.0, "shape {} is not positive", shape);
+        assert!(scale > 0.0, "scale {} is not positive", scale);
+        scale * (-(1.0 - self.uniform_double()).ln()).powf(1.0 / shape)
+    }
+
+    /// Generates floats of at least `scale` with a pareto density with tail index `alpha`, like
+    /// [`OrangeyCtx::pareto`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` or `alpha` is not positive
+    fn pareto(&mut self, scale: f64, alpha: f64) -> f64 {
+        assert!(scale > 0.0, "scale {} is not positive", scale);
+        assert!(alpha > 0.0, "alpha {} is not positive", alpha);
+        scale / (1.0 - self.uniform_double()).powf(1.0 / alpha)
+    }
+
+    /// Generates floats with a cauchy density centered on `median` with the half-width `scale`,
+    /// like [`OrangeyCtx::cauchy`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is not positive
+    fn cauchy(&mut self, median: f64, scale: f64) -> f64 {
+        assert!(scale > 0.0, "scale {} is not positive", scale);
+        median + scale * (core::f64::consts::PI * (self.uniform_double() - 0.5)).tan()
+    }
+
+    /// Generates floats in the range [`min`, `max`] with a triangular density peaking at `mode`,
+    /// like [`OrangeyCtx::triangular`]
+    fn triangular(&mut self, min: f64, mode: f64, max: f64) -> Result<f64, ParamError> {
+        ParamError::check_bounded(min, mode, max)?;
+        let u = self.uniform_double();
+        let width = max - min;
+        if u * width < mode - min {
+            Ok(min + (u * width * (mode - min)).sqrt())
+        } else {
+            Ok(max - ((1.0 - u) * width * (max - mode)).sqrt())
+        }
+    }
+
+    /// Generates floats in the range [`min`, `max`] with a PERT density peaking at `mode`, like
+    /// [`OrangeyCtx::pert`]
+    fn pert(&mut self, min: f64, mode: f64, max: f64) -> Result<f64, ParamError> {
+        ParamError::check_bounded(min, mode, max)?;
+        let width = max - min;
+        let a = 1.0 + 4.0 * (mode - min) / width;
+        let b = 1.0 + 4.0 * (max - mode) / width;
+        Ok(min + self.beta(a, b) * width)
+    }
+
+    /// Generates a vector of probabilities summing to 1 with a dirichlet density with
+    /// concentration parameters `alphas`, like [`OrangeyCtx::dirichlet`]
+    #[cfg(feature = "alloc")]
+    fn dirichlet(&mut self, alphas: &[f64]) -> Result<Vec<f64>, ParamError> {
+        if alphas.is_empty() {
+            return Err(ParamError::Empty);
+        }
+        if alphas.iter().any(|a| !a.is_finite()) {
+            return Err(ParamError::NotFinite);
+        }
+        if alphas.iter().any(|&a| a <= 0.0) {
+            return Err(ParamError::NotPositive);
+        }
+        let (mut x, ln_x): (Vec<f64>, Vec<f64>) =
+            alphas.iter().map(|&a| gamma_with_ln(self, a)).unzip();
+        let mut sum: f64 = x.iter().sum();
+        if sum <= 0.0 {
+            // Every sample underflowed, so scale them relative to the largest logarithm
+            let max = ln_x.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            if max == f64::NEG_INFINITY {
+                let total: f64 = alphas.iter().sum();
+                let mut target = self.uniform_double() * total;
+                let i = alphas
+                    .iter()
+                    .position(|&a| {
+                        target -= a;
+                        target < 0.0
+                    })
+                    .unwrap_or(alphas.len() - 1);
+                x.iter_mut().for_each(|x| *x = 0.0);
+                x[i] = 1.0;
+                return Ok(x);
+            }
+            x = ln_x.iter().map(|&l| (l - max).exp()).collect();
+            sum = x.iter().sum();
+        }
+        x.iter_mut().for_each(|x| *x /= sum);
+        Ok(x)
+    }
+
+    /// Generates how many of `n` independent trials land in each category, where trials land in
+    /// category `i` with probability `probs[i]`, like [`OrangeyCtx::multinomial`]
+    #[cfg(feature = "alloc")]
+    fn multinomial(&mut self, n: u64, probs: &[f64]) -> Result<Vec<u64>, ParamError> {
+        if probs.is_empty() {
+            return Err(ParamError::Empty);
+        }
+        if probs.iter().any(|p| !p.is_finite()) {
+            return Err(ParamError::NotFinite);
+        }
+        let total: f64 = probs.iter().sum();
+        if probs.iter().any(|&p| p < 0.0) || (total - 1.0).abs() > 1e-9 {
+            return Err(ParamError::NotNormalized);
+        }
+        let mut counts = vec![0; probs.len()];
+        let mut remaining_n = n;
+        let mut remaining_p = total;
+        for (count, &p) in counts.iter_mut().zip(probs).take(probs.len() - 1) {
+            if remaining_n == 0 || remaining_p <= 0.0 {
+                break;
+            }
+            *count = self.binomial(remaining_n, (p / remaining_p).min(1.0));
+            remaining_n -= *count;
+            remaining_p -= p;
+        }
+        counts[probs.len() - 1] += remaining_n;
+        Ok(counts)
+    }
+
+    /// Generates integers matching a poisson distribution with an expected value of `ev`, like
+    /// [`OrangeyCtx::poisson`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ev` is negative or not finite
+    fn poisson(&mut self, ev: f64) -> u64 {
+        assert!(
+            ev >= 0.0 && ev.is_finite(),
+            "expected value {} is not non-negative and finite",
+            ev
+        );
+        discrete::PoissonSampler::new(ev).sample(self)
+    }
+
+    /// Generates the number of successes in `n` independent trials that each succeed with
+    /// probability `p`, like [`OrangeyCtx::binomial`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in the range [0, 1]
+    fn binomial(&mut self, n: u64, p: f64) -> u64 {
+        assert!(
+            (0.0..=1.0).contains(&p),
+            "probability {} is not in [0, 1]",
+            p
+        );
+        discrete::binomial(self, n, p)
+    }
+
+    /// Generates the number of failures before the first success in a series of independent
+    /// trials that each succeed with probability `p`, like [`OrangeyCtx::geometric`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in the range (0, 1]
+    fn geometric(&mut self, p: f64) -> u64 {
+        assert!(p > 0.0 && p <= 1.0, "probability {} is not in (0, 1]", p);
+        let u = 1.0 - self.uniform_double();
+        if p == 1.0 {
+            return 0;
+        }
+        (u.ln() / (-p).ln_1p()).floor() as u64
+    }
+
+    /// Generates the number of failures before the `r`-th success in a series of independent
+    /// trials that each succeed with probability `p`, like [`OrangeyCtx::negative_binomial`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in the range (0, 1]
+    fn negative_binomial(&mut self, r: u64, p: f64) -> u64 {
+        (0..r).fold(0u64, |failures, _| {
+            failures.saturating_add(self.geometric(p))
+        })
+    }
+
+    /// Generates the number of successes in `draws` draws without replacement from `total`
+    /// items, `successes` of which count as a success, like [`OrangeyCtx::hypergeometric`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `total` is greater than 2^53, or if `successes` or `draws` is greater than `total`
+    fn hypergeometric(&mut self, total: u64, successes: u64, draws: u64) -> u64 {
+        assert!(total <= 1 << 53, "a total of {} is more than 2^53", total);
+        assert!(
+            successes <= total,
+            "{} successes is more than the total of {}",
+            successes,
+            total
+        );
+        assert!(
+            draws <= total,
+            "{} draws is more than the total of {}",
+            draws,
+            total
+        );
+        discrete::hypergeometric(self, total, successes, draws)
+    }
+
+    /// Generates an integer in the range [1, `n`] with a zipf distribution with exponent `s`,
+    /// like [`OrangeyCtx::zipf`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0 or `s` is negative
+    fn zipf(&mut self, n: u64, s: f64) -> u64 {
+        assert!(n > 0, "n must not be 0");
+        assert!(s >= 0.0, "exponent {} is negative", s);
+        discrete::zipf(self, n, s)
+    }
+}
+
+impl OrangeyEngine for OrangeyCtx {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rand()
+    }
+
+    fn skip(&mut self, delta: u128) {
+        OrangeyCtx::skip(self, delta)
+    }
+
+    fn peek_raw(&self, delta: u128) -> u64 {
+        self.peek(delta)
+    }
+
+    fn uniform_double(&mut self) -> f64 {
+        OrangeyCtx::uniform_double(self)
+    }
+
+    fn rand_range(&mut self, range: Range<u64>) -> u64 {
+        OrangeyCtx::rand_range(self, range)
+    }
+
+    fn shuffle<T>(&mut self, slice: &mut [T]) {
+        OrangeyCtx::shuffle(self, slice)
+    }
+
+    fn gaussian(&mut self) -> f64 {
+        OrangeyCtx::gaussian(self)
+    }
+}
+
+impl OrangeyCtx {
+    /// Everything that decides what the generator does next, with the spare deviate as bits so it
+    /// can be compared exactly. Jumps and reseeds empty the bit buffer without clearing it, so its
+    /// stale bits are left out
+    fn key(&self) -> (u128, u128, u64, u32, Option<u64>, &[OrangeyCtx]) {
+        (
+            self.state,
+            self.inc,
+            if self.buffered_bits == 0 {
+                0
+            } else {
+                self.buffer
+            },
+            self.buffered_bits,
+            self.gaussian_spare.map(f64::to_bits),
+            self.checkpoints(),
+        )
+    }
+}
+
+impl PartialEq for OrangeyCtx {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for OrangeyCtx {}
+
+impl core::hash::Hash for OrangeyCtx {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl core::fmt::Debug for OrangeyCtx {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OrangeyCtx")
+            .field("state", &format_args!("{:#034x}", self.state))
+            .field("inc", &format_args!("{:#034x}", self.inc))
+            .field("buffered_bits", &self.buffered_bits)
+            .field("gaussian_spare", &self.gaussian_spare)
+            .field("checkpoints", &self.checkpoints().len())
+            .finish()
+    }
+}
+
+impl Default for OrangeyCtx {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl OrangeyCtx {
+    /// Overwrites the generator's state with zeros, along with its buffered values and saved
+    /// checkpoints, for generators seeded from secrets. With the `zeroize` feature this also
+    /// happens when the generator is dropped. The generator must be reseeded to be of any use
+    /// afterwards
+    ///
+    /// This only wipes the generator where it is now. Copies made by `.clone()` or left behind
+    /// when it was moved are separate, and are wiped when they are dropped, if at all
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::seed_from_str("secret passphrase");
+    /// let _token = orangey_ctx.rand();
+    /// orangey_ctx.zeroize();
+    /// assert_eq!(orangey_ctx.state_bytes(), [0; 32]);
+    /// ```
+    pub fn zeroize(&mut self) {
+        wipe(&mut self.state);
+        wipe(&mut self.inc);
+        wipe(&mut self.origin);
+        wipe(&mut self.buffer);
+        wipe(&mut self.buffered_bits);
+        wipe(&mut self.gaussian_spare);
+        #[cfg(feature = "alloc")]
+        {
+            self.checkpoints.iter_mut().for_each(OrangeyCtx::zeroize);
+            self.checkpoints.clear();
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for OrangeyCtx {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+macro_rules! iter_wrapper {
+    (fn $name:ident(&mut self $(, $arg:ident: $type:ty)* $(,)?) -> $ret:ty, $struct_name:ident, $method_name:ident) => {
+        iter_wrapper!(@define fn $name(&mut self $(, $arg: $type)*) -> $ret, $struct_name, $method_name, {});
+    };
+    // For methods that always consume the same number of outputs, skipping items becomes a jump
+    (fn $name:ident(&mut self $(, $arg:ident: $type:ty)* $(,)?) -> $ret:ty, $struct_name:ident, $method_name:ident, outputs = $outputs:expr) => {
+        iter_wrapper!(@define fn $name(&mut self $(, $arg: $type)*) -> $ret, $struct_name, $method_name, {
+            /// Jumps over `n` items in `O(log n)` time instead of generating them. Unlike `.skip()`,
+            /// this keeps the bits and spare deviate other methods left buffered, as generating the
+            /// items would have
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                let delta = n as u128 * $outputs;
+                self.ctx.state = OrangeyCtx::advance(self.ctx.state, delta, OrangeyCtx::MUL, self.ctx.inc);
+                self.next()
+            }
+        });
+    };
+    (@define fn $name:ident(&mut self $(, $arg:ident: $type:ty)*) -> $ret:ty, $struct_name:ident, $method_name:ident, { $($extra:tt)* }) => {
         pub struct $struct_name<'a> {
             ctx: &'a mut OrangeyCtx,
             $($arg: $type,)*
         }
 
-        impl<'a> Iterator for $struct_name<'a> {
-            type Item = $ret;
+        impl<'a> Iterator for $struct_name<'a> {
+            type Item = $ret;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                Some(self.ctx.$name($(self.$arg.clone(),)*))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (usize::MAX, None)
+            }
+
+            $($extra)*
+        }
+
+        impl core::iter::FusedIterator for $struct_name<'_> {}
+
+        impl core::fmt::Debug for $struct_name<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct(stringify!($struct_name))
+                    $(.field(stringify!($arg), &self.$arg))*
+                    .finish_non_exhaustive()
+            }
+        }
+
+        impl OrangeyCtx {
+            #[doc = concat!("Returns an iterator over the values of [`OrangeyCtx::", stringify!($name), "`]")]
+            pub fn $method_name(&mut self $(, $arg: $type)*) -> $struct_name<'_> {
+                $struct_name {
+                    ctx: self,
+                    $($arg,)*
+                }
+            }
+        }
+    };
+}
+
+iter_wrapper!(fn rand(&mut self) -> u64, RandIter, rand_iter, outputs = 1);
+iter_wrapper!(fn rand_range(&mut self, range: Range<u64>) -> u64, RandRangeIter, rand_range_iter);
+iter_wrapper!(fn uniform_double(&mut self) -> f64, UniformDoubleIter, uniform_double_iter, outputs = 1);
+iter_wrapper!(fn uniform_double_53(&mut self) -> f64, UniformDouble53Iter, uniform_double_53_iter, outputs = 1);
+iter_wrapper!(fn open01(&mut self) -> f64, Open01Iter, open01_iter, outputs = 1);
+iter_wrapper!(fn open_closed01(&mut self) -> f64, OpenClosed01Iter, open_closed01_iter, outputs = 1);
+iter_wrapper!(fn rand_range_f64(&mut self, range: Range<f64>) -> f64, RandRangeF64Iter, rand_range_f64_iter);
+iter_wrapper!(fn rand_range_f32(&mut self, range: Range<f32>) -> f32, RandRangeF32Iter, rand_range_f32_iter);
+iter_wrapper!(fn all_doubles(&mut self) -> f64, AllDoublesIter, all_doubles_iter);
+iter_wrapper!(fn uniform_float(&mut self) -> f32, UniformFloatIter, uniform_float_iter, outputs = 1);
+iter_wrapper!(fn all_floats(&mut self) -> f32, AllFloatsIter, all_floats_iter);
+iter_wrapper!(fn gaussian(&mut self) -> f64, GaussianIter, gaussian_iter);
+iter_wrapper!(fn gaussian_with(&mut self, mean: f64, std_dev: f64) -> f64, GaussianWithIter, gaussian_with_iter);
+iter_wrapper!(fn gaussian_f32(&mut self) -> f32, GaussianF32Iter, gaussian_f32_iter);
+iter_wrapper!(fn gaussian_fast(&mut self) -> f64, GaussianFastIter, gaussian_fast_iter);
+iter_wrapper!(fn exponential_fast(&mut self) -> f64, ExponentialFastIter, exponential_fast_iter);
+iter_wrapper!(fn gamma(&mut self, shape: f64, scale: f64) -> f64, GammaIter, gamma_iter);
+iter_wrapper!(fn beta(&mut self, a: f64, b: f64) -> f64, BetaIter, beta_iter);
+iter_wrapper!(fn chi_squared(&mut self, k: f64) -> f64, ChiSquaredIter, chi_squared_iter);
+iter_wrapper!(fn log_normal(&mut self, mu: f64, sigma: f64) -> f64, LogNormalIter, log_normal_iter);
+iter_wrapper!(fn angle(&mut self) -> f64, AngleIter, angle_iter, outputs = 1);
+iter_wrapper!(fn von_mises(&mut self, mu: f64, kappa: f64) -> f64, VonMisesIter, von_mises_iter);
+iter_wrapper!(fn unit_vector_2d(&mut self) -> [f64; 2], UnitVector2dIter, unit_vector_2d_iter, outputs = 1);
+iter_wrapper!(fn unit_vector_3d(&mut self) -> [f64; 3], UnitVector3dIter, unit_vector_3d_iter, outputs = 2);
+iter_wrapper!(fn in_unit_disk(&mut self) -> [f64; 2], InUnitDiskIter, in_unit_disk_iter);
+iter_wrapper!(fn in_unit_sphere(&mut self) -> [f64; 3], InUnitSphereIter, in_unit_sphere_iter);
+iter_wrapper!(fn on_unit_sphere(&mut self) -> [f64; 3], OnUnitSphereIter, on_unit_sphere_iter);
+iter_wrapper!(fn student_t(&mut self, df: f64) -> f64, StudentTIter, student_t_iter);
+iter_wrapper!(fn fisher_f(&mut self, d1: f64, d2: f64) -> f64, FisherFIter, fisher_f_iter);
+iter_wrapper!(fn weibull(&mut self, shape: f64, scale: f64) -> f64, WeibullIter, weibull_iter, outputs = 1);
+iter_wrapper!(fn pareto(&mut self, scale: f64, alpha: f64) -> f64, ParetoIter, pareto_iter, outputs = 1);
+iter_wrapper!(fn cauchy(&mut self, median: f64, scale: f64) -> f64, CauchyIter, cauchy_iter, outputs = 1);
+iter_wrapper!(fn triangular(&mut self, min: f64, mode: f64, max: f64) -> Result<f64, ParamError>, TriangularIter, triangular_iter);
+iter_wrapper!(fn pert(&mut self, min: f64, mode: f64, max: f64) -> Result<f64, ParamError>, PertIter, pert_iter);
+iter_wrapper!(fn poisson(&mut self, ev: f64) -> u64, PoissonIter, poisson_iter);
+iter_wrapper!(fn exponential(&mut self, lambda: f64) -> f64, ExponentialIter, exponential_iter, outputs = 1);
+iter_wrapper!(fn binomial(&mut self, n: u64, p: f64) -> u64, BinomialIter, binomial_iter);
+iter_wrapper!(fn geometric(&mut self, p: f64) -> u64, GeometricIter, geometric_iter);
+iter_wrapper!(fn negative_binomial(&mut self, r: u64, p: f64) -> u64, NegativeBinomialIter, negative_binomial_iter);
+iter_wrapper!(fn hypergeometric(&mut self, total: u64, successes: u64, draws: u64) -> u64, HypergeometricIter, hypergeometric_iter);
+iter_wrapper!(fn zipf(&mut self, n: u64, s: f64) -> u64, ZipfIter, zipf_iter);
+
+/// An iterator over the positions of a simple random walk, returned by [`OrangeyCtx::random_walk`]
+pub struct RandomWalk<'a> {
+    ctx: &'a mut OrangeyCtx,
+    step: f64,
+    position: f64,
+}
+
+impl Iterator for RandomWalk<'_> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.ctx.rand_bits(1) == 1 {
+            self.position += self.step;
+        } else {
+            self.position -= self.step;
+        }
+        Some(self.position)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl core::iter::FusedIterator for RandomWalk<'_> {}
+
+impl core::fmt::Debug for RandomWalk<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RandomWalk")
+            .field("step", &self.step)
+            .field("position", &self.position)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An iterator over the positions of Brownian motion sampled at fixed times, returned by
+/// [`OrangeyCtx::brownian`]
+pub struct Brownian<'a> {
+    ctx: &'a mut OrangeyCtx,
+    scale: f64,
+    position: f64,
+}
+
+impl Iterator for Brownian<'_> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.position += self.scale * self.ctx.gaussian();
+        Some(self.position)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl core::iter::FusedIterator for Brownian<'_> {}
+
+impl core::fmt::Debug for Brownian<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Brownian")
+            .field("scale", &self.scale)
+            .field("position", &self.position)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A generator that can be shared between threads, for when they should all draw from one stream.
+/// Each call takes a lock for the duration of the call, because 128-bit atomics aren't available
+/// on stable Rust. Giving each thread its own generator with [`OrangeyCtx::split`] or
+/// [`OrangeyCtx::jumped`] is faster when the order of the draws doesn't matter
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{AtomicOrangeyCtx, OrangeyCtx};
+/// use std::sync::Arc;
+///
+/// let shared = Arc::new(AtomicOrangeyCtx::new(OrangeyCtx::new()));
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let shared = Arc::clone(&shared);
+///         std::thread::spawn(move || (0..100).map(|_| shared.rand()).collect::<Vec<u64>>())
+///     })
+///     .collect();
+/// let mut drawn: Vec<u64> = handles.into_iter().flat_map(|h| h.join().unwrap()).collect();
+/// let mut expected = OrangeyCtx::new().rand_batch(400);
+/// drawn.sort();
+/// expected.sort();
+/// assert_eq!(drawn, expected);
+/// ```
+#[cfg(feature = "std")]
+pub struct AtomicOrangeyCtx {
+    ctx: std::sync::Mutex<OrangeyCtx>,
+}
+
+#[cfg(feature = "std")]
+impl AtomicOrangeyCtx {
+    /// Wraps `ctx` so it can be shared
+    pub fn new(ctx: OrangeyCtx) -> Self {
+        AtomicOrangeyCtx {
+            ctx: std::sync::Mutex::new(ctx),
+        }
+    }
+
+    /// Runs `f` on the generator while holding the lock, so its draws come from one unbroken
+    /// stretch of the stream
+    pub fn with<T>(&self, f: impl FnOnce(&mut OrangeyCtx) -> T) -> T {
+        // A panic mid-call can't leave the state half-updated, so a poisoned lock is still usable
+        let mut ctx = self.ctx.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut ctx)
+    }
+
+    /// Gives back the generator
+    pub fn into_inner(self) -> OrangeyCtx {
+        self.ctx.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Shared counterpart of [`OrangeyCtx::rand`]
+    pub fn rand(&self) -> u64 {
+        self.with(|ctx| ctx.rand())
+    }
+
+    /// Shared counterpart of [`OrangeyCtx::rand_u128`]
+    pub fn rand_u128(&self) -> u128 {
+        self.with(|ctx| ctx.rand_u128())
+    }
+
+    /// Shared counterpart of [`OrangeyCtx::rand_range`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty
+    pub fn rand_range(&self, range: Range<u64>) -> u64 {
+        self.with(|ctx| ctx.rand_range(range))
+    }
+
+    /// Shared counterpart of [`OrangeyCtx::try_rand_range`]
+    pub fn try_rand_range<R: RangeBounds<u64>>(&self, range: R) -> Result<u64, RangeError> {
+        self.with(|ctx| ctx.try_rand_range(range))
+    }
+
+    /// Shared counterpart of [`OrangeyCtx::gen_range`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty
+    pub fn gen_range<T: SampleUniform, R: RangeBounds<T>>(&self, range: R) -> T {
+        self.with(|ctx| ctx.gen_range(range))
+    }
+
+    /// Shared counterpart of [`OrangeyCtx::uniform_double`]
+    pub fn uniform_double(&self) -> f64 {
+        self.with(|ctx| ctx.uniform_double())
+    }
+
+    /// Shared counterpart of [`OrangeyCtx::skip`]
+    pub fn skip(&self, delta: u128) {
+        self.with(|ctx| ctx.skip(delta))
+    }
+
+    /// Shared counterpart of [`OrangeyCtx::peek`]
+    pub fn peek(&self, delta: u128) -> u64 {
+        self.with(|ctx| ctx.peek(delta))
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Debug for AtomicOrangeyCtx {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AtomicOrangeyCtx").finish_non_exhaustive()
+    }
+}
+
+/// A source of seeds for [`ReseedingOrangey`]
+pub trait EntropySource {
+    /// Returns a fresh seed, laid out as the little-endian `initstate` in the first 16 bytes and
+    /// the little-endian `initseq` in the last 16
+    fn entropy(&mut self) -> [u8; 32];
+}
+
+impl<F: FnMut() -> [u8; 32]> EntropySource for F {
+    fn entropy(&mut self) -> [u8; 32] {
+        self()
+    }
+}
+
+/// Seeds from the operating system's random number generator
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{OsEntropy, ReseedingOrangey};
+///
+/// let mut rng = ReseedingOrangey::with_fork_detection(OsEntropy, 1 << 20);
+/// let session_key = rng.rand_u128();
+/// ```
+#[cfg(feature = "getrandom")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsEntropy;
+
+#[cfg(feature = "getrandom")]
+impl OsEntropy {
+    fn try_seed() -> Result<[u8; 32], getrandom::Error> {
+        let mut seed = [0; 32];
+        getrandom::fill(&mut seed)?;
+        Ok(seed)
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl EntropySource for OsEntropy {
+    /// # Panics
+    ///
+    /// Panics if the operating system can't provide randomness
+    fn entropy(&mut self) -> [u8; 32] {
+        Self::try_seed().expect("the operating system failed to provide a seed")
+    }
+}
+
+/// A generator that reseeds itself from `S` after every `threshold` outputs, and optionally
+/// whenever it finds itself in a different process than the one that last seeded it, so a forked
+/// child doesn't repeat its parent's stream
+///
+/// # Examples
+///
+/// ```
+/// use orangey::ReseedingOrangey;
+///
+/// let mut counter = 0u8;
+/// let source = move || {
+///     counter += 1;
+///     [counter; 32]
+/// };
+/// let mut rng = ReseedingOrangey::new(source, 1000);
+/// let values: Vec<u64> = (0..2500).map(|_| rng.rand()).collect();
+/// assert_eq!(rng.reseeds(), 2);
+/// ```
+pub struct ReseedingOrangey<S: EntropySource> {
+    ctx: OrangeyCtx,
+    source: S,
+    threshold: u64,
+    remaining: u64,
+    reseeds: u64,
+    #[cfg(feature = "std")]
+    pid: Option<u32>,
+}
+
+impl<S: EntropySource> ReseedingOrangey<S> {
+    /// Seeds a generator from `source`, to be reseeded after every `threshold` outputs
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is 0
+    pub fn new(mut source: S, threshold: u64) -> Self {
+        assert!(threshold > 0, "reseeding threshold must not be 0");
+        let mut ctx = OrangeyCtx::new();
+        ctx.srand_bytes(source.entropy());
+        ReseedingOrangey {
+            ctx,
+            source,
+            threshold,
+            remaining: threshold,
+            reseeds: 0,
+            #[cfg(feature = "std")]
+            pid: None,
+        }
+    }
+
+    /// Like `ReseedingOrangey::new`, but also reseeds after a fork. This checks the process ID on
+    /// every call, which costs a system call each time
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is 0
+    #[cfg(feature = "std")]
+    pub fn with_fork_detection(source: S, threshold: u64) -> Self {
+        let mut reseeding = Self::new(source, threshold);
+        reseeding.pid = Some(std::process::id());
+        reseeding
+    }
 
-            fn next(&mut self) -> Option<Self::Item> {
-                Some(self.ctx.$name($(self.$arg.clone(),)*))
-            }
+    /// Reseeds from the entropy source right away, and restarts the count towards the threshold
+    pub fn reseed(&mut self) {
+        self.ctx.srand_bytes(self.source.entropy());
+        self.remaining = self.threshold;
+        self.reseeds += 1;
+        #[cfg(feature = "std")]
+        if self.pid.is_some() {
+            self.pid = Some(std::process::id());
         }
+    }
 
-        impl OrangeyCtx {
-            #[doc = concat!("Returns an iterator over the values of [`OrangeyCtx::", stringify!($name), "`]")]
-            pub fn $method_name(&mut self $(, $arg: $type)*) -> $struct_name {
-                $struct_name {
-                    ctx: self,
-                    $($arg,)*
-                }
-            }
+    /// The number of times the generator has been reseeded since it was made
+    pub fn reseeds(&self) -> u64 {
+        self.reseeds
+    }
+
+    /// Returns the generator for any other method, after reseeding if it's due. Whatever is done with
+    /// it counts as a single output towards the threshold
+    pub fn ctx(&mut self) -> &mut OrangeyCtx {
+        self.consume(1);
+        &mut self.ctx
+    }
+
+    /// Reseeding counterpart of [`OrangeyCtx::rand`]
+    pub fn rand(&mut self) -> u64 {
+        self.consume(1);
+        self.ctx.rand()
+    }
+
+    /// Reseeding counterpart of [`OrangeyCtx::rand_u128`]
+    pub fn rand_u128(&mut self) -> u128 {
+        self.consume(2);
+        self.ctx.rand_u128()
+    }
+
+    /// Reseeding counterpart of [`OrangeyCtx::uniform_double`]
+    pub fn uniform_double(&mut self) -> f64 {
+        self.consume(1);
+        self.ctx.uniform_double()
+    }
+
+    /// Reseeding counterpart of [`OrangeyCtx::fill_bytes`]. A `buf` longer than the threshold
+    /// allows is filled in pieces, reseeding between them
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::ReseedingOrangey;
+    ///
+    /// let mut counter = 0u8;
+    /// let source = move || {
+    ///     counter += 1;
+    ///     [counter; 32]
+    /// };
+    /// let mut rng = ReseedingOrangey::new(source, 4);
+    /// let mut buf = [0; 100];
+    /// rng.fill_bytes(&mut buf);
+    /// assert_eq!(rng.reseeds(), 3);
+    /// ```
+    pub fn fill_bytes(&mut self, buf: &mut [u8]) {
+        let chunk = usize::try_from(self.threshold.saturating_mul(8)).unwrap_or(usize::MAX);
+        for piece in buf.chunks_mut(chunk) {
+            self.consume(piece.len().div_ceil(8) as u64);
+            self.ctx.fill_bytes(piece);
         }
-    };
+    }
+
+    /// Reseeds first if `outputs` more would pass the threshold or the process has changed
+    fn consume(&mut self, outputs: u64) {
+        #[cfg(feature = "std")]
+        let forked = self.pid.is_some_and(|pid| pid != std::process::id());
+        #[cfg(not(feature = "std"))]
+        let forked = false;
+        if forked || outputs > self.remaining {
+            self.reseed();
+        }
+        self.remaining = self.remaining.saturating_sub(outputs);
+    }
 }
 
-iter_wrapper!(fn rand_range(&mut self, range: Range<u64>) -> u64, RandRangeIter, rand_range_iter);
-iter_wrapper!(fn uniform_double(&mut self) -> f64, UniformDoubleIter, uniform_double_iter);
-iter_wrapper!(fn all_doubles(&mut self) -> f64, AllDoublesIter, all_doubles_iter);
-iter_wrapper!(fn gaussian(&mut self) -> f64, GaussianIter, gaussian_iter);
-iter_wrapper!(fn poisson(&mut self, ev: f64) -> u64, PoissonIter, poisson_iter);
+impl<S: EntropySource> core::fmt::Debug for ReseedingOrangey<S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ReseedingOrangey")
+            .field("threshold", &self.threshold)
+            .field("remaining", &self.remaining)
+            .field("reseeds", &self.reseeds)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An iterator that owns its generator, so unlike the borrowing iterators it can be stored or
+/// returned freely. Each item is made by calling `f` on the generator. Made by
+/// [`OrangeyCtx::into_iter_with`] and its shortcuts
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{OrangeyCtx, OwnedIter};
+///
+/// struct Spawner {
+///     positions: OwnedIter<fn(&mut OrangeyCtx) -> f64>,
+/// }
+///
+/// let mut spawner = Spawner { positions: OrangeyCtx::new().into_uniform_double_iter() };
+/// let x = spawner.positions.next().unwrap();
+/// let ctx = spawner.positions.into_inner();
+/// ```
+pub struct OwnedIter<F> {
+    ctx: OrangeyCtx,
+    f: F,
+}
+
+impl<F> OwnedIter<F> {
+    /// Gives back the generator, in whatever state the iterator left it
+    pub fn into_inner(self) -> OrangeyCtx {
+        self.ctx
+    }
+
+    /// Borrows the generator, so it can be peeked at or used directly between items
+    pub fn ctx(&mut self) -> &mut OrangeyCtx {
+        &mut self.ctx
+    }
+}
+
+impl<T, F: FnMut(&mut OrangeyCtx) -> T> Iterator for OwnedIter<F> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some((self.f)(&mut self.ctx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<T, F: FnMut(&mut OrangeyCtx) -> T> core::iter::FusedIterator for OwnedIter<F> {}
+
+impl<F> core::fmt::Debug for OwnedIter<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OwnedIter").finish_non_exhaustive()
+    }
+}
+
+impl<F: Clone> Clone for OwnedIter<F> {
+    /// Copies the iterator along with its generator, so the copy yields the same items
+    fn clone(&self) -> Self {
+        OwnedIter {
+            ctx: self.ctx.snapshot(),
+            f: self.f.clone(),
+        }
+    }
+}
+
+impl IntoIterator for OrangeyCtx {
+    type Item = u64;
+    type IntoIter = OwnedIter<fn(&mut OrangeyCtx) -> u64>;
+
+    /// Turns the generator into an iterator over the results of `.rand()`
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_rand_iter()
+    }
+}
+
+impl OrangeyCtx {
+    /// Turns the generator into an iterator that calls `f` on it to make each item
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// fn dice() -> impl Iterator<Item = u8> {
+    ///     OrangeyCtx::new().into_iter_with(|ctx| ctx.gen_range(1..=6))
+    /// }
+    ///
+    /// assert!(dice().take(10).all(|roll| (1..=6).contains(&roll)));
+    /// ```
+    pub fn into_iter_with<T, F: FnMut(&mut OrangeyCtx) -> T>(self, f: F) -> OwnedIter<F> {
+        OwnedIter { ctx: self, f }
+    }
+
+    /// Turns the generator into an iterator over the results of [`OrangeyCtx::rand`]
+    pub fn into_rand_iter(self) -> OwnedIter<fn(&mut OrangeyCtx) -> u64> {
+        self.into_iter_with(OrangeyCtx::rand)
+    }
+
+    /// Turns the generator into an iterator over the results of [`OrangeyCtx::uniform_double`]
+    pub fn into_uniform_double_iter(self) -> OwnedIter<fn(&mut OrangeyCtx) -> f64> {
+        self.into_iter_with(OrangeyCtx::uniform_double)
+    }
+
+    /// Turns the generator into an iterator over the results of [`OrangeyCtx::gaussian`]
+    pub fn into_gaussian_iter(self) -> OwnedIter<fn(&mut OrangeyCtx) -> f64> {
+        self.into_iter_with(OrangeyCtx::gaussian)
+    }
+}
+
+/// Random bits read one at a time from the same buffer as [`OrangeyCtx::rand_u32`], so that each
+/// output of the generator provides 64 coin flips. Made by [`OrangeyCtx::bit_source`], and
+/// iterating over it yields bits as bools
+pub struct BitSource<'a> {
+    ctx: &'a mut OrangeyCtx,
+}
+
+impl BitSource<'_> {
+    /// Returns a random bit
+    pub fn next_bit(&mut self) -> bool {
+        self.ctx.rand_bits(1) == 1
+    }
+
+    /// Returns `n` random bits in the low bits of the result. Unlike the fixed-width methods of
+    /// [`OrangeyCtx`], leftover bits are used up before starting on a new output, so none are wasted
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than 64
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let mut bits = orangey_ctx.bit_source();
+    /// // Ten 3-bit rolls and a 34-bit one use exactly one output
+    /// let rolls: Vec<u64> = (0..10).map(|_| bits.next_bits(3)).collect();
+    /// let rest = bits.next_bits(34);
+    /// assert_eq!(rest, OrangeyCtx::new().rand() >> 30);
+    /// ```
+    pub fn next_bits(&mut self, n: u32) -> u64 {
+        assert!(n <= 64, "cannot take {} bits at once", n);
+        if n == 0 {
+            return 0;
+        }
+        let carried = self.ctx.buffered_bits.min(n);
+        let low = if carried == 0 {
+            0
+        } else {
+            self.ctx.rand_bits(carried)
+        };
+        if carried == n {
+            return low;
+        }
+        low | self.ctx.rand_bits(n - carried) << carried
+    }
+
+    /// Returns `true` with probability `p`, like [`OrangeyCtx::rand_bool`], using an average of two bits
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in the range [0, 1]
+    pub fn bernoulli(&mut self, p: f64) -> bool {
+        self.ctx.rand_bool(p)
+    }
+}
+
+impl Iterator for BitSource<'_> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_bit())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl core::iter::FusedIterator for BitSource<'_> {}
+
+impl core::fmt::Debug for BitSource<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BitSource")
+            .field("buffered_bits", &self.ctx.buffered_bits)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OrangeyCtx {
+    /// Returns a [`BitSource`] for reading random bits one or a few at a time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let heads = orangey_ctx.bit_source().take(640).filter(|&b| b).count();
+    /// assert!((220..420).contains(&heads));
+    /// ```
+    pub fn bit_source(&mut self) -> BitSource<'_> {
+        BitSource { ctx: self }
+    }
+
+    /// Returns an iterator over the positions of a walk that starts at 0 and moves up or down by
+    /// `step` with equal probability each time. Steps use single bits, so 64 of them consume one
+    /// output of the generator
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let path: Vec<f64> = orangey_ctx.random_walk(1.0).take(100).collect();
+    /// assert!(path.windows(2).all(|w| (w[1] - w[0]).abs() == 1.0));
+    /// ```
+    pub fn random_walk(&mut self, step: f64) -> RandomWalk<'_> {
+        RandomWalk {
+            ctx: self,
+            step,
+            position: 0.0,
+        }
+    }
+
+    /// Returns an iterator over the positions of Brownian motion that starts at 0 and has volatility
+    /// `sigma`, sampled every `dt` units of time. Each step is gaussian with a standard deviation of
+    /// `sigma * dt.sqrt()`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dt` or `sigma` is negative or not finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// // A year of daily log-returns with 20% annual volatility
+    /// let log_price: Vec<f64> = orangey_ctx.brownian(1.0 / 365.0, 0.2).take(365).collect();
+    /// ```
+    pub fn brownian(&mut self, dt: f64, sigma: f64) -> Brownian<'_> {
+        assert!(
+            dt.is_finite() && dt >= 0.0,
+            "time step {} must be finite and not negative",
+            dt
+        );
+        assert!(
+            sigma.is_finite() && sigma >= 0.0,
+            "volatility {} must be finite and not negative",
+            sigma
+        );
+        Brownian {
+            ctx: self,
+            scale: sigma * dt.sqrt(),
+            position: 0.0,
+        }
+    }
+}
 
 macro_rules! peek_iter_wrapper {
     (fn $name:ident(&self $(, $arg:ident: $type:ty)* $(,)?) -> $ret:ty, $struct_name:ident, $method_name:ident) => {
+        #[derive(Clone)]
         pub struct $struct_name<'a> {
             ctx: &'a OrangeyCtx,
             delta: u128,
@@ -298,11 +4317,32 @@ macro_rules! peek_iter_wrapper {
                 self.delta += 1;
                 Some(self.ctx.$name(previous_delta $(, self.$arg.clone())*))
             }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (usize::MAX, None)
+            }
+
+            /// Jumps over `n` items without peeking at them
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                self.delta += n as u128;
+                self.next()
+            }
+        }
+
+        impl core::iter::FusedIterator for $struct_name<'_> {}
+
+        impl core::fmt::Debug for $struct_name<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct(stringify!($struct_name))
+                    .field("delta", &self.delta)
+                    $(.field(stringify!($arg), &self.$arg))*
+                    .finish_non_exhaustive()
+            }
         }
 
         impl OrangeyCtx {
             #[doc = concat!("Returns an iterator over the values of [`OrangeyCtx::", stringify!($name), "`] with increasing `delta`s")]
-            pub fn $method_name(&self $(, $arg: $type)*) -> $struct_name {
+            pub fn $method_name(&self $(, $arg: $type)*) -> $struct_name<'_> {
                 $struct_name {
                     ctx: self,
                     delta: 0,
@@ -313,8 +4353,213 @@ macro_rules! peek_iter_wrapper {
     };
 }
 
+peek_iter_wrapper!(fn peek(&self) -> u64, PeekRandIter, peek_rand_iter);
 peek_iter_wrapper!(fn peek_range(&self, range: Range<u64>) -> u64, PeekRangeIter, peek_range_iter);
 peek_iter_wrapper!(fn peek_uniform_double(&self) -> f64, PeekUniformDoubleIter, peek_uniform_double_iter);
+peek_iter_wrapper!(fn peek_range_f64(&self, range: Range<f64>) -> f64, PeekRangeF64Iter, peek_range_f64_iter);
+peek_iter_wrapper!(fn peek_range_f32(&self, range: Range<f32>) -> f32, PeekRangeF32Iter, peek_range_f32_iter);
 peek_iter_wrapper!(fn peek_all_doubles(&self) -> f64, PeekAllDoublesIter, peek_all_doubles_iter);
+peek_iter_wrapper!(fn peek_uniform_float(&self) -> f32, PeekUniformFloatIter, peek_uniform_float_iter);
+peek_iter_wrapper!(fn peek_all_floats(&self) -> f32, PeekAllFloatsIter, peek_all_floats_iter);
 peek_iter_wrapper!(fn peek_gaussian(&self) -> f64, PeekGaussianIter, peek_gaussian_iter);
+peek_iter_wrapper!(fn peek_gaussian_with(&self, mean: f64, std_dev: f64) -> f64, PeekGaussianWithIter, peek_gaussian_with_iter);
+peek_iter_wrapper!(fn peek_gaussian_f32(&self) -> f32, PeekGaussianF32Iter, peek_gaussian_f32_iter);
+peek_iter_wrapper!(fn peek_gaussian_fast(&self) -> f64, PeekGaussianFastIter, peek_gaussian_fast_iter);
+peek_iter_wrapper!(fn peek_exponential_fast(&self) -> f64, PeekExponentialFastIter, peek_exponential_fast_iter);
+peek_iter_wrapper!(fn peek_gamma(&self, shape: f64, scale: f64) -> f64, PeekGammaIter, peek_gamma_iter);
+peek_iter_wrapper!(fn peek_beta(&self, a: f64, b: f64) -> f64, PeekBetaIter, peek_beta_iter);
+peek_iter_wrapper!(fn peek_chi_squared(&self, k: f64) -> f64, PeekChiSquaredIter, peek_chi_squared_iter);
+peek_iter_wrapper!(fn peek_log_normal(&self, mu: f64, sigma: f64) -> f64, PeekLogNormalIter, peek_log_normal_iter);
+peek_iter_wrapper!(fn peek_angle(&self) -> f64, PeekAngleIter, peek_angle_iter);
+peek_iter_wrapper!(fn peek_von_mises(&self, mu: f64, kappa: f64) -> f64, PeekVonMisesIter, peek_von_mises_iter);
+peek_iter_wrapper!(fn peek_student_t(&self, df: f64) -> f64, PeekStudentTIter, peek_student_t_iter);
+peek_iter_wrapper!(fn peek_fisher_f(&self, d1: f64, d2: f64) -> f64, PeekFisherFIter, peek_fisher_f_iter);
+peek_iter_wrapper!(fn peek_weibull(&self, shape: f64, scale: f64) -> f64, PeekWeibullIter, peek_weibull_iter);
+peek_iter_wrapper!(fn peek_pareto(&self, scale: f64, alpha: f64) -> f64, PeekParetoIter, peek_pareto_iter);
+peek_iter_wrapper!(fn peek_cauchy(&self, median: f64, scale: f64) -> f64, PeekCauchyIter, peek_cauchy_iter);
+peek_iter_wrapper!(fn peek_triangular(&self, min: f64, mode: f64, max: f64) -> Result<f64, ParamError>, PeekTriangularIter, peek_triangular_iter);
+peek_iter_wrapper!(fn peek_pert(&self, min: f64, mode: f64, max: f64) -> Result<f64, ParamError>, PeekPertIter, peek_pert_iter);
 peek_iter_wrapper!(fn peek_poisson(&self, ev: f64) -> u64, PeekPoissonIter, peek_poisson_iter);
+peek_iter_wrapper!(fn peek_exponential(&self, lambda: f64) -> f64, PeekExponentialIter, peek_exponential_iter);
+peek_iter_wrapper!(fn peek_binomial(&self, n: u64, p: f64) -> u64, PeekBinomialIter, peek_binomial_iter);
+peek_iter_wrapper!(fn peek_geometric(&self, p: f64) -> u64, PeekGeometricIter, peek_geometric_iter);
+peek_iter_wrapper!(fn peek_negative_binomial(&self, r: u64, p: f64) -> u64, PeekNegativeBinomialIter, peek_negative_binomial_iter);
+peek_iter_wrapper!(fn peek_hypergeometric(&self, total: u64, successes: u64, draws: u64) -> u64, PeekHypergeometricIter, peek_hypergeometric_iter);
+peek_iter_wrapper!(fn peek_zipf(&self, n: u64, s: f64) -> u64, PeekZipfIter, peek_zipf_iter);
+
+/// An iterator over past results of `.rand()`, newest first, returned by [`OrangeyCtx::peek_back_iter`]
+#[derive(Clone)]
+pub struct PeekBackIter<'a> {
+    ctx: &'a OrangeyCtx,
+    delta: u128,
+}
+
+impl Iterator for PeekBackIter<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let previous_delta = self.delta;
+        self.delta = self.delta.wrapping_add(1);
+        Some(self.ctx.peek_back(previous_delta))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+
+    /// Jumps over `n` items without peeking at them
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.delta = self.delta.wrapping_add(n as u128);
+        self.next()
+    }
+}
+
+impl core::iter::FusedIterator for PeekBackIter<'_> {}
+
+/// Every `stride`-th result of `.rand()`, starting from the `thread_index`-th, returned by
+/// [`OrangeyCtx::leapfrog`]. Each step jumps the whole stride at once, so it costs the same as
+/// a plain `.rand()`
+#[derive(Clone, PartialEq, Eq)]
+pub struct Leapfrog {
+    state: u128,
+    mult: u128,
+    plus: u128,
+}
+
+impl Iterator for Leapfrog {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.state = self.state.wrapping_mul(self.mult).wrapping_add(self.plus);
+        Some(OrangeyCtx::output(self.state))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+
+    /// Jumps over `n` items in `O(log n)` time instead of generating them
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.state = OrangeyCtx::advance(self.state, n as u128, self.mult, self.plus);
+        self.next()
+    }
+}
+
+impl core::iter::FusedIterator for Leapfrog {}
+
+impl core::fmt::Debug for Leapfrog {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Leapfrog").finish_non_exhaustive()
+    }
+}
+
+impl core::fmt::Debug for PeekBackIter<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PeekBackIter")
+            .field("delta", &self.delta)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OrangeyCtx {
+    /// Returns an iterator over the values of [`OrangeyCtx::peek_back`] with increasing `delta`s,
+    /// walking back through the results of `.rand()` from the most recent one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let history: Vec<u64> = orangey_ctx.rand_iter().take(5).collect();
+    /// let recalled: Vec<u64> = orangey_ctx.peek_back_iter().take(5).collect();
+    /// assert!(history.iter().rev().eq(&recalled));
+    /// ```
+    pub fn peek_back_iter(&self) -> PeekBackIter<'_> {
+        PeekBackIter {
+            ctx: self,
+            delta: 0,
+        }
+    }
+
+    /// Returns the share of this stream that worker `thread_index` of `stride` workers gets when the
+    /// results of `.rand()` are dealt out in turn. Between them the workers see exactly the values a
+    /// single loop over `.rand()` would, so splitting up a run doesn't change its result
+    ///
+    /// # Panics
+    ///
+    /// Panics if `thread_index` is not less than `stride`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let orangey_ctx = OrangeyCtx::new();
+    /// let serial: u64 = OrangeyCtx::new().rand_iter().take(1000).map(|r| r >> 32).sum();
+    /// let parallel: u64 = (0..4)
+    ///     .map(|i| orangey_ctx.leapfrog(i, 4).take(250).map(|r| r >> 32).sum::<u64>())
+    ///     .sum();
+    /// assert_eq!(parallel, serial);
+    /// ```
+    pub fn leapfrog(&self, thread_index: u128, stride: u128) -> Leapfrog {
+        assert!(
+            thread_index < stride,
+            "thread index {} is out of range for a stride of {}",
+            thread_index,
+            stride
+        );
+        // Jumping the whole stride is another affine map, read off from where it sends 0 and 1
+        let plus = Self::advance(0, stride, Self::MUL, self.inc);
+        let mult = Self::advance(1, stride, Self::MUL, self.inc).wrapping_sub(plus);
+        // Start one stride before this worker's first value, so `next` lands on it
+        let back = (thread_index + 1).wrapping_sub(stride);
+        let state = Self::advance(self.state, back, Self::MUL, self.inc);
+        Leapfrog { state, mult, plus }
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::RngCore for OrangeyCtx {
+    /// The low half of one output, as [`OrangeyEngine::next_u32`] takes, rather than the buffered
+    /// halves of `.rand_u32()`
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.rand() as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rand()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        OrangeyCtx::fill_bytes(self, dst)
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::SeedableRng for OrangeyCtx {
+    /// The first 16 bytes are the little-endian `initstate`, the last 16 the little-endian `initseq`
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        OrangeyCtx::from_seed(seed)
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl<S: EntropySource> rand_core::RngCore for ReseedingOrangey<S> {
+    /// The low half of one output, as [`OrangeyEngine::next_u32`] takes
+    fn next_u32(&mut self) -> u32 {
+        self.rand() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rand()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        ReseedingOrangey::fill_bytes(self, dst)
+    }
+}
@@ -1,16 +1,1039 @@
-use orangey::OrangeyCtx;
-
-fn main(){
-    let mut rng = OrangeyCtx::new();
-    for i in 0..3 {println!("({:2}) rng.rand():                   {:0<16x}", i, rng.rand())}
-    rng = OrangeyCtx::new();
-    for i in 0..3 {println!("({:2}) rng.rand_range(64, 128):      {}", i, rng.rand_range(64..128))}
-    rng = OrangeyCtx::new();
-    for i in 0..3 {println!("({:2}) rng.uniform_double():         {}", i, rng.uniform_double())}
-    rng = OrangeyCtx::new();
-    for i in 0..3 {println!("({:2}) rng.all_doubles():            {}", i, rng.all_doubles())}
-    rng = OrangeyCtx::new();
-    for i in 0..3 {println!("({:2}) rng.gaussian():               {}", i, rng.gaussian())}
-    rng = OrangeyCtx::new();
-    for i in 0..3 {println!("({:2}) rng.poisson(1.33333333):      {}", i, rng.poisson(1.33333333))}
+//! The `orangey` command line tool, for getting random numbers out of the library from a shell.
+//! Every command takes `--seed`, so any output can be made again exactly
+
+use orangey::{dice::DiceExpr, OrangeyCtx};
+use std::{
+    fmt::Display,
+    fs::File,
+    hint::black_box,
+    io::{self, BufRead, BufWriter, Write},
+    process::ExitCode,
+    str::FromStr,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+const USAGE: &str = "\
+Usage: orangey <command> [options]
+
+Commands:
+  rand                 Random 64-bit integers
+  range <start..end>   Integers from a range, like 1..100 or 1..=6
+  gaussian             Floats from a normal distribution
+  stream               Raw little-endian output bytes, without end unless --count is given,
+                       for piping into PractRand or dieharder
+  roll <DICE>          Totals of rolling dice, like 3d6+2 or 4d6kh3
+  coin                 Coin flips, heads or tails
+  pick <ITEM>...       Items picked from the ones given
+  id                   Identifiers: UUIDs, ULIDs or hex tokens
+  hist                 A histogram of a distribution, with its mean, standard deviation,
+                       minimum and maximum
+  shuffle              The lines of stdin in random order
+  sample               Lines picked at random from stdin, in random order, without reading
+                       all of it into memory
+  seed <STRING>        The generator state --seed STRING makes, and its save_hex string
+  advance              The state --delta outputs after --state, or after --seed
+  bench                Measure how fast the generator runs on this machine
+  help                 Show this message
+
+Options:
+  -n, --count <N>      How many values to generate [default: 1, 100000 for hist, no limit for
+                       stream]
+      --seed <SEED>    Seed the generator from any string, for reproducible output. Without
+                       it, output can't be predicted
+      --format <FMT>   Write values as text, json, csv, hex, u64, f64 or bytes [default: text].
+                       hex, u64 and bytes write each value's 64 bits, and f64 turns raw
+                       output into a float in [0, 1). coin, pick, id, hist, shuffle, sample,
+                       seed and advance only write text, json or csv, stream only bytes and
+                       bench only text
+  -o, --output <FILE>  Write to FILE instead of stdout
+      --dist <DIST>    Distribution for hist: uniform, gaussian, gaussian-fast, exponential or
+                       exponential-fast [default: gaussian]
+      --bins <N>       Number of bars in hist [default: 40]
+      --mean <MEAN>    Mean for gaussian [default: 0]
+      --std <STD>      Standard deviation for gaussian [default: 1]
+      --lambda <RATE>  Rate for exponential [default: 1]
+      --bias <P>       Chance of heads for coin [default: 0.5]
+      --kind <KIND>    Kind of id: uuid4, ulid or hex [default: uuid4]
+      --len <LEN>      Number of digits in a hex id [default: 32]
+      --timestamp <MS> Milliseconds since 1970 for the start of a ULID [default: now]
+      --state <HEX>    A state as save_hex writes it, like o1:...
+      --delta <N>      Outputs to move the state by, negative to go back [default: 1]
+      --iterations <N> Timed runs of each bench, after one to warm up [default: 5]
+  -h, --help           Show this message
+  -V, --version        Show the version
+";
+
+// Options that don't take a value
+const FLAGS: &[&str] = &["help", "version"];
+
+fn main() -> ExitCode {
+    match Args::parse(std::env::args().skip(1)).and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(Error::Usage(message)) => {
+            eprintln!("orangey: {}\n\nRun `orangey help` for usage", message);
+            ExitCode::from(2)
+        }
+        // Output cut short by the reader, as in `orangey rand -n 1000 | head`, isn't a failure
+        Err(Error::Io(e)) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(Error::Io(e)) => {
+            eprintln!("orangey: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(mut args: Args) -> Result<(), Error> {
+    if args.flag("version") {
+        println!("orangey {}", env!("CARGO_PKG_VERSION"));
+        return Ok(());
+    }
+    if args.flag("help") {
+        print!("{}", USAGE);
+        return Ok(());
+    }
+    match args.command.take().as_deref() {
+        None | Some("help") => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        Some("rand") => rand(args),
+        Some("range") => range(args),
+        Some("gaussian") => gaussian(args),
+        Some("stream") => stream(args),
+        Some("roll") => roll(args),
+        Some("coin") => coin(args),
+        Some("pick") => pick(args),
+        Some("id") => id(args),
+        Some("hist") => hist(args),
+        Some("seed") => seed(args),
+        Some("advance") => advance(args),
+        Some("bench") => bench(args),
+        Some("shuffle") => shuffle(args),
+        Some("sample") => sample(args),
+        Some(other) => Err(Error::usage(format!("unknown command `{}`", other))),
+    }
+}
+
+fn rand(mut args: Args) -> Result<(), Error> {
+    let count: u64 = args.value("count")?.unwrap_or(1);
+    let mut ctx = args.ctx()?;
+    let mut out = args.output()?;
+    for _ in 0..count {
+        out.write(Value::U64(ctx.rand()))?;
+    }
+    out.finish()
+}
+
+fn range(mut args: Args) -> Result<(), Error> {
+    let [range] = args.positionals(["start..end"])?;
+    let (start, end) = parse_range(&range)?;
+    let count: u64 = args.value("count")?.unwrap_or(1);
+    let mut ctx = args.ctx()?;
+    let mut out = args.output()?;
+    for _ in 0..count {
+        out.write(Value::I64(ctx.gen_range(start..=end)))?;
+    }
+    out.finish()
+}
+
+fn gaussian(mut args: Args) -> Result<(), Error> {
+    let (mean, std_dev) = args.gaussian_params()?;
+    let count: u64 = args.value("count")?.unwrap_or(1);
+    let mut ctx = args.ctx()?;
+    let mut out = args.output()?;
+    for _ in 0..count {
+        out.write(Value::F64(ctx.gaussian_with(mean, std_dev)))?;
+    }
+    out.finish()
+}
+
+fn stream(mut args: Args) -> Result<(), Error> {
+    // Big enough that the writes cost little next to filling them
+    const CHUNK: usize = 1 << 16;
+    let mut remaining: Option<u64> = args.value("count")?;
+    args.format_for("stream", &[Format::Bytes])?;
+    let mut ctx = args.ctx()?;
+    let mut out = args.writer()?;
+    let mut buf = vec![0; CHUNK];
+    loop {
+        let len = match remaining {
+            Some(0) => break,
+            Some(outputs) => CHUNK.min(outputs.saturating_mul(8).try_into().unwrap_or(CHUNK)),
+            None => CHUNK,
+        };
+        fill_bytes(&mut ctx, &mut buf[..len]);
+        out.write_all(&buf[..len])?;
+        if let Some(outputs) = &mut remaining {
+            *outputs -= len as u64 / 8;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn roll(mut args: Args) -> Result<(), Error> {
+    // The notation allows spaces, so `orangey roll 1d20 + 5` works without quotes
+    let expr = std::mem::take(&mut args.positionals).join(" ");
+    if expr.is_empty() {
+        return Err(Error::usage("missing <DICE>"));
+    }
+    let dice: DiceExpr = expr
+        .parse()
+        .map_err(|e| Error::usage(format!("`{}`: {}", expr, e)))?;
+    let count: u64 = args.value("count")?.unwrap_or(1);
+    let mut ctx = args.ctx()?;
+    let mut out = args.output()?;
+    for _ in 0..count {
+        out.write(Value::I64(dice.roll(&mut ctx)))?;
+    }
+    out.finish()
+}
+
+fn coin(mut args: Args) -> Result<(), Error> {
+    let bias: f64 = args.value("bias")?.unwrap_or(0.5);
+    if !(0.0..=1.0).contains(&bias) {
+        return Err(Error::usage("the bias must be between 0 and 1"));
+    }
+    let count: u64 = args.value("count")?.unwrap_or(1);
+    let mut ctx = args.ctx()?;
+    let mut out = args.word_output()?;
+    for _ in 0..count {
+        let side = if ctx.rand_bool(bias) {
+            "heads"
+        } else {
+            "tails"
+        };
+        out.write(Value::Str(side))?;
+    }
+    out.finish()
+}
+
+fn pick(mut args: Args) -> Result<(), Error> {
+    let items = std::mem::take(&mut args.positionals);
+    if items.is_empty() {
+        return Err(Error::usage("missing <ITEM>"));
+    }
+    let count: u64 = args.value("count")?.unwrap_or(1);
+    let mut ctx = args.ctx()?;
+    let mut out = args.word_output()?;
+    for _ in 0..count {
+        out.write(Value::Str(ctx.choose(&items).unwrap()))?;
+    }
+    out.finish()
+}
+
+fn id(mut args: Args) -> Result<(), Error> {
+    let kind = args.value("kind")?.unwrap_or(IdKind::Uuid4);
+    // Each kind only takes its own settings, so the others are rejected as unknown
+    let (mut timestamp, mut len) = (0, 0);
+    match kind {
+        IdKind::Uuid4 => {}
+        IdKind::Ulid => {
+            timestamp = match args.value("timestamp")? {
+                Some(timestamp) => timestamp,
+                None => SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |d| d.as_millis() as u64),
+            };
+            if timestamp >= 1 << 48 {
+                return Err(Error::usage("ULID timestamps must fit in 48 bits"));
+            }
+        }
+        IdKind::Hex => len = args.value("len")?.unwrap_or(32),
+    }
+    let count: u64 = args.value("count")?.unwrap_or(1);
+    let mut ctx = args.ctx()?;
+    let mut out = args.word_output()?;
+    for _ in 0..count {
+        let id = match kind {
+            IdKind::Uuid4 => ctx.uuid_v4_string(),
+            IdKind::Ulid => ctx.ulid_string(timestamp),
+            IdKind::Hex => ctx.string_hex(len),
+        };
+        out.write(Value::Str(&id))?;
+    }
+    out.finish()
+}
+
+fn hist(mut args: Args) -> Result<(), Error> {
+    // The longest bar, in characters
+    const WIDTH: u64 = 60;
+    let dist = args.value("dist")?.unwrap_or(Dist::Gaussian);
+    let (a, b) = match dist {
+        Dist::Uniform => (0.0, 1.0),
+        Dist::Gaussian | Dist::GaussianFast => args.gaussian_params()?,
+        Dist::Exponential | Dist::ExponentialFast => {
+            let lambda: f64 = args.value("lambda")?.unwrap_or(1.0);
+            if !lambda.is_finite() || lambda <= 0.0 {
+                return Err(Error::usage("the rate must be finite and positive"));
+            }
+            (lambda, 0.0)
+        }
+    };
+    let count: u64 = args.value("count")?.unwrap_or(100_000);
+    let bins: usize = args.value("bins")?.unwrap_or(40);
+    if count == 0 || bins == 0 {
+        return Err(Error::usage("hist needs at least one value and one bin"));
+    }
+    let format = args.format_for("hist", &[Format::Text, Format::Json, Format::Csv])?;
+    let mut ctx = args.ctx()?;
+    let mut out = args.writer()?;
+    let sample = move |ctx: &mut OrangeyCtx| match dist {
+        Dist::Uniform => ctx.uniform_double(),
+        Dist::Gaussian => ctx.gaussian_with(a, b),
+        Dist::GaussianFast => a + b * ctx.gaussian_fast(),
+        Dist::Exponential => ctx.exponential(a),
+        Dist::ExponentialFast => ctx.exponential_fast() / a,
+    };
+
+    // The bins depend on the range, so the values are made twice instead of kept: once for the
+    // statistics, then again from the same state to count them
+    let mut replay = ctx.clone();
+    let (mut mean, mut m2) = (0.0, 0.0);
+    let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+    for i in 1..=count {
+        let x = sample(&mut ctx);
+        let delta = x - mean;
+        mean += delta / i as f64;
+        m2 += delta * (x - mean);
+        min = min.min(x);
+        max = max.max(x);
+    }
+    let std_dev = (m2 / count as f64).sqrt();
+    // With every value the same there's nothing to spread across bins
+    let bins = if max > min { bins } else { 1 };
+    let width = (max - min) / bins as f64;
+    let mut counts = vec![0u64; bins];
+    for _ in 0..count {
+        let x = sample(&mut replay);
+        let bin = ((x - min) / width) as usize;
+        counts[bin.min(bins - 1)] += 1;
+    }
+
+    let starts = (0..bins).map(|i| min + width * i as f64);
+    match format {
+        Format::Json => {
+            write!(out, "{{\"count\":{},\"mean\":", count)?;
+            write_json_f64(&mut out, mean)?;
+            write!(out, ",\"stddev\":")?;
+            write_json_f64(&mut out, std_dev)?;
+            write!(out, ",\"min\":")?;
+            write_json_f64(&mut out, min)?;
+            write!(out, ",\"max\":")?;
+            write_json_f64(&mut out, max)?;
+            write!(out, ",\"bins\":[")?;
+            for (i, (start, n)) in starts.zip(&counts).enumerate() {
+                out.write_all(if i == 0 {
+                    b"{\"start\":"
+                } else {
+                    b",{\"start\":"
+                })?;
+                write_json_f64(&mut out, start)?;
+                write!(out, ",\"count\":{}}}", n)?;
+            }
+            writeln!(out, "]}}")?;
+        }
+        // Only the bins fit in a table, since the statistics are a row of their own
+        Format::Csv => {
+            writeln!(out, "start,count")?;
+            for (start, n) in starts.zip(&counts) {
+                writeln!(out, "{:?},{}", start, n)?;
+            }
+        }
+        _ => {
+            let tallest = *counts.iter().max().unwrap();
+            for (start, &n) in starts.zip(&counts) {
+                let bar = (n * WIDTH).div_ceil(tallest) as usize;
+                writeln!(out, "{:>12.4} {:>9} {}", start, n, "#".repeat(bar))?;
+            }
+            writeln!(out)?;
+            writeln!(out, "count  {}", count)?;
+            writeln!(out, "mean   {:.6}", mean)?;
+            writeln!(out, "stddev {:.6}", std_dev)?;
+            writeln!(out, "min    {:.6}", min)?;
+            writeln!(out, "max    {:.6}", max)?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn shuffle(mut args: Args) -> Result<(), Error> {
+    let format = args.format_for("shuffle", &[Format::Text, Format::Json, Format::Csv])?;
+    let mut ctx = args.ctx()?;
+    let out = args.writer()?;
+    let mut lines = Vec::new();
+    for line in input_lines() {
+        lines.push(line?);
+    }
+    ctx.shuffle(&mut lines);
+    write_lines(out, format, &lines)
+}
+
+fn sample(mut args: Args) -> Result<(), Error> {
+    let count = args.value("count")?.unwrap_or(1);
+    let format = args.format_for("sample", &[Format::Text, Format::Json, Format::Csv])?;
+    let mut ctx = args.ctx()?;
+    let out = args.writer()?;
+    let mut error = None;
+    let lines = input_lines().map_while(|line| line.map_err(|e| error = Some(e)).ok());
+    let mut picked = ctx.sample_iter(lines, count);
+    if let Some(e) = error {
+        return Err(e.into());
+    }
+    // The reservoir keeps the picks in no particular order, but not a random one either
+    ctx.shuffle(&mut picked);
+    write_lines(out, format, &picked)
+}
+
+fn seed(mut args: Args) -> Result<(), Error> {
+    let [seed] = args.positionals(["STRING"])?;
+    let format = args.format_for("seed", &[Format::Text, Format::Json, Format::Csv])?;
+    let out = args.writer()?;
+    write_state(out, format, &OrangeyCtx::seed_from_str(&seed))
+}
+
+fn advance(mut args: Args) -> Result<(), Error> {
+    let delta = args.value::<String>("delta")?;
+    let state = args.value::<String>("state")?;
+    let seed = args.value::<String>("seed")?;
+    let mut ctx = match (state, seed) {
+        (Some(state), None) => OrangeyCtx::restore_hex(&state)
+            .map_err(|e| Error::usage(format!("invalid value `{}` for `--state`: {}", state, e)))?,
+        (None, Some(seed)) => OrangeyCtx::seed_from_str(&seed),
+        (Some(_), Some(_)) => return Err(Error::usage("give `--state` or `--seed`, not both")),
+        (None, None) => return Err(Error::usage("advance needs `--state` or `--seed`")),
+    };
+    let invalid = |delta: &str| {
+        Error::usage(format!(
+            "invalid value `{}` for `--delta`: expected an integer",
+            delta
+        ))
+    };
+    match delta.as_deref() {
+        None => ctx.skip(1),
+        Some(delta) => match delta.strip_prefix('-') {
+            Some(back) => ctx.skip_back(back.parse().map_err(|_| invalid(delta))?),
+            None => ctx.skip(delta.parse().map_err(|_| invalid(delta))?),
+        },
+    }
+    let format = args.format_for("advance", &[Format::Text, Format::Json, Format::Csv])?;
+    let out = args.writer()?;
+    write_state(out, format, &ctx)
+}
+
+/// Writes the state and stream increment of `ctx` in hex, then its `.save_hex()` string
+fn write_state(mut out: impl Write, format: Format, ctx: &OrangeyCtx) -> Result<(), Error> {
+    let bytes = ctx.state_bytes();
+    let state = u128::from_le_bytes(bytes[..16].try_into().unwrap());
+    let inc = u128::from_le_bytes(bytes[16..].try_into().unwrap());
+    let save = ctx.save_hex();
+    match format {
+        Format::Json => writeln!(
+            out,
+            "{{\"state\":\"{:#034x}\",\"inc\":\"{:#034x}\",\"save\":\"{}\"}}",
+            state, inc, save
+        )?,
+        Format::Csv => {
+            writeln!(out, "state,inc,save")?;
+            writeln!(out, "{:#034x},{:#034x},{}", state, inc, save)?;
+        }
+        _ => {
+            writeln!(out, "state {:#034x}", state)?;
+            writeln!(out, "inc   {:#034x}", inc)?;
+            writeln!(out, "save  {}", save)?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn bench(mut args: Args) -> Result<(), Error> {
+    const BYTES: usize = 1 << 20;
+    const FILLS: u32 = 64;
+    const OPS: u32 = 1 << 24;
+    let iterations: u32 = args.value("iterations")?.unwrap_or(5);
+    if iterations == 0 {
+        return Err(Error::usage("bench needs at least one iteration"));
+    }
+    args.format_for("bench", &[Format::Text])?;
+    let mut ctx = args.ctx()?;
+    let mut out = args.writer()?;
+
+    writeln!(
+        out,
+        "orangey {}, simd feature {}",
+        env!("CARGO_PKG_VERSION"),
+        if cfg!(feature = "simd") { "on" } else { "off" }
+    )?;
+    if cfg!(debug_assertions) {
+        writeln!(
+            out,
+            "warning: built without optimizations, so these are much slower than they should be"
+        )?;
+    }
+    writeln!(out, "{:<26} {:>14} {:>14}", "", "median", "best")?;
+
+    let mut buf = vec![0; BYTES];
+    let gigabytes = (BYTES as u64 * FILLS as u64) as f64 / 1e9;
+    let mut report = |name: &str, unit: &str, amount: f64, run: &mut dyn FnMut()| {
+        let (median, best) = measure(iterations, run);
+        writeln!(
+            out,
+            "{:<26} {:>9.2} {:<4} {:>9.2} {}",
+            name,
+            amount / median,
+            unit,
+            amount / best,
+            unit
+        )
+    };
+    report("raw output, fill_bytes", "GB/s", gigabytes, &mut || {
+        for _ in 0..FILLS {
+            ctx.fill_bytes(&mut buf);
+        }
+        black_box(&buf);
+    })?;
+    #[cfg(feature = "simd")]
+    report("raw output, simd", "GB/s", gigabytes, &mut || {
+        for _ in 0..FILLS {
+            ctx.fill_bytes_simd(&mut buf);
+        }
+        black_box(&buf);
+    })?;
+    let millions = OPS as f64 / 1e6;
+    report("rand_range(0..1000)", "M/s", millions, &mut || {
+        for _ in 0..OPS {
+            black_box(ctx.rand_range(0..black_box(1000)));
+        }
+    })?;
+    report("gaussian", "M/s", millions, &mut || {
+        for _ in 0..OPS {
+            black_box(ctx.gaussian());
+        }
+    })?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Times `run` `iterations` times after one untimed run, returning the median and best times in
+/// seconds
+fn measure(iterations: u32, run: &mut dyn FnMut()) -> (f64, f64) {
+    run();
+    let mut times: Vec<f64> = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed().as_secs_f64()
+        })
+        .collect();
+    times.sort_by(f64::total_cmp);
+    (times[times.len() / 2], times[0])
+}
+
+/// Fills `buf` with output as `.fill_bytes()` does, with the faster version when there is one
+fn fill_bytes(ctx: &mut OrangeyCtx, buf: &mut [u8]) {
+    #[cfg(feature = "simd")]
+    ctx.fill_bytes_simd(buf);
+    #[cfg(not(feature = "simd"))]
+    ctx.fill_bytes(buf);
+}
+
+/// The lines of stdin without their newlines, as bytes so that any input can be passed through
+fn input_lines() -> impl Iterator<Item = io::Result<Vec<u8>>> {
+    io::stdin().lock().split(b'\n')
+}
+
+/// Writes `lines` as they came in text, or as strings in the other formats, with anything that
+/// isn't UTF-8 replaced
+fn write_lines(mut out: impl Write, format: Format, lines: &[Vec<u8>]) -> Result<(), Error> {
+    if format != Format::Text {
+        let mut out = Output::new(out, format);
+        for line in lines {
+            out.write(Value::Str(&String::from_utf8_lossy(line)))?;
+        }
+        return out.finish();
+    }
+    for line in lines {
+        out.write_all(line)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Parses `start..end` or `start..=end` into inclusive bounds
+fn parse_range(range: &str) -> Result<(i64, i64), Error> {
+    let invalid = || Error::usage(format!("`{}` is not a range like 1..100 or 1..=6", range));
+    let (start, end, inclusive) = match range.split_once("..=") {
+        Some((start, end)) => (start, end, true),
+        None => {
+            let (start, end) = range.split_once("..").ok_or_else(invalid)?;
+            (start, end, false)
+        }
+    };
+    let start: i64 = start.parse().map_err(|_| invalid())?;
+    let end: i64 = end.parse().map_err(|_| invalid())?;
+    let end = if inclusive {
+        Some(end)
+    } else {
+        end.checked_sub(1)
+    };
+    match end {
+        Some(end) if start <= end => Ok((start, end)),
+        _ => Err(Error::usage(format!("the range `{}` is empty", range))),
+    }
+}
+
+enum Error {
+    /// The command line was wrong
+    Usage(String),
+    /// Reading or writing failed
+    Io(io::Error),
+}
+
+impl Error {
+    fn usage(message: impl Into<String>) -> Self {
+        Error::Usage(message.into())
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// The command line, split into the command, its positional arguments and its options. Commands
+/// take the options they understand, and `.finish()` rejects any left over
+struct Args {
+    command: Option<String>,
+    positionals: Vec<String>,
+    options: Vec<(String, Option<String>)>,
+}
+
+impl Args {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, Error> {
+        let mut parsed = Args {
+            command: None,
+            positionals: Vec::new(),
+            options: Vec::new(),
+        };
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let name = match arg.as_str() {
+                "--" => {
+                    args.by_ref().for_each(|arg| parsed.push_positional(arg));
+                    break;
+                }
+                "-n" => "count".to_string(),
+                "-o" => "output".to_string(),
+                "-h" => "help".to_string(),
+                "-V" => "version".to_string(),
+                _ => match arg.strip_prefix("--") {
+                    Some(name) => name.to_string(),
+                    None => {
+                        // Anything else is positional, including a lone `-` and negative numbers
+                        parsed.push_positional(arg);
+                        continue;
+                    }
+                },
+            };
+            let (name, value) = match name.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None if FLAGS.contains(&name.as_str()) => (name, None),
+                None => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| Error::usage(format!("`--{}` needs a value", name)))?;
+                    (name, Some(value))
+                }
+            };
+            parsed.options.push((name, value));
+        }
+        Ok(parsed)
+    }
+
+    /// The first positional argument is the command, and the rest belong to it
+    fn push_positional(&mut self, arg: String) {
+        if self.command.is_none() {
+            self.command = Some(arg);
+        } else {
+            self.positionals.push(arg);
+        }
+    }
+
+    /// Takes every use of the option `name`, returning the value of the last
+    fn take(&mut self, name: &str) -> Option<Option<String>> {
+        let mut last = None;
+        self.options.retain(|(option, value)| {
+            if option == name {
+                last = Some(value.clone());
+            }
+            option != name
+        });
+        last
+    }
+
+    /// Checks whether the flag `name` was given
+    fn flag(&mut self, name: &str) -> bool {
+        self.take(name).is_some()
+    }
+
+    /// Parses the value of the option `name`, if it was given
+    fn value<T: FromStr>(&mut self, name: &str) -> Result<Option<T>, Error>
+    where
+        T::Err: Display,
+    {
+        match self.take(name) {
+            None => Ok(None),
+            Some(None) => Err(Error::usage(format!("`--{}` needs a value", name))),
+            Some(Some(value)) => value.parse().map(Some).map_err(|e| {
+                Error::usage(format!("invalid value `{}` for `--{}`: {}", value, name, e))
+            }),
+        }
+    }
+
+    /// Takes exactly as many positional arguments as `names` lists
+    fn positionals<const N: usize>(&mut self, names: [&str; N]) -> Result<[String; N], Error> {
+        if self.positionals.len() < N {
+            return Err(Error::usage(format!(
+                "missing <{}>",
+                names[self.positionals.len()]
+            )));
+        }
+        if self.positionals.len() > N {
+            return Err(Error::usage(format!(
+                "unexpected argument `{}`",
+                self.positionals[N]
+            )));
+        }
+        let positionals = std::mem::take(&mut self.positionals);
+        Ok(positionals.try_into().unwrap())
+    }
+
+    /// Makes the generator from `--seed` if it was given. Otherwise it's seeded by the operating
+    /// system when built with the `getrandom` feature, and from the clock without it
+    fn ctx(&mut self) -> Result<OrangeyCtx, Error> {
+        Ok(match self.value::<String>("seed")? {
+            Some(seed) => OrangeyCtx::seed_from_str(&seed),
+            #[cfg(feature = "getrandom")]
+            None => OrangeyCtx::from_entropy(),
+            #[cfg(not(feature = "getrandom"))]
+            None => OrangeyCtx::from_time(),
+        })
+    }
+
+    /// Takes `--mean` and `--std` for a normal distribution
+    fn gaussian_params(&mut self) -> Result<(f64, f64), Error> {
+        let mean: f64 = self.value("mean")?.unwrap_or(0.0);
+        let std_dev: f64 = self.value("std")?.unwrap_or(1.0);
+        if !mean.is_finite() || !std_dev.is_finite() || std_dev < 0.0 {
+            return Err(Error::usage(
+                "the mean must be finite and the standard deviation finite and not negative",
+            ));
+        }
+        Ok((mean, std_dev))
+    }
+
+    /// Sets up writing values in the `--format` asked for, once the command has taken everything
+    /// else it needs
+    fn output(mut self) -> Result<Output<impl Write>, Error> {
+        let format = self.value("format")?.unwrap_or(Format::Text);
+        Ok(Output::new(self.writer()?, format))
+    }
+
+    /// Like `.output()`, for commands that write words rather than numbers
+    fn word_output(mut self) -> Result<Output<impl Write>, Error> {
+        let format = self.value("format")?.unwrap_or(Format::Text);
+        if !format.has_words() {
+            return Err(Error::usage(
+                "only the text, json and csv formats can write words",
+            ));
+        }
+        Ok(Output::new(self.writer()?, format))
+    }
+
+    /// Takes `--format` for a command that can only write some formats, the first being its
+    /// default
+    fn format_for(&mut self, command: &str, formats: &[Format]) -> Result<Format, Error> {
+        let format = self.value("format")?.unwrap_or(formats[0]);
+        if !formats.contains(&format) {
+            let names: Vec<_> = formats.iter().map(|format| format.name()).collect();
+            let names = match names.split_last() {
+                Some((last, [])) => last.to_string(),
+                Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+                None => unreachable!("every command writes some format"),
+            };
+            return Err(Error::usage(format!(
+                "{} can only write {}",
+                command, names
+            )));
+        }
+        Ok(format)
+    }
+
+    /// Opens `--output`, or stdout if it wasn't given or is `-`. This happens last, so that a
+    /// mistake on the command line doesn't leave an empty file behind
+    fn writer(mut self) -> Result<BufWriter<Box<dyn Write>>, Error> {
+        let path: Option<String> = self.value("output")?;
+        self.finish()?;
+        let out: Box<dyn Write> =
+            match path.as_deref() {
+                None | Some("-") => Box::new(io::stdout().lock()),
+                Some(path) => Box::new(File::create(path).map_err(|e| {
+                    io::Error::new(e.kind(), format!("cannot create {}: {}", path, e))
+                })?),
+            };
+        Ok(BufWriter::with_capacity(1 << 16, out))
+    }
+
+    /// Rejects any options and arguments the command didn't take
+    fn finish(self) -> Result<(), Error> {
+        if let Some((name, _)) = self.options.first() {
+            return Err(Error::usage(format!("unknown option `--{}`", name)));
+        }
+        if let Some(arg) = self.positionals.first() {
+            return Err(Error::usage(format!("unexpected argument `{}`", arg)));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// One value per line, as it would normally be written
+    Text,
+    /// A single JSON array
+    Json,
+    /// A `value` column with one value per row
+    Csv,
+    /// One value per line, as the 16 hex digits of its 64 bits
+    Hex,
+    /// One value per line, as its 64 bits read as an unsigned integer
+    U64,
+    /// One value per line, as a float. Raw output becomes a float in [0, 1), as
+    /// `.uniform_double()` would make it
+    F64,
+    /// The 64 bits of each value as 8 little-endian bytes, with nothing between them
+    Bytes,
+}
+
+impl Format {
+    fn has_words(self) -> bool {
+        matches!(self, Format::Text | Format::Json | Format::Csv)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Format::Text => "text",
+            Format::Json => "json",
+            Format::Csv => "csv",
+            Format::Hex => "hex",
+            Format::U64 => "u64",
+            Format::F64 => "f64",
+            Format::Bytes => "bytes",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "hex" => Ok(Format::Hex),
+            "u64" => Ok(Format::U64),
+            "f64" => Ok(Format::F64),
+            "bytes" => Ok(Format::Bytes),
+            _ => Err("expected text, json, csv, hex, u64, f64 or bytes"),
+        }
+    }
+}
+
+/// The identifiers `id` can make
+#[derive(Clone, Copy)]
+enum IdKind {
+    Uuid4,
+    Ulid,
+    Hex,
+}
+
+impl FromStr for IdKind {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "uuid4" => Ok(IdKind::Uuid4),
+            "ulid" => Ok(IdKind::Ulid),
+            "hex" => Ok(IdKind::Hex),
+            _ => Err("expected uuid4, ulid or hex"),
+        }
+    }
+}
+
+/// The distributions `hist` can draw
+#[derive(Clone, Copy)]
+enum Dist {
+    Uniform,
+    Gaussian,
+    GaussianFast,
+    Exponential,
+    ExponentialFast,
+}
+
+impl FromStr for Dist {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "uniform" => Ok(Dist::Uniform),
+            "gaussian" => Ok(Dist::Gaussian),
+            "gaussian-fast" => Ok(Dist::GaussianFast),
+            "exponential" => Ok(Dist::Exponential),
+            "exponential-fast" => Ok(Dist::ExponentialFast),
+            _ => Err("expected uniform, gaussian, gaussian-fast, exponential or exponential-fast"),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Value<'a> {
+    U64(u64),
+    I64(i64),
+    F64(f64),
+    /// A word rather than a number, which only some formats can show
+    Str(&'a str),
+}
+
+impl Value<'_> {
+    fn bits(self) -> u64 {
+        match self {
+            Value::U64(v) => v,
+            Value::I64(v) => v as u64,
+            Value::F64(v) => v.to_bits(),
+            Value::Str(_) => unreachable!("words are only written in formats that have them"),
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        match self {
+            // The same as `.uniform_double()`
+            Value::U64(v) => f64::from_bits(v & 0x000FFFFFFFFFFFFF | 0x3FF0000000000000) - 1.0,
+            Value::I64(v) => v as f64,
+            Value::F64(v) => v,
+            Value::Str(_) => unreachable!("words are only written in formats that have them"),
+        }
+    }
+}
+
+/// Writes a run of values in one format
+struct Output<W: Write> {
+    out: W,
+    format: Format,
+    written: u64,
+}
+
+impl<W: Write> Output<W> {
+    fn new(out: W, format: Format) -> Self {
+        Output {
+            out,
+            format,
+            written: 0,
+        }
+    }
+
+    fn write(&mut self, value: Value) -> io::Result<()> {
+        match self.format {
+            Format::Text => {
+                write_plain(&mut self.out, value)?;
+                writeln!(self.out)
+            }
+            Format::Json => {
+                self.out
+                    .write_all(if self.written == 0 { b"[" } else { b"," })?;
+                match value {
+                    Value::F64(v) => write_json_f64(&mut self.out, v),
+                    Value::Str(v) => write_json_string(&mut self.out, v),
+                    _ => write_plain(&mut self.out, value),
+                }
+            }
+            Format::Csv => {
+                if self.written == 0 {
+                    writeln!(self.out, "value")?;
+                }
+                match value {
+                    Value::Str(v) => write_csv_string(&mut self.out, v)?,
+                    _ => write_plain(&mut self.out, value)?,
+                }
+                writeln!(self.out)
+            }
+            Format::Hex => writeln!(self.out, "{:016x}", value.bits()),
+            Format::U64 => writeln!(self.out, "{}", value.bits()),
+            Format::F64 => writeln!(self.out, "{:?}", value.to_f64()),
+            Format::Bytes => self.out.write_all(&value.bits().to_le_bytes()),
+        }?;
+        self.written += 1;
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<(), Error> {
+        match self.format {
+            Format::Json if self.written == 0 => self.out.write_all(b"[]\n")?,
+            Format::Json => self.out.write_all(b"]\n")?,
+            // Even with no rows, the header says what the file is
+            Format::Csv if self.written == 0 => writeln!(self.out, "value")?,
+            _ => {}
+        }
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// Writes a value as it would normally be written, in decimal or as the word itself
+fn write_plain(out: &mut impl Write, value: Value) -> io::Result<()> {
+    match value {
+        Value::U64(v) => write!(out, "{}", v),
+        Value::I64(v) => write!(out, "{}", v),
+        Value::F64(v) => write!(out, "{}", v),
+        Value::Str(v) => write!(out, "{}", v),
+    }
+}
+
+/// Writes `s` as a CSV field, quoted only if it needs to be
+fn write_csv_string(out: &mut impl Write, s: &str) -> io::Result<()> {
+    if s.contains([',', '"', '\n', '\r']) {
+        write!(out, "\"{}\"", s.replace('"', "\"\""))
+    } else {
+        write!(out, "{}", s)
+    }
+}
+
+/// Writes `v` as a JSON number, or `null` for the infinities and NaN that JSON doesn't have
+fn write_json_f64(out: &mut impl Write, v: f64) -> io::Result<()> {
+    if v.is_finite() {
+        write!(out, "{:?}", v)
+    } else {
+        write!(out, "null")
+    }
+}
+
+/// Writes `s` as a quoted JSON string
+fn write_json_string(out: &mut impl Write, s: &str) -> io::Result<()> {
+    out.write_all(b"\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => out.write_all(b"\\\"")?,
+            '\\' => out.write_all(b"\\\\")?,
+            '\n' => out.write_all(b"\\n")?,
+            '\r' => out.write_all(b"\\r")?,
+            '\t' => out.write_all(b"\\t")?,
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32)?,
+            c => write!(out, "{}", c)?,
+        }
+    }
+    out.write_all(b"\"")
 }
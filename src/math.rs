@@ -0,0 +1,62 @@
+// The `f64` methods that live in std rather than core, routed through libm for `no_std` builds.
+// Only the methods the samplers actually use are here.
+
+pub(crate) trait FloatExt {
+    fn floor(self) -> f64;
+    fn sqrt(self) -> f64;
+    fn ln(self) -> f64;
+    fn ln_1p(self) -> f64;
+    fn exp(self) -> f64;
+    fn powf(self, n: f64) -> f64;
+    fn powi(self, n: i32) -> f64;
+    fn cos(self) -> f64;
+    fn tan(self) -> f64;
+    fn acos(self) -> f64;
+    fn sin_cos(self) -> (f64, f64);
+}
+
+impl FloatExt for f64 {
+    fn floor(self) -> f64 {
+        libm::floor(self)
+    }
+
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+
+    fn ln(self) -> f64 {
+        libm::log(self)
+    }
+
+    fn ln_1p(self) -> f64 {
+        libm::log1p(self)
+    }
+
+    fn exp(self) -> f64 {
+        libm::exp(self)
+    }
+
+    fn powf(self, n: f64) -> f64 {
+        libm::pow(self, n)
+    }
+
+    fn powi(self, n: i32) -> f64 {
+        libm::pow(self, n as f64)
+    }
+
+    fn cos(self) -> f64 {
+        libm::cos(self)
+    }
+
+    fn tan(self) -> f64 {
+        libm::tan(self)
+    }
+
+    fn acos(self) -> f64 {
+        libm::acos(self)
+    }
+
+    fn sin_cos(self) -> (f64, f64) {
+        libm::sincos(self)
+    }
+}
@@ -0,0 +1,202 @@
+//! Monte Carlo integration over boxes in any number of dimensions. Every point takes one output
+//! of the generator per dimension, so a run of `n` points from a given state always gives the same
+//! estimate, and a run can be split between workers by `.skip()`ping each one ahead to its share
+//! of the points. Every function takes any [`OrangeyEngine`]
+
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::FloatExt;
+use crate::OrangeyEngine;
+use core::ops::Range;
+
+/// The result of an integration: the estimate of the integral and its standard error
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    /// The estimated value of the integral
+    pub value: f64,
+    /// The standard error of `value`, estimated from the spread of the samples. The true integral is
+    /// within two of these of `value` about 95% of the time
+    pub std_error: f64,
+}
+
+/// Estimates the integral of `f` over the box given by `bounds` from `n` points picked uniformly
+/// inside it. This consumes exactly `D` outputs of the generator per point
+///
+/// # Panics
+///
+/// Panics if `n` is less than 2, or if any of the bounds isn't finite or ends before it starts
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{monte_carlo, OrangeyCtx};
+///
+/// let mut orangey_ctx = OrangeyCtx::new();
+/// let estimate = monte_carlo::integrate(&mut orangey_ctx, |[x]| x * x, &[0.0..3.0], 100_000);
+/// assert!((estimate.value - 9.0).abs() < 4.0 * estimate.std_error);
+///
+/// // The area of the unit disk, from the square around it
+/// let inside = |[x, y]: [f64; 2]| if x * x + y * y < 1.0 { 1.0 } else { 0.0 };
+/// let bounds = [-1.0..1.0, -1.0..1.0];
+/// let estimate = monte_carlo::integrate(&mut orangey_ctx, inside, &bounds, 100_000);
+/// assert!((estimate.value - std::f64::consts::PI).abs() < 4.0 * estimate.std_error);
+/// ```
+///
+/// Splitting the points between two workers gives the same samples as one run:
+///
+/// ```
+/// use orangey::{monte_carlo, OrangeyCtx};
+///
+/// let f = |[x, y]: [f64; 2]| (x * y).sin();
+/// let bounds = [0.0..1.0, 0.0..2.0];
+/// let whole = monte_carlo::integrate(&mut OrangeyCtx::new(), f, &bounds, 2000);
+///
+/// let mut second = OrangeyCtx::new();
+/// second.skip(2 * 1000);
+/// let halves = [
+///     monte_carlo::integrate(&mut OrangeyCtx::new(), f, &bounds, 1000),
+///     monte_carlo::integrate(&mut second, f, &bounds, 1000),
+/// ];
+/// let combined = (halves[0].value + halves[1].value) / 2.0;
+/// assert!((combined - whole.value).abs() < 1e-12);
+/// ```
+pub fn integrate<E, F, const D: usize>(
+    ctx: &mut E,
+    mut f: F,
+    bounds: &[Range<f64>; D],
+    n: usize,
+) -> Estimate
+where
+    E: OrangeyEngine,
+    F: FnMut([f64; D]) -> f64,
+{
+    assert!(n >= 2, "cannot estimate the error from fewer than 2 points");
+    let volume = check_bounds(bounds);
+    let mut moments = Moments::default();
+    for _ in 0..n {
+        let point = core::array::from_fn(|d| lerp(&bounds[d], ctx.uniform_double()));
+        moments.push(f(point));
+    }
+    Estimate {
+        value: volume * moments.mean,
+        std_error: volume * (moments.variance() / n as f64).sqrt(),
+    }
+}
+
+/// Estimates the integral of `f` over the box given by `bounds`, after splitting each axis into
+/// `strata` equal parts and picking `per_stratum` points uniformly inside each of the resulting
+/// `strata^D` cells. For smooth functions this gives a much smaller error than `integrate` does
+/// with the same number of points. This consumes exactly `D` outputs of the generator per point,
+/// taking the cells in order with the first axis changing fastest
+///
+/// # Panics
+///
+/// Panics if `strata` is 0, if `per_stratum` is less than 2, or if any of the bounds isn't finite
+/// or ends before it starts
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{monte_carlo, OrangeyCtx};
+///
+/// let f = |[x, y]: [f64; 2]| (x + y).exp();
+/// let bounds = [0.0..1.0, 0.0..1.0];
+/// let exact = (std::f64::consts::E - 1.0).powi(2);
+///
+/// let mut orangey_ctx = OrangeyCtx::new();
+/// let plain = monte_carlo::integrate(&mut orangey_ctx, f, &bounds, 20_000);
+/// let stratified = monte_carlo::integrate_stratified(&mut orangey_ctx, f, &bounds, 100, 2);
+/// assert!((stratified.value - exact).abs() < 4.0 * stratified.std_error);
+/// assert!(stratified.std_error < plain.std_error / 10.0);
+/// ```
+pub fn integrate_stratified<E, F, const D: usize>(
+    ctx: &mut E,
+    mut f: F,
+    bounds: &[Range<f64>; D],
+    strata: usize,
+    per_stratum: usize,
+) -> Estimate
+where
+    E: OrangeyEngine,
+    F: FnMut([f64; D]) -> f64,
+{
+    assert!(strata > 0, "cannot split the axes into 0 strata");
+    assert!(
+        per_stratum >= 2,
+        "cannot estimate the error from fewer than 2 points per stratum"
+    );
+    let volume = check_bounds(bounds);
+    let cells = strata.pow(D as u32);
+    let cell_volume = volume / cells as f64;
+    let mut cell = [0; D];
+    let mut value = 0.0;
+    let mut variance = 0.0;
+    for _ in 0..cells {
+        let mut moments = Moments::default();
+        for _ in 0..per_stratum {
+            let point = core::array::from_fn(|d| {
+                let t = (cell[d] as f64 + ctx.uniform_double()) / strata as f64;
+                lerp(&bounds[d], t)
+            });
+            moments.push(f(point));
+        }
+        value += cell_volume * moments.mean;
+        variance += cell_volume * cell_volume * moments.variance() / per_stratum as f64;
+        // Step to the next cell like an odometer
+        for index in cell.iter_mut() {
+            *index += 1;
+            if *index < strata {
+                break;
+            }
+            *index = 0;
+        }
+    }
+    Estimate {
+        value,
+        std_error: variance.sqrt(),
+    }
+}
+
+/// Returns the volume of the box, panicking if any of its bounds are unusable
+fn check_bounds(bounds: &[Range<f64>]) -> f64 {
+    bounds.iter().fold(1.0, |volume, range| {
+        assert!(
+            range.start.is_finite() && range.end.is_finite() && range.start <= range.end,
+            "cannot integrate over the range {:?}",
+            range
+        );
+        volume * (range.end - range.start)
+    })
+}
+
+/// Maps `t` in [0, 1) into `range`, keeping the result below `range.end`
+fn lerp(range: &Range<f64>, t: f64) -> f64 {
+    let x = range.start + t * (range.end - range.start);
+    if x < range.end {
+        x
+    } else {
+        range.start.max(range.end.next_down())
+    }
+}
+
+/// The running mean and spread of the samples, kept with Welford's method so that large values
+/// don't swamp the variance in rounding error
+#[derive(Default)]
+struct Moments {
+    count: f64,
+    mean: f64,
+    sum_sq: f64,
+}
+
+impl Moments {
+    fn push(&mut self, x: f64) {
+        self.count += 1.0;
+        let delta = x - self.mean;
+        self.mean += delta / self.count;
+        self.sum_sq += delta * (x - self.mean);
+    }
+
+    /// The sample variance, with Bessel's correction
+    fn variance(&self) -> f64 {
+        self.sum_sq / (self.count - 1.0)
+    }
+}
@@ -0,0 +1,183 @@
+//! Pronounceable names built from patterns, for procedural game content. A pattern like
+//! `"CVC-CV"` is a template where each letter that names a class of sounds is replaced by a pick
+//! from that class, and every other character is kept as it is. `C` and `V` start out as
+//! consonants and vowels, and more classes can be added or those two replaced
+//!
+//! # Examples
+//!
+//! ```
+//! use orangey::{namegen::NameGenerator, OrangeyCtx};
+//!
+//! let elves = NameGenerator::builder()
+//!     .pattern("CVCVL")
+//!     .pattern("VCVL")
+//!     .class('C', &["l", "r", "th", "n", "s", "v"])
+//!     .class('V', &["a", "e", "i", "ae", "ia"])
+//!     .class('L', &["n", "l", "s", "wen", "riel"])
+//!     .build()
+//!     .unwrap();
+//!
+//! let mut orangey_ctx = OrangeyCtx::seed_from_str("the world");
+//! let name = orangey_ctx.sample(&elves);
+//! assert!(name.chars().next().unwrap().is_uppercase());
+//! ```
+
+use crate::{distributions::Distribution, OrangeyEngine, ParamError};
+use alloc::{string::String, vec::Vec};
+
+/// The consonants `C` stands for unless it's given others
+pub const CONSONANTS: &[&str] = &[
+    "b", "d", "f", "g", "h", "k", "l", "m", "n", "p", "r", "s", "t", "v", "z", "ch", "sh", "th",
+];
+
+/// The vowels `V` stands for unless it's given others
+pub const VOWELS: &[&str] = &["a", "e", "i", "o", "u", "ai", "ei", "ou"];
+
+/// Generates names from one or more patterns, sampled with
+/// [`OrangeyCtx::sample`](crate::OrangeyCtx::sample) or any engine's `.sample()`. Each name takes
+/// one bounded integer, as `.rand_range()` would, for each choice that has more than one option:
+/// which pattern to follow, then each class letter of it from left to right
+///
+/// Generators are put together with [`NameGenerator::builder`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameGenerator {
+    patterns: Vec<Vec<Symbol>>,
+    classes: Vec<Vec<String>>,
+    capitalize: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Symbol {
+    Class(usize),
+    Literal(char),
+}
+
+/// Builds a [`NameGenerator`], returned by [`NameGenerator::builder`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameGeneratorBuilder {
+    patterns: Vec<String>,
+    classes: Vec<(char, Vec<String>)>,
+    capitalize: bool,
+}
+
+impl NameGenerator {
+    /// Starts a generator with no patterns, where `C` and `V` stand for [`CONSONANTS`] and
+    /// [`VOWELS`], and names are capitalized
+    pub fn builder() -> NameGeneratorBuilder {
+        NameGeneratorBuilder {
+            patterns: Vec::new(),
+            classes: Vec::new(),
+            capitalize: true,
+        }
+        .class('C', CONSONANTS)
+        .class('V', VOWELS)
+    }
+
+    /// Generates a name, adding it to the end of `name`. This is what `.sample()` does, without
+    /// needing a new string each time
+    pub fn sample_into<E: OrangeyEngine>(&self, ctx: &mut E, name: &mut String) {
+        let start = name.len();
+        for &symbol in pick(ctx, &self.patterns) {
+            match symbol {
+                Symbol::Class(class) => name.push_str(pick::<String, _>(ctx, &self.classes[class])),
+                Symbol::Literal(c) => name.push(c),
+            }
+        }
+        if self.capitalize {
+            if let Some(first) = name[start..].chars().next() {
+                let rest = name.split_off(start + first.len_utf8());
+                name.truncate(start);
+                name.extend(first.to_uppercase());
+                name.push_str(&rest);
+            }
+        }
+    }
+}
+
+/// Picks one of `options`, only consuming output when there's a choice to make
+fn pick<'a, T, E: OrangeyEngine>(ctx: &mut E, options: &'a [T]) -> &'a T {
+    match options {
+        [only] => only,
+        _ => &options[ctx.rand_range(0..options.len() as u64) as usize],
+    }
+}
+
+impl Distribution<String> for NameGenerator {
+    fn sample<E: OrangeyEngine>(&self, ctx: &mut E) -> String {
+        let mut name = String::new();
+        self.sample_into(ctx, &mut name);
+        name
+    }
+}
+
+impl NameGeneratorBuilder {
+    /// Adds a pattern, which each name follows with the same probability as every other pattern
+    pub fn pattern(mut self, pattern: &str) -> Self {
+        self.patterns.push(pattern.into());
+        self
+    }
+
+    /// Makes `letter` stand for a pick from `options` in patterns, replacing what it stood for
+    /// before. Each option is picked with the same probability, so listing one several times
+    /// makes it more common
+    pub fn class(mut self, letter: char, options: &[&str]) -> Self {
+        let options = options.iter().map(|&option| option.into()).collect();
+        match self.classes.iter_mut().find(|(c, _)| *c == letter) {
+            Some((_, existing)) => *existing = options,
+            None => self.classes.push((letter, options)),
+        }
+        self
+    }
+
+    /// Sets whether the first letter of each name is made uppercase, which it is unless set
+    pub fn capitalize(mut self, capitalize: bool) -> Self {
+        self.capitalize = capitalize;
+        self
+    }
+
+    /// Finishes the generator. There must be at least one pattern, none of them empty, and every
+    /// class a pattern uses must have at least one option
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::{namegen::NameGenerator, ParamError};
+    ///
+    /// let no_patterns = NameGenerator::builder().build();
+    /// assert_eq!(no_patterns, Err(ParamError::Empty));
+    /// let no_options = NameGenerator::builder().pattern("CVX").class('X', &[]).build();
+    /// assert_eq!(no_options, Err(ParamError::Empty));
+    /// ```
+    pub fn build(self) -> Result<NameGenerator, ParamError> {
+        if self.patterns.is_empty() || self.patterns.iter().any(String::is_empty) {
+            return Err(ParamError::Empty);
+        }
+        let patterns = self
+            .patterns
+            .iter()
+            .map(|pattern| {
+                pattern
+                    .chars()
+                    .map(
+                        |c| match self.classes.iter().position(|(letter, _)| *letter == c) {
+                            Some(class) if self.classes[class].1.is_empty() => {
+                                Err(ParamError::Empty)
+                            }
+                            Some(class) => Ok(Symbol::Class(class)),
+                            None => Ok(Symbol::Literal(c)),
+                        },
+                    )
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(NameGenerator {
+            patterns,
+            classes: self
+                .classes
+                .into_iter()
+                .map(|(_, options)| options)
+                .collect(),
+            capitalize: self.capitalize,
+        })
+    }
+}
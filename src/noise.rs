@@ -0,0 +1,324 @@
+//! Coherent noise for procedural generation: smooth random functions of position whose values
+//! change gradually, so nearby points get similar values. Each generator shuffles its own
+//! permutation table from an [`OrangeyEngine`] when it is made, so one seed always gives the same
+//! terrain, and sampling it afterwards doesn't touch the generator
+//!
+//! Values lie in [-1, 1], with the largest ones close to the ends. [`Perlin`] noise repeats every
+//! 256 units along each axis, or over a chosen period with its tiled variants. The lattices of
+//! [`OpenSimplex`] don't line up with the axes, so it has no tiled variants
+
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::FloatExt;
+use crate::OrangeyEngine;
+
+const SIZE: usize = 256;
+
+// Eight evenly spaced unit vectors
+const GRAD_2D: [[f64; 2]; 8] = {
+    const S: f64 = core::f64::consts::FRAC_1_SQRT_2;
+    [
+        [1.0, 0.0],
+        [S, S],
+        [0.0, 1.0],
+        [-S, S],
+        [-1.0, 0.0],
+        [-S, -S],
+        [0.0, -1.0],
+        [S, -S],
+    ]
+};
+
+// The midpoints of the edges of a cube, with four repeated so a hash can pick one with a mask
+const GRAD_3D: [[f64; 3]; 16] = [
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [1.0, -1.0, 0.0],
+    [-1.0, -1.0, 0.0],
+    [1.0, 0.0, 1.0],
+    [-1.0, 0.0, 1.0],
+    [1.0, 0.0, -1.0],
+    [-1.0, 0.0, -1.0],
+    [0.0, 1.0, 1.0],
+    [0.0, -1.0, 1.0],
+    [0.0, 1.0, -1.0],
+    [0.0, -1.0, -1.0],
+    [1.0, 1.0, 0.0],
+    [-1.0, 1.0, 0.0],
+    [0.0, -1.0, 1.0],
+    [0.0, -1.0, -1.0],
+];
+
+/// A shuffled ordering of `0..256`, stored twice over so hashes can index past the end
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Permutation([u8; 2 * SIZE]);
+
+impl Permutation {
+    fn new<E: OrangeyEngine>(ctx: &mut E) -> Self {
+        let mut order: [u8; SIZE] = core::array::from_fn(|i| i as u8);
+        ctx.shuffle(&mut order);
+        Permutation(core::array::from_fn(|i| order[i % SIZE]))
+    }
+
+    fn hash_2d(&self, x: i64, y: i64) -> usize {
+        let x = self.0[(x & 0xFF) as usize] as usize;
+        self.0[x + (y & 0xFF) as usize] as usize
+    }
+
+    fn hash_3d(&self, x: i64, y: i64, z: i64) -> usize {
+        let xy = self.hash_2d(x, y);
+        self.0[xy + (z & 0xFF) as usize] as usize
+    }
+}
+
+/// Ken Perlin's improved gradient noise, in two and three dimensions. The lattice is aligned with
+/// the axes, which can show as faint grid-aligned features; [`OpenSimplex`] avoids those
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{noise::Perlin, OrangeyCtx};
+///
+/// let mut orangey_ctx = OrangeyCtx::seed_from_str("island 7");
+/// let terrain = Perlin::new(&mut orangey_ctx);
+/// let height = terrain.get(12.3, 45.6);
+/// assert!((-1.0..=1.0).contains(&height));
+/// // Nearby points have similar heights
+/// assert!((terrain.get(12.31, 45.6) - height).abs() < 0.05);
+/// // And the same seed builds the same terrain
+/// let same = Perlin::new(&mut OrangeyCtx::seed_from_str("island 7"));
+/// assert_eq!(same.get(12.3, 45.6), height);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Perlin {
+    permutation: Permutation,
+}
+
+impl Perlin {
+    // Bring the largest values up to about 1
+    const SCALE_2D: f64 = core::f64::consts::SQRT_2;
+    const SCALE_3D: f64 = 0.965;
+
+    /// Builds the noise from a table shuffled by `ctx`, which takes 255 bounded integers
+    pub fn new<E: OrangeyEngine>(ctx: &mut E) -> Self {
+        Perlin {
+            permutation: Permutation::new(ctx),
+        }
+    }
+
+    /// Returns the 2D noise at `(x, y)`
+    pub fn get(&self, x: f64, y: f64) -> f64 {
+        self.noise_2d(x, y, [SIZE as u32; 2])
+    }
+
+    /// Returns the 2D noise at `(x, y)`, made to repeat every `period[0]` units along x and every
+    /// `period[1]` along y so it wraps seamlessly around a tile of that size
+    ///
+    /// # Panics
+    ///
+    /// Panics if either period is 0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::{noise::Perlin, OrangeyCtx};
+    ///
+    /// let texture = Perlin::new(&mut OrangeyCtx::new());
+    /// let a = texture.get_tiled(1.25, 2.5, [8, 4]);
+    /// assert!((texture.get_tiled(1.25 + 8.0, 2.5 - 4.0, [8, 4]) - a).abs() < 1e-12);
+    /// ```
+    pub fn get_tiled(&self, x: f64, y: f64, period: [u32; 2]) -> f64 {
+        assert!(period.iter().all(|&p| p > 0), "noise periods must not be 0");
+        self.noise_2d(x, y, period)
+    }
+
+    /// Returns the 3D noise at `(x, y, z)`
+    pub fn get_3d(&self, x: f64, y: f64, z: f64) -> f64 {
+        self.noise_3d(x, y, z, [SIZE as u32; 3])
+    }
+
+    /// Returns the 3D noise at `(x, y, z)`, made to repeat over `period` along each axis in turn
+    ///
+    /// # Panics
+    ///
+    /// Panics if any period is 0
+    pub fn get_3d_tiled(&self, x: f64, y: f64, z: f64, period: [u32; 3]) -> f64 {
+        assert!(period.iter().all(|&p| p > 0), "noise periods must not be 0");
+        self.noise_3d(x, y, z, period)
+    }
+
+    fn noise_2d(&self, x: f64, y: f64, period: [u32; 2]) -> f64 {
+        let (x0, dx) = split(x);
+        let (y0, dy) = split(y);
+        let [x1, y1] = [x0 + 1, y0 + 1];
+        let [x0, x1] = [x0, x1].map(|x| x.rem_euclid(period[0] as i64));
+        let [y0, y1] = [y0, y1].map(|y| y.rem_euclid(period[1] as i64));
+        let corner = |x, y, dx, dy| {
+            let [gx, gy] = GRAD_2D[self.permutation.hash_2d(x, y) & 7];
+            gx * dx + gy * dy
+        };
+        let (u, v) = (fade(dx), fade(dy));
+        let bottom = lerp(u, corner(x0, y0, dx, dy), corner(x1, y0, dx - 1.0, dy));
+        let top = lerp(
+            u,
+            corner(x0, y1, dx, dy - 1.0),
+            corner(x1, y1, dx - 1.0, dy - 1.0),
+        );
+        Self::SCALE_2D * lerp(v, bottom, top)
+    }
+
+    fn noise_3d(&self, x: f64, y: f64, z: f64, period: [u32; 3]) -> f64 {
+        let (x0, dx) = split(x);
+        let (y0, dy) = split(y);
+        let (z0, dz) = split(z);
+        let wrap = |i: i64, axis: usize| i.rem_euclid(period[axis] as i64);
+        let xs = [wrap(x0, 0), wrap(x0 + 1, 0)];
+        let ys = [wrap(y0, 1), wrap(y0 + 1, 1)];
+        let zs = [wrap(z0, 2), wrap(z0 + 1, 2)];
+        let corner = |i: usize, j: usize, k: usize| {
+            let [gx, gy, gz] = GRAD_3D[self.permutation.hash_3d(xs[i], ys[j], zs[k]) & 15];
+            gx * (dx - i as f64) + gy * (dy - j as f64) + gz * (dz - k as f64)
+        };
+        let (u, v, w) = (fade(dx), fade(dy), fade(dz));
+        let face = |k| {
+            lerp(
+                v,
+                lerp(u, corner(0, 0, k), corner(1, 0, k)),
+                lerp(u, corner(0, 1, k), corner(1, 1, k)),
+            )
+        };
+        Self::SCALE_3D * lerp(w, face(0), face(1))
+    }
+}
+
+impl core::fmt::Debug for Perlin {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Perlin").finish_non_exhaustive()
+    }
+}
+
+/// Gradient noise on the lattices of OpenSimplex2: triangles in 2D and the body-centered cubic
+/// lattice in 3D, with each lattice point's gradient fading out as `(r² - d²)⁴`. Neither lattice
+/// lines up with the axes, so the noise shows far fewer grid-aligned features than [`Perlin`]
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{noise::OpenSimplex, OrangeyCtx};
+///
+/// let clouds = OpenSimplex::new(&mut OrangeyCtx::seed_from_str("weather"));
+/// let density = clouds.get_3d(0.4, 1.7, 2.2);
+/// assert!((-1.0..=1.0).contains(&density));
+/// assert!((clouds.get_3d(0.4, 1.7, 2.21) - density).abs() < 0.05);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct OpenSimplex {
+    permutation: Permutation,
+}
+
+impl OpenSimplex {
+    // Bring the largest values up to about 1
+    const SCALE_2D: f64 = 99.2;
+    const SCALE_3D: f64 = 32.6;
+    // Skewing along the main diagonal turns the triangular lattice into the square one
+    const SKEW_2D: f64 = 0.3660254037844386;
+    const UNSKEW_2D: f64 = 0.21132486540518713;
+    const RADIUS_SQ_2D: f64 = 0.5;
+    const RADIUS_SQ_3D: f64 = 0.6;
+
+    /// Builds the noise from a table shuffled by `ctx`, which takes 255 bounded integers
+    pub fn new<E: OrangeyEngine>(ctx: &mut E) -> Self {
+        OpenSimplex {
+            permutation: Permutation::new(ctx),
+        }
+    }
+
+    /// Returns the 2D noise at `(x, y)`
+    pub fn get(&self, x: f64, y: f64) -> f64 {
+        let skew = (x + y) * Self::SKEW_2D;
+        let (i, _) = split(x + skew);
+        let (j, _) = split(y + skew);
+        let unskew = (i + j) as f64 * Self::UNSKEW_2D;
+        let (dx, dy) = (x - (i as f64 - unskew), y - (j as f64 - unskew));
+        // The corners of the triangle holding the point are the only lattice points in range
+        let (di, dj) = if dx > dy { (1, 0) } else { (0, 1) };
+        let corners = [
+            (0, 0, dx, dy),
+            (
+                di,
+                dj,
+                dx - di as f64 + Self::UNSKEW_2D,
+                dy - dj as f64 + Self::UNSKEW_2D,
+            ),
+            (
+                1,
+                1,
+                dx - 1.0 + 2.0 * Self::UNSKEW_2D,
+                dy - 1.0 + 2.0 * Self::UNSKEW_2D,
+            ),
+        ];
+        let total: f64 = corners
+            .iter()
+            .map(|&(ci, cj, dx, dy)| {
+                let a = Self::RADIUS_SQ_2D - dx * dx - dy * dy;
+                if a <= 0.0 {
+                    return 0.0;
+                }
+                let [gx, gy] = GRAD_2D[self.permutation.hash_2d(i + ci, j + cj) & 7];
+                a * a * a * a * (gx * dx + gy * dy)
+            })
+            .sum();
+        Self::SCALE_2D * total
+    }
+
+    /// Returns the 3D noise at `(x, y, z)`
+    pub fn get_3d(&self, x: f64, y: f64, z: f64) -> f64 {
+        // Turn the lattice so that its main diagonal points along z, as OpenSimplex2 does
+        let r = (x + y + z) * (2.0 / 3.0);
+        let p = [r - x, r - y, r - z];
+        // The lattice is two cubic grids, one offset by half a cell. Doubling the coordinates puts
+        // both on the integers for hashing, and every point in range is a corner of the cell of
+        // one of the grids holding `p`
+        let mut total = 0.0;
+        for offset in [0.0, 0.5] {
+            let cell = p.map(|c| split(c - offset));
+            for corner in 0..8 {
+                let mut d = [0.0; 3];
+                let mut lattice = [0; 3];
+                for axis in 0..3 {
+                    let step = (corner >> axis) & 1;
+                    d[axis] = cell[axis].1 - step as f64;
+                    lattice[axis] = 2 * (cell[axis].0 + step) + (offset != 0.0) as i64;
+                }
+                let a = Self::RADIUS_SQ_3D - d[0] * d[0] - d[1] * d[1] - d[2] * d[2];
+                if a <= 0.0 {
+                    continue;
+                }
+                let [gx, gy, gz] =
+                    GRAD_3D[self.permutation.hash_3d(lattice[0], lattice[1], lattice[2]) & 15];
+                total += a * a * a * a * (gx * d[0] + gy * d[1] + gz * d[2]);
+            }
+        }
+        Self::SCALE_3D * total
+    }
+}
+
+impl core::fmt::Debug for OpenSimplex {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OpenSimplex").finish_non_exhaustive()
+    }
+}
+
+/// Splits `x` into the lattice cell below it and the offset into that cell
+fn split(x: f64) -> (i64, f64) {
+    let cell = x.floor();
+    (cell as i64, x - cell)
+}
+
+/// Perlin's quintic fade curve, whose first and second derivatives are 0 at both ends
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
@@ -0,0 +1,74 @@
+//! Rayon sources that split up the stream deterministically, so results come out the same no
+//! matter how the work is scheduled
+
+use crate::OrangeyCtx;
+use rayon::prelude::*;
+
+// Values per chunk handed to a rayon worker by `par_rand_iter`, big enough that the jump to each
+// chunk is cheap next to generating it
+const CHUNK: usize = 4096;
+
+impl OrangeyCtx {
+    /// Returns a parallel iterator over the next `n` results of `.rand()`, in order. Collecting it
+    /// gives the same values as `.rand_batch(n)`, and this generator is left in the same state
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let parallel: Vec<u64> = orangey_ctx.par_rand_iter(100_000).collect();
+    /// assert_eq!(parallel, OrangeyCtx::new().rand_batch(100_000));
+    /// ```
+    pub fn par_rand_iter(&mut self, n: usize) -> impl ParallelIterator<Item = u64> {
+        let start = self.snapshot();
+        self.skip(n as u128);
+        let chunks = n.div_ceil(CHUNK);
+        (0..chunks).into_par_iter().flat_map_iter(move |chunk| {
+            let offset = chunk * CHUNK;
+            let mut ctx = start.snapshot();
+            ctx.skip(offset as u128);
+            (offset..n.min(offset + CHUNK)).map(move |_| ctx.rand())
+        })
+    }
+
+    /// Maps `f` over `items` in parallel, giving each item its own generator. The generators are
+    /// picked by each item's position rather than by which thread runs it, so the results are
+    /// reproducible. This consumes two outputs of this generator to seed them
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    /// use rayon::prelude::*;
+    ///
+    /// let paths: Vec<f64> = OrangeyCtx::new()
+    ///     .par_map_with_rng(0..1000, |ctx, _| ctx.brownian(0.01, 1.0).nth(99).unwrap())
+    ///     .collect();
+    /// let again: Vec<f64> = OrangeyCtx::new()
+    ///     .par_map_with_rng(0..1000, |ctx, _| ctx.brownian(0.01, 1.0).nth(99).unwrap())
+    ///     .collect();
+    /// assert_eq!(paths, again);
+    /// ```
+    pub fn par_map_with_rng<I, U, F>(
+        &mut self,
+        items: I,
+        f: F,
+    ) -> impl IndexedParallelIterator<Item = U>
+    where
+        I: IntoParallelIterator,
+        I::Iter: IndexedParallelIterator,
+        F: Fn(&mut OrangeyCtx, I::Item) -> U + Sync + Send,
+        U: Send,
+    {
+        let seed = self.rand_u128();
+        items.into_par_iter().enumerate().map(move |(i, item)| {
+            // Every item runs on its own sequence, all starting from the same seed
+            let mut ctx = OrangeyCtx::new();
+            ctx.srand(seed, i as u128);
+            f(&mut ctx, item)
+        })
+    }
+}
@@ -0,0 +1,216 @@
+//! Passwords and passphrases, from an [`OrangeyCtx`] for test fixtures, placeholder accounts and
+#![cfg_attr(
+    feature = "secure",
+    doc = "the like, or from a [`SecureOrangeyCtx`](crate::SecureOrangeyCtx) for real credentials"
+)]
+#![cfg_attr(
+    not(feature = "secure"),
+    doc = "the like, or from a `SecureOrangeyCtx`, with the `secure` feature, for real credentials"
+)]
+//!
+//! **Passwords from an `OrangeyCtx` are not secure.** It is not a cryptographically secure
+//! generator: anyone who sees a few of its outputs can work out its state and predict everything
+//! it gives before and after, including every password made from it. `SecureOrangeyCtx` has the
+//! same `.password()` and `.passphrase()` methods, so generate real credentials with one of those
+//! seeded from the operating system instead
+//!
+//! # Examples
+//!
+//! ```
+//! use orangey::{password::CharsetPolicy, OrangeyCtx};
+//!
+//! let mut orangey_ctx = OrangeyCtx::seed_from_str("fixtures");
+//! let password = orangey_ctx.password(16, CharsetPolicy::ALL);
+//! assert_eq!(password.len(), 16);
+//! assert!(password.chars().any(|c| c.is_ascii_punctuation()));
+//! ```
+
+use crate::{OrangeyCtx, OrangeyEngine};
+use alloc::string::String;
+
+#[cfg(feature = "eff-wordlist")]
+mod eff_large;
+#[cfg(feature = "eff-wordlist")]
+pub use eff_large::EFF_LARGE;
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &[u8] = b"0123456789";
+const SYMBOLS: &[u8] = b"!\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+
+/// The classes of characters a password is made from, and must have at least one of each
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CharsetPolicy {
+    /// `a` to `z`
+    pub lowercase: bool,
+    /// `A` to `Z`
+    pub uppercase: bool,
+    /// `0` to `9`
+    pub digits: bool,
+    /// The 32 ASCII punctuation characters, from `!` to `~`
+    pub symbols: bool,
+}
+
+impl CharsetPolicy {
+    /// Every class, for 94 characters in all
+    pub const ALL: Self = CharsetPolicy {
+        lowercase: true,
+        uppercase: true,
+        digits: true,
+        symbols: true,
+    };
+
+    /// Letters of both cases and digits, for 62 characters in all
+    pub const ALPHANUMERIC: Self = CharsetPolicy {
+        symbols: false,
+        ..Self::ALL
+    };
+
+    fn classes(self) -> impl Iterator<Item = &'static [u8]> {
+        [
+            (self.lowercase, LOWERCASE),
+            (self.uppercase, UPPERCASE),
+            (self.digits, DIGITS),
+            (self.symbols, SYMBOLS),
+        ]
+        .into_iter()
+        .filter_map(|(wanted, class)| wanted.then_some(class))
+    }
+}
+
+impl Default for CharsetPolicy {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl OrangeyCtx {
+    /// Generates a password of `len` ASCII characters from the classes `policy` asks for, with at
+    /// least one character of each. Every such password is equally likely: whole passwords are
+    /// drawn from all the allowed characters and redrawn until one has every class, rather than
+    /// forcing characters in, which would make some passwords likelier than others. Each attempt
+    /// takes one bounded integer per character, as `.rand_range()` would
+    ///
+    /// **This is not secure**, see the [`password`](crate::password) module. Use
+    #[cfg_attr(
+        feature = "secure",
+        doc = "[`SecureOrangeyCtx::password`](crate::SecureOrangeyCtx::password) for real credentials"
+    )]
+    #[cfg_attr(
+        not(feature = "secure"),
+        doc = "`SecureOrangeyCtx::password`, with the `secure` feature, for real credentials"
+    )]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `policy` asks for no classes, or if `len` is too short to have one character of
+    /// each
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::{password::CharsetPolicy, OrangeyCtx};
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let pin = CharsetPolicy {
+    ///     lowercase: false,
+    ///     uppercase: false,
+    ///     digits: true,
+    ///     symbols: false,
+    /// };
+    /// assert!(orangey_ctx.password(6, pin).bytes().all(|b| b.is_ascii_digit()));
+    ///
+    /// let password = orangey_ctx.password(3, CharsetPolicy::ALPHANUMERIC);
+    /// assert!(password.bytes().any(|b| b.is_ascii_lowercase()));
+    /// assert!(password.bytes().any(|b| b.is_ascii_uppercase()));
+    /// assert!(password.bytes().any(|b| b.is_ascii_digit()));
+    /// ```
+    pub fn password(&mut self, len: usize, policy: CharsetPolicy) -> String {
+        password(self, len, policy)
+    }
+
+    /// Generates a passphrase of `n_words` words picked uniformly and independently from
+    /// `wordlist`, separated by spaces. Each word adds `log2(wordlist.len())` bits to how hard the
+    /// passphrase is to guess, about 12.9 for a diceware list of 7776 words, and takes one bounded
+    /// integer, as `.rand_range()` would
+    ///
+    /// **This is not secure**, see the [`password`](crate::password) module. Use
+    #[cfg_attr(
+        feature = "secure",
+        doc = "[`SecureOrangeyCtx::passphrase`](crate::SecureOrangeyCtx::passphrase) for real credentials"
+    )]
+    #[cfg_attr(
+        not(feature = "secure"),
+        doc = "`SecureOrangeyCtx::passphrase`, with the `secure` feature, for real credentials"
+    )]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `wordlist` is empty and `n_words` is not zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let words = ["correct", "horse", "battery", "staple", "orange", "peel"];
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let passphrase = orangey_ctx.passphrase(&words, 4);
+    /// assert_eq!(passphrase.split(' ').count(), 4);
+    /// assert!(passphrase.split(' ').all(|word| words.contains(&word)));
+    /// ```
+    pub fn passphrase(&mut self, wordlist: &[&str], n_words: usize) -> String {
+        passphrase(self, wordlist, n_words)
+    }
+}
+
+pub(crate) fn password<E: OrangeyEngine>(ctx: &mut E, len: usize, policy: CharsetPolicy) -> String {
+    let classes = policy.classes().count();
+    assert!(
+        classes > 0,
+        "a password needs at least one class of characters"
+    );
+    assert!(
+        len >= classes,
+        "a password of {} characters cannot have one of each of {} classes",
+        len,
+        classes
+    );
+    let mut charset = [0; 94];
+    let mut size = 0;
+    for class in policy.classes() {
+        charset[size..size + class.len()].copy_from_slice(class);
+        size += class.len();
+    }
+    let charset = &charset[..size];
+    let mut password = String::with_capacity(len);
+    loop {
+        password.clear();
+        password.extend((0..len).map(|_| charset[ctx.rand_range(0..size as u64) as usize] as char));
+        if policy
+            .classes()
+            .all(|class| password.bytes().any(|b| class.contains(&b)))
+        {
+            return password;
+        }
+    }
+}
+
+pub(crate) fn passphrase<E: OrangeyEngine>(
+    ctx: &mut E,
+    wordlist: &[&str],
+    n_words: usize,
+) -> String {
+    assert!(
+        !wordlist.is_empty() || n_words == 0,
+        "cannot pick words from an empty list"
+    );
+    let mut passphrase = String::new();
+    for i in 0..n_words {
+        if i > 0 {
+            passphrase.push(' ');
+        }
+        passphrase.push_str(wordlist[ctx.rand_range(0..wordlist.len() as u64) as usize]);
+    }
+    passphrase
+}
@@ -0,0 +1,328 @@
+// The 32-bit counterpart of `OrangeyCtx`, for targets where 128-bit multiplies are slow. It is the
+// standard PCG32, so its outputs match the reference implementation's `pcg32_random_r`.
+
+use crate::{OrangeyEngine, RangeError};
+use core::ops::{Bound, Range, RangeBounds};
+
+/// A PCG32 generator with 64 bits of state and 32-bit outputs, using the XSH-RR output permutation.
+/// Every step is a single 64-bit multiply, so this is much faster than [`OrangeyCtx`](crate::OrangeyCtx)
+/// on 32-bit microcontrollers, at the cost of a period of only 2^64
+///
+/// The methods mirror those of `OrangeyCtx`, with jumps counted in 32-bit outputs. The
+/// distributions, such as `.gaussian()`, `.exponential()`, `.poisson()` and `.sample()`, come from
+/// [`OrangeyEngine`], so bring it into scope to use them
+///
+/// # Examples
+///
+/// ```
+/// use orangey::Orangey32Ctx;
+///
+/// let mut orangey_ctx = Orangey32Ctx::new();
+/// orangey_ctx.srand(42, 54);
+/// assert_eq!(orangey_ctx.rand(), 0xa15c02b7);
+/// assert_eq!(orangey_ctx.peek(1), 0xba1d3330);
+/// assert_eq!(orangey_ctx.rand(), 0x7b47f409);
+/// ```
+///
+/// ```
+/// use orangey::{distributions::Uniform, Orangey32Ctx, OrangeyEngine};
+///
+/// let mut orangey_ctx = Orangey32Ctx::new();
+/// let height = 170.0 + 10.0 * orangey_ctx.gaussian();
+/// let wait = orangey_ctx.exponential(0.5);
+/// let arrivals = orangey_ctx.poisson(4.0);
+/// let die = orangey_ctx.sample(&Uniform::new(1..7).unwrap());
+/// assert!(height.is_finite() && wait >= 0.0 && arrivals < 100 && (1..7).contains(&die));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Orangey32Ctx {
+    state: u64,
+    inc: u64,
+}
+
+impl Orangey32Ctx {
+    const MUL: u64 = 6364136223846793005;
+
+    /// Makes a generator with the same fixed seed as the reference implementation's `PCG32_INITIALIZER`
+    pub const fn new() -> Self {
+        Self::from_seed_parts(0x853c49e6748fea9b, 0xda3e39cb94b95bdb)
+    }
+
+    /// Makes a generator with exactly the given state and stream increment, without the mixing
+    /// `.srand()` does. The increment must be odd, so its lowest bit is always taken as set
+    pub const fn from_seed_parts(state: u64, inc: u64) -> Self {
+        Orangey32Ctx {
+            state,
+            inc: inc | 1,
+        }
+    }
+
+    /// Makes a generator seeded from arbitrary bytes, such as a configuration string. Related inputs
+    /// still give unrelated streams
+    pub fn seed_from_bytes(bytes: &[u8]) -> Self {
+        let seed = crate::seed::expand(bytes);
+        let mut ctx = Self::new();
+        ctx.srand(
+            u64::from_le_bytes(seed[..8].try_into().unwrap()),
+            u64::from_le_bytes(seed[8..16].try_into().unwrap()),
+        );
+        ctx
+    }
+
+    /// Seeds the generator with new initial state and sequence values, like `pcg32_srandom_r`
+    pub fn srand(&mut self, initstate: u64, initseq: u64) {
+        self.state = 0;
+        self.inc = (initseq << 1) | 1;
+        self.step();
+        self.state = self.state.wrapping_add(initstate);
+        self.step();
+    }
+
+    /// Returns which of the 2^63 streams the generator is on, as given to `.srand()`
+    pub fn stream(&self) -> u64 {
+        self.inc >> 1
+    }
+
+    /// Returns a random 32-bit number
+    #[inline]
+    pub fn rand(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+        Self::output(old)
+    }
+
+    /// Runs the generator twice and returns a 64-bit random number, with the first output in the high bits
+    #[inline]
+    pub fn rand_u64(&mut self) -> u64 {
+        let high = self.rand() as u64;
+        (high << 32) | self.rand() as u64
+    }
+
+    /// Jumps `delta` values ahead in the rng stream in `O(log delta)` time
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::Orangey32Ctx;
+    ///
+    /// let mut skipped = Orangey32Ctx::new();
+    /// skipped.skip(1000);
+    /// let mut stepped = Orangey32Ctx::new();
+    /// (0..1000).for_each(|_| { stepped.rand(); });
+    /// assert_eq!(skipped, stepped);
+    /// ```
+    pub fn skip(&mut self, delta: u64) {
+        self.state = advance(self.state, delta, Self::MUL, self.inc);
+    }
+
+    /// Jumps `delta` values back in the rng stream, undoing a `.skip(delta)` or `delta` calls to `.rand()`
+    pub fn skip_back(&mut self, delta: u64) {
+        self.skip(delta.wrapping_neg());
+    }
+
+    /// Peeks at the `delta`-th value ahead in the rng stream, so `.peek(0)` is what `.rand()` returns next.
+    /// This doesn't modify the rng state
+    pub fn peek(&self, delta: u64) -> u32 {
+        Self::output(advance(self.state, delta, Self::MUL, self.inc))
+    }
+
+    /// Fills `buf` with random bytes, taken from the little-endian bytes of consecutive outputs
+    pub fn fill_bytes(&mut self, buf: &mut [u8]) {
+        for chunk in buf.chunks_mut(4) {
+            chunk.copy_from_slice(&self.rand().to_le_bytes()[..chunk.len()]);
+        }
+    }
+
+    /// Generates a number in the range given
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty. Use `.try_rand_range()` to handle that case instead
+    pub fn rand_range(&mut self, range: Range<u32>) -> u32 {
+        match self.try_rand_range(range) {
+            Ok(r) => r,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Generates a number in the range given, which may be any kind of range,
+    /// including the full `0..=u32::MAX`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::{Orangey32Ctx, RangeError};
+    ///
+    /// let mut orangey_ctx = Orangey32Ctx::new();
+    /// let roll = orangey_ctx.try_rand_range(1..=6).unwrap();
+    /// assert!((1..=6).contains(&roll));
+    /// assert_eq!(orangey_ctx.try_rand_range(3..3), Err(RangeError::Empty));
+    /// ```
+    pub fn try_rand_range<R: RangeBounds<u32>>(&mut self, range: R) -> Result<u32, RangeError> {
+        // Widened so the bounds can be checked the same way `OrangeyCtx`'s are
+        let end = match range.end_bound() {
+            Bound::Unbounded => Bound::Included(u32::MAX as u64),
+            end => end.map(|&b| b as u64),
+        };
+        let (low, high) = RangeError::check_bounds(&(range.start_bound().map(|&b| b as u64), end))?;
+        // Lemire's method as in `OrangeyCtx::try_rand_range`, with a 64-bit product
+        let distance = (high - low + 1) as u32;
+        if distance == 0 {
+            return Ok(self.rand());
+        }
+        let mut m = self.rand() as u64 * distance as u64;
+        if (m as u32) < distance {
+            let threshold = distance.wrapping_neg() % distance;
+            while (m as u32) < threshold {
+                m = self.rand() as u64 * distance as u64;
+            }
+        }
+        Ok(low as u32 + (m >> 32) as u32)
+    }
+
+    /// Generates a float in the range [0, 1) with uniform density, from one output
+    pub fn uniform_float(&mut self) -> f32 {
+        const MASK: u32 = 0x007FFFFF;
+        const S_EXP: u32 = 0x3F800000;
+        f32::from_bits((self.rand() & MASK) | S_EXP) - 1.0
+    }
+
+    /// Generates a float in the range [0, 1) with uniform density, from two outputs
+    pub fn uniform_double(&mut self) -> f64 {
+        const MASK: u64 = 0x000FFFFFFFFFFFFF;
+        const S_EXP: u64 = 0x3FF0000000000000;
+        f64::from_bits((self.rand_u64() & MASK) | S_EXP) - 1.0
+    }
+
+    #[inline]
+    fn output(state: u64) -> u32 {
+        ((((state >> 18) ^ state) >> 27) as u32).rotate_right((state >> 59) as _)
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        self.state = self.state.wrapping_mul(Self::MUL).wrapping_add(self.inc);
+    }
+}
+
+impl core::fmt::Debug for Orangey32Ctx {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Orangey32Ctx")
+            .field("state", &format_args!("{:#018x}", self.state))
+            .field("inc", &format_args!("{:#018x}", self.inc))
+            .finish()
+    }
+}
+
+impl Default for Orangey32Ctx {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Orangey32Ctx {
+    /// Overwrites the generator's state with zeros, like
+    /// [`OrangeyCtx::zeroize`](crate::OrangeyCtx::zeroize). The generator is `Copy`, so it can't
+    /// also be wiped when dropped, and every copy of it has to be wiped by hand
+    pub fn zeroize(&mut self) {
+        crate::wipe(&mut self.state);
+        crate::wipe(&mut self.inc);
+    }
+}
+
+/// Each 64-bit result is two outputs, so jumps are counted in pairs of outputs
+impl OrangeyEngine for Orangey32Ctx {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rand_u64()
+    }
+
+    fn skip(&mut self, delta: u128) {
+        Orangey32Ctx::skip(self, (delta as u64).wrapping_mul(2))
+    }
+
+    fn peek_raw(&self, delta: u128) -> u64 {
+        let delta = (delta as u64).wrapping_mul(2);
+        (self.peek(delta) as u64) << 32 | self.peek(delta.wrapping_add(1)) as u64
+    }
+
+    fn uniform_double(&mut self) -> f64 {
+        Orangey32Ctx::uniform_double(self)
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::RngCore for Orangey32Ctx {
+    /// The low half of one `.rand_u64()`, that is the second of two outputs, as
+    /// [`OrangeyEngine::next_u32`] takes. `.rand()` gives every output, as `pcg32_random_r` does
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.rand_u64() as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rand_u64()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        Orangey32Ctx::fill_bytes(self, dst)
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::SeedableRng for Orangey32Ctx {
+    /// The first 8 bytes are the little-endian `initstate`, the last 8 the little-endian `initseq`
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut ctx = Self::new();
+        ctx.srand(
+            u64::from_le_bytes(seed[..8].try_into().unwrap()),
+            u64::from_le_bytes(seed[8..].try_into().unwrap()),
+        );
+        ctx
+    }
+}
+
+/// The 64-bit counterpart of `OrangeyCtx::advance`, jumping an LCG `delta` steps
+pub(crate) const fn advance(state: u64, delta: u64, cur_mult: u64, cur_plus: u64) -> u64 {
+    let mut delta = delta;
+    let mut cur_mult = cur_mult;
+    let mut cur_plus = cur_plus;
+
+    let mut acc_mult: u64 = 1;
+    let mut acc_plus: u64 = 0;
+    while delta > 0 {
+        if delta & 1 != 0 {
+            acc_mult = acc_mult.wrapping_mul(cur_mult);
+            acc_plus = acc_plus.wrapping_mul(cur_mult).wrapping_add(cur_plus);
+        }
+        cur_plus = cur_plus.wrapping_mul(cur_mult.wrapping_add(1));
+        cur_mult = cur_mult.wrapping_mul(cur_mult);
+        delta /= 2;
+    }
+    acc_mult.wrapping_mul(state).wrapping_add(acc_plus)
+}
+
+/// The 64-bit counterpart of `OrangeyCtx::distance`, finding how many steps take `cur_state` to `new_state`
+pub(crate) fn distance(cur_state: u64, new_state: u64, cur_mult: u64, cur_plus: u64) -> u64 {
+    let mut cur_state = cur_state;
+    let mut cur_mult = cur_mult;
+    let mut cur_plus = cur_plus;
+
+    let mut the_bit = 1;
+    let mut distance = 0;
+    while cur_state != new_state {
+        if cur_state & the_bit != new_state & the_bit {
+            cur_state = cur_state.wrapping_mul(cur_mult).wrapping_add(cur_plus);
+            distance |= the_bit;
+        }
+        the_bit <<= 1;
+        cur_plus = cur_plus.wrapping_mul(cur_mult.wrapping_add(1));
+        cur_mult = cur_mult.wrapping_mul(cur_mult);
+    }
+    distance
+}
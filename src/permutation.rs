@@ -0,0 +1,252 @@
+//! Output permutations, which turn the 128-bit state of the generator into each 64-bit output.
+//! [`OrangeyCtx`] always uses [`XslRr`], while [`OrangeyCore`] takes the
+//! permutation as a type parameter
+//!
+//! All three permutations sit on the same LCG, so generators that differ only in their
+//! permutation step through the same states and jump the same way
+
+use crate::{OrangeyCtx, OrangeyEngine, RangeError};
+use core::{
+    marker::PhantomData,
+    ops::{Range, RangeBounds},
+};
+
+/// A way of turning the state after each step into an output
+pub trait OutputPermutation {
+    /// Returns the output for `state`
+    fn permute(state: u128) -> u64;
+}
+
+/// The XSL-RR permutation of the PCG paper: the two halves of the state are xored together and
+/// rotated by the top 6 bits. This is what [`OrangeyCtx`] uses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct XslRr;
+
+impl OutputPermutation for XslRr {
+    #[inline]
+    fn permute(state: u128) -> u64 {
+        ((state >> 64) as u64 ^ state as u64).rotate_right((state >> 122) as _)
+    }
+}
+
+/// The DXSM ("double xor shift multiply") permutation that NumPy switched to for `PCG64DXSM`. The
+/// high half is hashed and multiplied by the low half, which hides the LCG's structure better
+/// than XSL-RR and passes stricter statistical tests
+///
+/// The LCG underneath is this crate's, not NumPy's, so the numbers differ from `PCG64DXSM`'s
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Dxsm;
+
+impl OutputPermutation for Dxsm {
+    #[inline]
+    fn permute(state: u128) -> u64 {
+        let mut high = (state >> 64) as u64;
+        let low = state as u64 | 1;
+        high ^= high >> 32;
+        high = high.wrapping_mul(0xda942042e4dd58b5);
+        high ^= high >> 48;
+        high.wrapping_mul(low)
+    }
+}
+
+/// The RXS-M-XS permutation of the PCG paper, applied to the full 128 bits with the high half
+/// kept. It is a random xorshift, a multiply and a fixed xorshift, so every output bit depends on
+/// the whole state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RxsMXs;
+
+impl OutputPermutation for RxsMXs {
+    #[inline]
+    fn permute(state: u128) -> u64 {
+        const MULTIPLIER: u128 = 0xf69019274d7f699caef17502108ef2d9;
+        let mut word = state ^ (state >> (6 + (state >> 122)));
+        word = word.wrapping_mul(MULTIPLIER);
+        word ^= word >> 86;
+        (word >> 64) as u64
+    }
+}
+
+/// The core generator of [`OrangeyCtx`], taking its output permutation as a type parameter.
+/// `OrangeyCore<XslRr>` gives exactly the numbers `OrangeyCtx` does, except that it has nowhere
+/// to keep the spare deviate of each pair `.gaussian()` makes
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{permutation::{Dxsm, XslRr}, OrangeyCore, OrangeyCtx};
+///
+/// let mut classic = OrangeyCore::<XslRr>::new();
+/// assert_eq!(classic.rand(), OrangeyCtx::new().rand());
+///
+/// let mut stronger = OrangeyCore::<Dxsm>::new();
+/// let roll = stronger.rand_range(1..7);
+/// assert!((1..7).contains(&roll));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrangeyCore<P: OutputPermutation = XslRr> {
+    state: u128,
+    inc: u128,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    permutation: PhantomData<P>,
+}
+
+impl<P: OutputPermutation> OrangeyCore<P> {
+    /// Makes a generator from the same fixed seed as [`OrangeyCtx::new`]
+    pub const fn new() -> Self {
+        Self::from_seed_parts(OrangeyCtx::NEW_STATE, OrangeyCtx::NEW_INC)
+    }
+
+    /// Makes a generator with exactly the given state and stream increment, like
+    /// [`OrangeyCtx::from_seed_parts`]
+    pub const fn from_seed_parts(state: u128, inc: u128) -> Self {
+        OrangeyCore {
+            state,
+            inc: inc | 1,
+            permutation: PhantomData,
+        }
+    }
+
+    /// Makes a generator seeded from arbitrary bytes, like [`OrangeyCtx::seed_from_bytes`]
+    pub fn seed_from_bytes(bytes: &[u8]) -> Self {
+        let base = OrangeyCtx::seed_from_bytes(bytes);
+        Self::from_seed_parts(base.state, base.inc)
+    }
+
+    /// Seeds the generator with new initial state and sequence values, the same way `OrangeyCtx` does
+    pub fn srand(&mut self, initstate: u128, initseq: u128) {
+        let mut base = OrangeyCtx::new();
+        base.srand(initstate, initseq);
+        *self = Self::from_seed_parts(base.state, base.inc);
+    }
+
+    /// Returns which of the 2^127 streams the generator is on, as given to `.srand()`
+    pub fn stream(&self) -> u128 {
+        self.inc >> 1
+    }
+
+    /// Returns a random 64-bit number
+    #[inline]
+    pub fn rand(&mut self) -> u64 {
+        self.state = OrangeyCtx::advance(self.state, 1, OrangeyCtx::MUL, self.inc);
+        P::permute(self.state)
+    }
+
+    /// Runs the generator twice and returns a 128-bit random number, with the first output in the high bits
+    pub fn rand_u128(&mut self) -> u128 {
+        let high = self.rand() as u128;
+        (high << 64) | self.rand() as u128
+    }
+
+    /// Jumps `delta` values ahead in the rng stream in `O(log delta)` time
+    pub fn skip(&mut self, delta: u128) {
+        self.state = OrangeyCtx::advance(self.state, delta, OrangeyCtx::MUL, self.inc);
+    }
+
+    /// Jumps `delta` values back in the rng stream, undoing a `.skip(delta)` or `delta` calls to `.rand()`
+    pub fn skip_back(&mut self, delta: u128) {
+        self.skip(delta.wrapping_neg());
+    }
+
+    /// Peeks at the `delta`-th value ahead in the rng stream, so `.peek(0)` is what `.rand()` returns next.
+    /// This doesn't modify the rng state
+    pub fn peek(&self, delta: u128) -> u64 {
+        P::permute(OrangeyCtx::advance(
+            self.state,
+            delta.wrapping_add(1),
+            OrangeyCtx::MUL,
+            self.inc,
+        ))
+    }
+
+    /// Fills `buf` with random bytes, taken from the little-endian bytes of consecutive outputs
+    pub fn fill_bytes(&mut self, buf: &mut [u8]) {
+        for chunk in buf.chunks_mut(8) {
+            chunk.copy_from_slice(&self.rand().to_le_bytes()[..chunk.len()]);
+        }
+    }
+
+    /// Generates a number in the range given
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty. Use `.try_rand_range()` to handle that case instead
+    pub fn rand_range(&mut self, range: Range<u64>) -> u64 {
+        match self.try_rand_range(range) {
+            Ok(r) => r,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Generates a number in the range given, which may be any kind of range,
+    /// including the full `0..=u64::MAX`
+    pub fn try_rand_range<R: RangeBounds<u64>>(&mut self, range: R) -> Result<u64, RangeError> {
+        let (low, high) = RangeError::check_bounds(&range)?;
+        Ok(low + crate::bounded((high - low).wrapping_add(1), || self.rand()))
+    }
+
+    /// Generates a float in the range [0, 1) with uniform density
+    pub fn uniform_double(&mut self) -> f64 {
+        const MASK: u64 = 0x000FFFFFFFFFFFFF;
+        const S_EXP: u64 = 0x3FF0000000000000;
+        f64::from_bits((self.rand() & MASK) | S_EXP) - 1.0
+    }
+}
+
+impl<P: OutputPermutation> core::fmt::Debug for OrangeyCore<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OrangeyCore")
+            .field("state", &format_args!("{:#034x}", self.state))
+            .field("inc", &format_args!("{:#034x}", self.inc))
+            .finish()
+    }
+}
+
+impl<P: OutputPermutation> Default for OrangeyCore<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<P: OutputPermutation> OrangeyCore<P> {
+    /// Overwrites the generator's state with zeros, like [`OrangeyCtx::zeroize`]. The generator is
+    /// `Copy`, so it can't also be wiped when dropped, and every copy of it has to be wiped by hand
+    pub fn zeroize(&mut self) {
+        crate::wipe(&mut self.state);
+        crate::wipe(&mut self.inc);
+    }
+}
+
+impl<P: OutputPermutation> OrangeyEngine for OrangeyCore<P> {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rand()
+    }
+
+    fn skip(&mut self, delta: u128) {
+        OrangeyCore::skip(self, delta)
+    }
+
+    fn peek_raw(&self, delta: u128) -> u64 {
+        self.peek(delta)
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl<P: OutputPermutation> rand_core::RngCore for OrangeyCore<P> {
+    /// The low half of one output, as [`OrangeyEngine::next_u32`] takes
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.rand() as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rand()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        OrangeyCore::fill_bytes(self, dst)
+    }
+}
@@ -0,0 +1,207 @@
+//! Python bindings through PyO3, so a notebook can make exactly the same streams from a seed as
+//! Rust simulation code does. The `Orangey` class wraps an [`OrangeyCtx`], with the methods of the
+//! `wasm` feature's class under their Rust names, along with the distributions. Each method
+//! consumes the same outputs as the Rust method it's named after, and bad parameters raise
+//! `ValueError` rather than panicking
+//!
+//! To build the extension module, enable PyO3's `extension-module` feature too and ask for a C
+//! crate type, then rename the library so Python finds it, or let maturin do both:
+//!
+//! ```text
+//! cargo rustc --lib --release --features python,pyo3/extension-module --crate-type cdylib
+//! cp target/release/liborangey.so orangey.so
+//! ```
+//!
+//! ```text
+//! >>> from orangey import Orangey
+//! >>> rng = Orangey.seed("experiment 7")
+//! >>> rng.rand_range(1, 7)
+//! >>> rng.gamma(2.0, 1.5)
+//! >>> rng.save_hex()
+//! ```
+
+use crate::OrangeyCtx;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+};
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyBytes};
+
+/// A generator for Python, seeded and stepped the same way as [`OrangeyCtx`]
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{python::Orangey, OrangeyCtx};
+///
+/// let mut rng = Orangey::seed("shared experiment");
+/// let mut native = OrangeyCtx::seed_from_str("shared experiment");
+/// assert_eq!(rng.rand(), native.rand());
+/// assert_eq!(rng.rand_range(1, 7).unwrap(), native.rand_range(1..7));
+/// assert_eq!(rng.gamma(2.0, 1.5).unwrap(), native.gamma(2.0, 1.5));
+/// assert_eq!(rng.save_hex(), native.save_hex());
+/// ```
+#[pyclass(module = "orangey", skip_from_py_object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Orangey {
+    ctx: OrangeyCtx,
+}
+
+/// Raises `ValueError` with `message` unless `ok`
+fn check(ok: bool, message: impl FnOnce() -> String) -> PyResult<()> {
+    if ok {
+        Ok(())
+    } else {
+        Err(PyValueError::new_err(message()))
+    }
+}
+
+#[pymethods]
+impl Orangey {
+    /// Makes a generator with the same fixed seed as [`OrangeyCtx::new`]
+    #[new]
+    pub fn new() -> Orangey {
+        Orangey {
+            ctx: OrangeyCtx::new(),
+        }
+    }
+
+    /// Makes a generator seeded from any string, like [`OrangeyCtx::seed_from_str`]
+    #[staticmethod]
+    pub fn seed(seed: &str) -> Orangey {
+        Orangey {
+            ctx: OrangeyCtx::seed_from_str(seed),
+        }
+    }
+
+    /// Seeds the generator with new initial state and sequence values, like
+    /// [`OrangeyCtx::srand`]
+    pub fn srand(&mut self, initstate: u128, initseq: u128) {
+        self.ctx.srand(initstate, initseq)
+    }
+
+    /// Returns a random 64-bit number
+    pub fn rand(&mut self) -> u64 {
+        self.ctx.rand()
+    }
+
+    /// Returns a number from `start` up to but not including `end`, like
+    /// [`OrangeyCtx::rand_range`], raising `ValueError` if the range is empty
+    pub fn rand_range(&mut self, start: u64, end: u64) -> PyResult<u64> {
+        check(start < end, || {
+            format!("the range {}..{} is empty", start, end)
+        })?;
+        Ok(self.ctx.rand_range(start..end))
+    }
+
+    /// Jumps `delta` values ahead in the stream, like [`OrangeyCtx::skip`]
+    pub fn skip(&mut self, delta: u128) {
+        self.ctx.skip(delta)
+    }
+
+    /// Jumps `delta` values back in the stream, like [`OrangeyCtx::skip_back`]
+    pub fn skip_back(&mut self, delta: u128) {
+        self.ctx.skip_back(delta)
+    }
+
+    /// Returns the generator's position as a string, like [`OrangeyCtx::save_hex`]
+    pub fn save_hex(&self) -> String {
+        self.ctx.save_hex()
+    }
+
+    /// Makes a generator at the position saved by `save_hex`, raising `ValueError` if `saved`
+    /// isn't one, like [`OrangeyCtx::restore_hex`]
+    #[staticmethod]
+    pub fn restore_hex(saved: &str) -> PyResult<Orangey> {
+        OrangeyCtx::restore_hex(saved)
+            .map(|ctx| Orangey { ctx })
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Returns a float in [0, 1), like [`OrangeyCtx::uniform_double`]
+    pub fn uniform_double(&mut self) -> f64 {
+        self.ctx.uniform_double()
+    }
+
+    /// Returns a float with standard gaussian density, like [`OrangeyCtx::gaussian`]
+    pub fn gaussian(&mut self) -> f64 {
+        self.ctx.gaussian()
+    }
+
+    /// Returns a float with gaussian density, like [`OrangeyCtx::gaussian_with`]
+    pub fn gaussian_with(&mut self, mean: f64, std_dev: f64) -> f64 {
+        self.ctx.gaussian_with(mean, std_dev)
+    }
+
+    /// Returns a float whose logarithm has gaussian density, like [`OrangeyCtx::log_normal`]
+    pub fn log_normal(&mut self, mu: f64, sigma: f64) -> f64 {
+        self.ctx.log_normal(mu, sigma)
+    }
+
+    /// Returns a float with exponential density, like [`OrangeyCtx::exponential`]
+    pub fn exponential(&mut self, lambda: f64) -> PyResult<f64> {
+        check(lambda > 0.0 && lambda.is_finite(), || {
+            format!("rate {} is not positive and finite", lambda)
+        })?;
+        Ok(self.ctx.exponential(lambda))
+    }
+
+    /// Returns a float with gamma density, like [`OrangeyCtx::gamma`]
+    pub fn gamma(&mut self, shape: f64, scale: f64) -> PyResult<f64> {
+        check(shape > 0.0, || format!("shape {} is not positive", shape))?;
+        check(scale > 0.0, || format!("scale {} is not positive", scale))?;
+        Ok(self.ctx.gamma(shape, scale))
+    }
+
+    /// Returns a float with beta density, like [`OrangeyCtx::beta`]
+    pub fn beta(&mut self, a: f64, b: f64) -> PyResult<f64> {
+        check(a > 0.0 && a.is_finite() && b > 0.0 && b.is_finite(), || {
+            format!("shapes {} and {} are not both positive and finite", a, b)
+        })?;
+        Ok(self.ctx.beta(a, b))
+    }
+
+    /// Returns a count with a poisson distribution, like [`OrangeyCtx::poisson`]
+    pub fn poisson(&mut self, ev: f64) -> PyResult<u64> {
+        check(ev >= 0.0 && ev.is_finite(), || {
+            format!("expected value {} is not non-negative and finite", ev)
+        })?;
+        Ok(self.ctx.poisson(ev))
+    }
+
+    /// Returns the successes in `n` trials of probability `p`, like [`OrangeyCtx::binomial`]
+    pub fn binomial(&mut self, n: u64, p: f64) -> PyResult<u64> {
+        check((0.0..=1.0).contains(&p), || {
+            format!("probability {} is not in [0, 1]", p)
+        })?;
+        Ok(self.ctx.binomial(n, p))
+    }
+
+    /// Returns the failures before the first success, like [`OrangeyCtx::geometric`]
+    pub fn geometric(&mut self, p: f64) -> PyResult<u64> {
+        check(p > 0.0 && p <= 1.0, || {
+            format!("probability {} is not in (0, 1]", p)
+        })?;
+        Ok(self.ctx.geometric(p))
+    }
+
+    /// Returns `n` random bytes as `bytes`, like [`OrangeyCtx::fill_bytes`]
+    pub fn rand_bytes<'py>(&mut self, py: Python<'py>, n: usize) -> Bound<'py, PyBytes> {
+        let mut bytes = vec![0; n];
+        self.ctx.fill_bytes(&mut bytes);
+        PyBytes::new(py, &bytes)
+    }
+}
+
+impl Default for Orangey {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The `orangey` Python module
+#[pymodule]
+fn orangey(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Orangey>()
+}
@@ -0,0 +1,316 @@
+//! Low-discrepancy sequences, which cover the unit hypercube more evenly than independent points.
+//! [`Sobol`], [`Halton`] and [`Pseudo`] are all iterators over points, so an estimate can
+//! switch between quasi-random and pseudo-random sampling by swapping which one it reads from
+//!
+//! The plain sequences are fixed, so the scrambled versions take any [`OrangeyEngine`] to randomize
+//! them while keeping their evenness. Averaging estimates over several scramblings gives an
+//! error bar, which the plain sequences can't
+
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::FloatExt;
+use crate::{OrangeyCtx, OrangeyEngine};
+use alloc::{vec, vec::Vec};
+
+/// The number of dimensions [`Sobol`] has direction numbers for
+pub const SOBOL_MAX_DIMS: usize = 32;
+
+// Degree, coefficients and initial direction numbers of the primitive polynomial for every
+// dimension after the first, from Joe & Kuo's new-joe-kuo-6.21201
+const JOE_KUO: [(u32, u32, &[u32]); SOBOL_MAX_DIMS - 1] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+    (7, 7, &[1, 1, 3, 13, 7, 35, 63]),
+    (7, 8, &[1, 3, 5, 9, 1, 25, 53]),
+    (7, 14, &[1, 3, 1, 13, 9, 35, 107]),
+    (7, 19, &[1, 3, 1, 5, 27, 61, 31]),
+    (7, 21, &[1, 1, 5, 11, 19, 41, 61]),
+    (7, 28, &[1, 3, 5, 3, 3, 13, 69]),
+    (7, 31, &[1, 1, 7, 13, 1, 19, 1]),
+    (7, 32, &[1, 3, 7, 5, 13, 19, 59]),
+    (7, 37, &[1, 1, 3, 9, 25, 29, 41]),
+    (7, 41, &[1, 3, 5, 13, 23, 1, 55]),
+    (7, 42, &[1, 3, 7, 3, 13, 59, 17]),
+];
+
+/// Points of the Sobol sequence in [0, 1)^`dims`, with 32 bits of precision per coordinate.
+/// The sequence ends after 2^32 points
+///
+/// # Examples
+///
+/// ```
+/// use orangey::quasi::Sobol;
+///
+/// let points: Vec<Vec<f64>> = Sobol::new(2).take(4).collect();
+/// assert_eq!(points, [[0.0, 0.0], [0.5, 0.5], [0.75, 0.25], [0.25, 0.75]]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sobol {
+    directions: Vec<[u32; 32]>,
+    current: Vec<u32>,
+    index: u64,
+}
+
+impl Sobol {
+    /// Starts the Sobol sequence in `dims` dimensions
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dims` is 0 or more than [`SOBOL_MAX_DIMS`]
+    pub fn new(dims: usize) -> Self {
+        assert!(
+            (1..=SOBOL_MAX_DIMS).contains(&dims),
+            "Sobol sequences need between 1 and {} dimensions, got {}",
+            SOBOL_MAX_DIMS,
+            dims
+        );
+        let mut directions = vec![[0; 32]; dims];
+        for (k, v) in directions[0].iter_mut().enumerate() {
+            *v = 1 << (31 - k);
+        }
+        for (v, &(s, a, m)) in directions[1..].iter_mut().zip(JOE_KUO.iter()) {
+            let s = s as usize;
+            for k in 0..32 {
+                v[k] = if k < s {
+                    m[k] << (31 - k)
+                } else {
+                    let mut x = v[k - s] ^ (v[k - s] >> s);
+                    for i in 1..s {
+                        if (a >> (s - 1 - i)) & 1 == 1 {
+                            x ^= v[k - i];
+                        }
+                    }
+                    x
+                };
+            }
+        }
+        Sobol {
+            directions,
+            current: vec![0; dims],
+            index: 0,
+        }
+    }
+
+    /// Starts a Sobol sequence in `dims` dimensions scrambled with a random linear matrix and a
+    /// random digital shift drawn from `ctx`. Every point is uniformly distributed, but the points
+    /// keep the sequence's evenness
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dims` is 0 or more than [`SOBOL_MAX_DIMS`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::{quasi::Sobol, OrangeyCtx};
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let xs: Vec<f64> = Sobol::scrambled(1, &mut orangey_ctx).take(8).map(|p| p[0]).collect();
+    /// let mut eighths: Vec<usize> = xs.iter().map(|x| (x * 8.0) as usize).collect();
+    /// eighths.sort();
+    /// assert_eq!(eighths, (0..8).collect::<Vec<_>>());
+    /// ```
+    pub fn scrambled<E: OrangeyEngine>(dims: usize, ctx: &mut E) -> Self {
+        let mut sobol = Sobol::new(dims);
+        for (v, x) in sobol.directions.iter_mut().zip(sobol.current.iter_mut()) {
+            // Row `i` of a lower triangular matrix with ones on the diagonal, where the first digit
+            // after the binary point is the top bit
+            let rows: Vec<u32> = (0..32)
+                .map(|i| (1u32 << (31 - i)) | (ctx.next_u32() & !(u32::MAX >> i)))
+                .collect();
+            for d in v.iter_mut() {
+                *d = rows.iter().enumerate().fold(0, |acc, (i, row)| {
+                    acc | ((row & *d).count_ones() & 1) << (31 - i)
+                });
+            }
+            *x = ctx.next_u32();
+        }
+        sobol
+    }
+
+    /// The number of dimensions of each point
+    pub fn dims(&self) -> usize {
+        self.current.len()
+    }
+}
+
+impl Iterator for Sobol {
+    type Item = Vec<f64>;
+
+    fn next(&mut self) -> Option<Vec<f64>> {
+        if self.index > u32::MAX as u64 {
+            return None;
+        }
+        let point = self
+            .current
+            .iter()
+            .map(|&x| x as f64 / 2f64.powi(32))
+            .collect();
+        // Going through the points in gray code order changes one direction number per step
+        let bit = self.index.trailing_ones() as usize;
+        if bit < 32 {
+            for (x, v) in self.current.iter_mut().zip(&self.directions) {
+                *x ^= v[bit];
+            }
+        }
+        self.index += 1;
+        Some(point)
+    }
+}
+
+/// Points of the Halton sequence in [0, 1)^`dims`, using the first `dims` primes as bases.
+/// Its evenness fades as the bases grow, so it is best kept to a handful of dimensions
+///
+/// # Examples
+///
+/// ```
+/// use orangey::quasi::Halton;
+///
+/// let points: Vec<Vec<f64>> = Halton::new(2).skip(1).take(2).collect();
+/// assert_eq!(points, [[0.5, 1.0 / 3.0], [0.25, 2.0 / 3.0]]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Halton {
+    bases: Vec<u64>,
+    // A permutation of the digits of each base, which leaves 0 alone
+    permutations: Vec<Vec<u64>>,
+    index: u64,
+}
+
+impl Halton {
+    /// Starts the Halton sequence in `dims` dimensions
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dims` is 0
+    pub fn new(dims: usize) -> Self {
+        assert!(dims > 0, "Halton sequences need at least one dimension");
+        let mut bases = Vec::with_capacity(dims);
+        let mut candidate = 2;
+        while bases.len() < dims {
+            if bases
+                .iter()
+                .take_while(|&&p| p * p <= candidate)
+                .all(|&p| candidate % p != 0)
+            {
+                bases.push(candidate);
+            }
+            candidate += 1;
+        }
+        let permutations = bases.iter().map(|&b| (0..b).collect()).collect();
+        Halton {
+            bases,
+            permutations,
+            index: 0,
+        }
+    }
+
+    /// Starts a Halton sequence in `dims` dimensions whose digits are scrambled, in each dimension,
+    /// by a random permutation drawn from `ctx`. This breaks up the patterns between dimensions
+    /// with large neighbouring bases
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dims` is 0
+    pub fn scrambled<E: OrangeyEngine>(dims: usize, ctx: &mut E) -> Self {
+        let mut halton = Halton::new(dims);
+        for permutation in &mut halton.permutations {
+            ctx.shuffle(&mut permutation[1..]);
+        }
+        halton
+    }
+
+    /// The number of dimensions of each point
+    pub fn dims(&self) -> usize {
+        self.bases.len()
+    }
+}
+
+impl Iterator for Halton {
+    type Item = Vec<f64>;
+
+    fn next(&mut self) -> Option<Vec<f64>> {
+        let point = self
+            .bases
+            .iter()
+            .zip(&self.permutations)
+            .map(|(&b, permutation)| {
+                // The radical inverse: the digits of the index mirrored around the radix point
+                let mut n = self.index;
+                let mut scale = 1.0 / b as f64;
+                let mut x = 0.0;
+                while n > 0 {
+                    x += permutation[(n % b) as usize] as f64 * scale;
+                    scale /= b as f64;
+                    n /= b;
+                }
+                x
+            })
+            .collect();
+        self.index = self.index.checked_add(1)?;
+        Some(point)
+    }
+}
+
+/// Independent uniform points in [0, 1)^`dims`, for comparing against the quasi-random sequences
+/// or using in their place. This consumes exactly one output of the generator per coordinate
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{quasi::{Pseudo, Sobol}, OrangeyCtx};
+///
+/// fn estimate_pi(points: impl Iterator<Item = Vec<f64>>) -> f64 {
+///     let n = 4096;
+///     let inside = points.take(n).filter(|p| p[0] * p[0] + p[1] * p[1] < 1.0).count();
+///     4.0 * inside as f64 / n as f64
+/// }
+///
+/// let mut orangey_ctx = OrangeyCtx::new();
+/// let quasi = estimate_pi(Sobol::new(2));
+/// let pseudo = estimate_pi(Pseudo::new(&mut orangey_ctx, 2));
+/// assert!((quasi - std::f64::consts::PI).abs() < 0.01);
+/// assert!((pseudo - std::f64::consts::PI).abs() < 0.2);
+/// ```
+#[derive(Debug)]
+pub struct Pseudo<'a, E: OrangeyEngine = OrangeyCtx> {
+    ctx: &'a mut E,
+    dims: usize,
+}
+
+impl<'a, E: OrangeyEngine> Pseudo<'a, E> {
+    /// Generates points in `dims` dimensions from `ctx`
+    pub fn new(ctx: &'a mut E, dims: usize) -> Self {
+        Pseudo { ctx, dims }
+    }
+
+    /// The number of dimensions of each point
+    pub fn dims(&self) -> usize {
+        self.dims
+    }
+}
+
+impl<E: OrangeyEngine> Iterator for Pseudo<'_, E> {
+    type Item = Vec<f64>;
+
+    fn next(&mut self) -> Option<Vec<f64>> {
+        Some((0..self.dims).map(|_| self.ctx.uniform_double()).collect())
+    }
+}
@@ -0,0 +1,98 @@
+//! Resampling methods for estimating how much a statistic would vary between samples, using only
+//! the sample at hand. Seeded from a single value, a whole pipeline replays exactly. Every
+//! function takes any [`OrangeyEngine`]
+
+use crate::OrangeyEngine;
+use alloc::vec::Vec;
+
+/// Computes `statistic` on each of `n_resamples` bootstrap resamples of `data`, each made by
+/// drawing `data.len()` items from it with replacement, and returns the results in the order
+/// they were computed. Their spread estimates the sampling distribution of the statistic. This
+/// takes one bounded integer per item per resample, as `.rand_range()` would
+///
+/// # Panics
+///
+/// Panics if `data` is empty
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{resample, OrangeyCtx};
+///
+/// let mut orangey_ctx = OrangeyCtx::seed_from_str("bootstrap");
+/// let data: Vec<f64> = (0..200).map(|_| orangey_ctx.gaussian_with(10.0, 2.0)).collect();
+/// let mean = |xs: &[f64]| xs.iter().sum::<f64>() / xs.len() as f64;
+///
+/// let mut means = resample::bootstrap(&mut orangey_ctx, &data, 2000, mean);
+/// means.sort_by(f64::total_cmp);
+/// // A 95% confidence interval for the mean, whose standard error is 2 / sqrt(200)
+/// let (low, high) = (means[50], means[1950]);
+/// assert!(low < mean(&data) && mean(&data) < high);
+/// assert!((high - low - 4.0 * 0.1414).abs() < 0.1);
+/// ```
+pub fn bootstrap<E, T, F>(ctx: &mut E, data: &[T], n_resamples: usize, mut statistic: F) -> Vec<f64>
+where
+    E: OrangeyEngine,
+    T: Clone,
+    F: FnMut(&[T]) -> f64,
+{
+    assert!(!data.is_empty(), "cannot resample from empty data");
+    let len = data.len() as u64;
+    let mut resample = Vec::with_capacity(data.len());
+    (0..n_resamples)
+        .map(|_| {
+            resample.clear();
+            resample.extend((0..len).map(|_| data[ctx.rand_range(0..len) as usize].clone()));
+            statistic(&resample)
+        })
+        .collect()
+}
+
+/// Tests whether `a` and `b` could come from the same distribution, by comparing the difference
+/// between their means with the differences found after `n_permutations` random reshufflings of
+/// which values belong to which sample. Returns the two-sided p-value, counting the observed split
+/// as one of the permutations so it is never 0. This takes one shuffle of all the values per
+/// permutation
+///
+/// # Panics
+///
+/// Panics if `a` or `b` is empty
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{resample, OrangeyCtx};
+///
+/// let mut orangey_ctx = OrangeyCtx::seed_from_str("permutation test");
+/// let control: Vec<f64> = (0..40).map(|_| orangey_ctx.gaussian_with(5.0, 1.0)).collect();
+/// let treated: Vec<f64> = (0..40).map(|_| orangey_ctx.gaussian_with(6.0, 1.0)).collect();
+/// let placebo: Vec<f64> = (0..40).map(|_| orangey_ctx.gaussian_with(5.0, 1.0)).collect();
+///
+/// assert!(resample::permutation_test(&mut orangey_ctx, &control, &treated, 5000) < 0.01);
+/// assert!(resample::permutation_test(&mut orangey_ctx, &control, &placebo, 5000) > 0.05);
+/// ```
+pub fn permutation_test<E: OrangeyEngine>(
+    ctx: &mut E,
+    a: &[f64],
+    b: &[f64],
+    n_permutations: usize,
+) -> f64 {
+    assert!(
+        !a.is_empty() && !b.is_empty(),
+        "cannot compare the means of empty samples"
+    );
+    let mean = |xs: &[f64]| xs.iter().sum::<f64>() / xs.len() as f64;
+    let observed = (mean(a) - mean(b)).abs();
+    // Sums come out slightly differently in other orders, so splits as extreme as the observed one
+    // mustn't be missed by a rounding error
+    let threshold = observed - observed * 1e-12;
+    let mut pooled: Vec<f64> = a.iter().chain(b).copied().collect();
+    let extreme = (0..n_permutations)
+        .filter(|_| {
+            ctx.shuffle(&mut pooled);
+            let (a, b) = pooled.split_at(a.len());
+            (mean(a) - mean(b)).abs() >= threshold
+        })
+        .count();
+    (extreme + 1) as f64 / (n_permutations + 1) as f64
+}
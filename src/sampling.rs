@@ -0,0 +1,182 @@
+//! Stratified sampling of the unit interval, square and hypercube. Spreading points out evenly
+//! like this usually lowers the variance of Monte Carlo estimates compared to independent points.
+//! Also blue-noise point sets, for placing things evenly but irregularly. Every function takes
+//! any [`OrangeyEngine`]
+
+#[cfg(all(not(feature = "std"), not(test)))]
+use crate::math::FloatExt;
+use crate::OrangeyEngine;
+use alloc::{vec, vec::Vec};
+
+/// Generates `n` points in [0, 1), one placed uniformly inside each of `n` equal strata, in
+/// ascending order. This consumes exactly one output of the generator per point
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{sampling, OrangeyCtx};
+///
+/// let mut orangey_ctx = OrangeyCtx::new();
+/// let xs = sampling::stratified_1d(&mut orangey_ctx, 4);
+/// for (i, x) in xs.iter().enumerate() {
+///     assert!((i as f64 / 4.0..(i + 1) as f64 / 4.0).contains(x));
+/// }
+/// ```
+pub fn stratified_1d<E: OrangeyEngine>(ctx: &mut E, n: usize) -> Vec<f64> {
+    (0..n).map(|i| jitter(ctx, i, n)).collect()
+}
+
+/// Generates `nx * ny` points in [0, 1)², one placed uniformly inside each cell of an `nx` by `ny`
+/// grid, row by row. This consumes exactly two outputs of the generator per point
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{sampling, OrangeyCtx};
+///
+/// let mut orangey_ctx = OrangeyCtx::new();
+/// let points = sampling::stratified_2d(&mut orangey_ctx, 8, 8);
+/// let estimate = points.iter().filter(|[x, y]| x * x + y * y < 1.0).count() as f64 / 16.0;
+/// assert!((estimate - std::f64::consts::PI).abs() < 0.2);
+/// ```
+pub fn stratified_2d<E: OrangeyEngine>(ctx: &mut E, nx: usize, ny: usize) -> Vec<[f64; 2]> {
+    let mut points = Vec::with_capacity(nx * ny);
+    for j in 0..ny {
+        for i in 0..nx {
+            let x = jitter(ctx, i, nx);
+            let y = jitter(ctx, j, ny);
+            points.push([x, y]);
+        }
+    }
+    points
+}
+
+/// Generates `n` points in [0, 1)^`dims` such that, along every axis, each of `n` equal strata holds
+/// exactly one point
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{sampling, OrangeyCtx};
+///
+/// let mut orangey_ctx = OrangeyCtx::new();
+/// let points = sampling::latin_hypercube(&mut orangey_ctx, 10, 3);
+/// let mut strata: Vec<usize> = points.iter().map(|p| (p[1] * 10.0) as usize).collect();
+/// strata.sort();
+/// assert_eq!(strata, (0..10).collect::<Vec<_>>());
+/// ```
+pub fn latin_hypercube<E: OrangeyEngine>(ctx: &mut E, n: usize, dims: usize) -> Vec<Vec<f64>> {
+    let mut points = vec![Vec::with_capacity(dims); n];
+    for _ in 0..dims {
+        let mut strata: Vec<usize> = (0..n).collect();
+        ctx.shuffle(&mut strata);
+        for (point, stratum) in points.iter_mut().zip(strata) {
+            point.push(jitter(ctx, stratum, n));
+        }
+    }
+    points
+}
+
+/// Generates points in the rectangle [0, `width`) × [0, `height`) that are all at least `radius`
+/// apart, packed until no more fit, using Bridson's algorithm. The result is blue noise: as even
+/// as a grid on average but with no visible pattern, which suits scattering trees, stars or
+/// sample positions. Points come out in the order they were placed, starting from a uniformly
+/// random one
+///
+/// Each placed point tries up to 30 candidates around it, so this takes time and outputs in
+/// proportion to the number of points, with two outputs per candidate and one more each time
+/// an active point is picked
+///
+/// # Panics
+///
+/// Panics if `width`, `height` or `radius` isn't positive and finite
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{sampling, OrangeyCtx};
+///
+/// let mut orangey_ctx = OrangeyCtx::seed_from_str("forest");
+/// let trees = sampling::poisson_disk(&mut orangey_ctx, 100.0, 50.0, 4.0);
+/// for (i, (x, y)) in trees.iter().enumerate() {
+///     assert!((0.0..100.0).contains(x) && (0.0..50.0).contains(y));
+///     for (x2, y2) in &trees[..i] {
+///         assert!((x - x2).hypot(y - y2) >= 4.0);
+///     }
+/// }
+/// // A bit over half as many as the densest packing at this spacing would fit
+/// assert!((180..240).contains(&trees.len()));
+/// ```
+pub fn poisson_disk<E: OrangeyEngine>(
+    ctx: &mut E,
+    width: f64,
+    height: f64,
+    radius: f64,
+) -> Vec<(f64, f64)> {
+    const ATTEMPTS: usize = 30;
+    for (name, value) in [("width", width), ("height", height), ("radius", radius)] {
+        assert!(
+            value > 0.0 && value.is_finite(),
+            "{} {} is not positive and finite",
+            name,
+            value
+        );
+    }
+    // A cell this size can hold at most one point, so each one only needs checking against the
+    // cells two either side of its own
+    let cell = radius / core::f64::consts::SQRT_2;
+    let columns = (width / cell) as usize + 1;
+    let rows = (height / cell) as usize + 1;
+    let mut grid: Vec<Option<usize>> = vec![None; columns * rows];
+    let cell_of = |(x, y): (f64, f64)| ((x / cell) as usize, (y / cell) as usize);
+
+    let mut points = Vec::new();
+    let mut active = Vec::new();
+    let first = (width * ctx.uniform_double(), height * ctx.uniform_double());
+    let (column, row) = cell_of(first);
+    grid[row * columns + column] = Some(0);
+    points.push(first);
+    active.push(0);
+
+    while !active.is_empty() {
+        let slot = ctx.rand_range(0..active.len() as u64) as usize;
+        let (cx, cy) = points[active[slot]];
+        let found = (0..ATTEMPTS).find_map(|_| {
+            // Uniform over the area of the ring between `radius` and twice it
+            let distance = radius * (1.0 + 3.0 * ctx.uniform_double()).sqrt();
+            let (sin, cos) = (core::f64::consts::TAU * ctx.uniform_double()).sin_cos();
+            let candidate = (cx + distance * cos, cy + distance * sin);
+            let (x, y) = candidate;
+            if !(0.0..width).contains(&x) || !(0.0..height).contains(&y) {
+                return None;
+            }
+            let (column, row) = cell_of(candidate);
+            let near = (row.saturating_sub(2)..(row + 3).min(rows)).any(|r| {
+                (column.saturating_sub(2)..(column + 3).min(columns)).any(|c| {
+                    grid[r * columns + c].is_some_and(|i| {
+                        let (px, py) = points[i];
+                        (px - x) * (px - x) + (py - y) * (py - y) < radius * radius
+                    })
+                })
+            });
+            (!near).then_some((candidate, row * columns + column))
+        });
+        match found {
+            Some((candidate, index)) => {
+                grid[index] = Some(points.len());
+                active.push(points.len());
+                points.push(candidate);
+            }
+            None => {
+                active.swap_remove(slot);
+            }
+        }
+    }
+    points
+}
+
+/// Generates a point in the `i`-th of `n` equal strata of [0, 1)
+fn jitter<E: OrangeyEngine>(ctx: &mut E, i: usize, n: usize) -> f64 {
+    // Rounding can carry the top of a stratum into the next one, which would land on 1 for the last
+    ((i as f64 + ctx.uniform_double()) / n as f64).min(1.0 - f64::EPSILON / 2.0)
+}
@@ -0,0 +1,405 @@
+// ChaCha20 in the original layout from Bernstein's paper: a 256-bit key, a 64-bit block counter
+// in words 12 and 13, and a 64-bit nonce in words 14 and 15, which serves as the stream. Each
+// block gives 64 bytes of keystream, read out as 8 little-endian outputs, so the keystream bytes
+// come out in order through `fill_bytes`. The blocks themselves come from the RustCrypto
+// `chacha20` crate rather than a block function of our own.
+
+#[cfg(feature = "alloc")]
+use crate::{password::CharsetPolicy, ALPHANUMERIC};
+use crate::{OrangeyEngine, RangeError};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use chacha20::{
+    cipher::{KeyIvInit, StreamCipherCore},
+    ChaCha20LegacyCore,
+};
+use core::ops::{Range, RangeBounds};
+
+const BLOCK_OUTPUTS: usize = 8;
+
+/// A cryptographically secure generator, built on the ChaCha20 stream cipher, with the same
+/// methods as [`OrangeyCtx`](crate::OrangeyCtx) for the things secrets are made from: raw
+/// outputs, ranges, bytes, strings, UUIDs and passwords. Code that makes keys or tokens with
+/// an `OrangeyCtx` can switch to this without changing its calls, and anything generic over
+/// [`OrangeyEngine`] takes it as well
+///
+/// Unlike `OrangeyCtx`, its outputs don't give its state away, so knowing some of them doesn't
+/// help predict the rest. That only holds while the seed is secret, so seed it with
+/// `SecureOrangeyCtx::from_entropy` unless a known seed is what's wanted, as in tests. It is
+/// deliberately not `Clone`, so a stream isn't duplicated by accident, and prints no state in
+/// its `Debug` output. Each output costs several times one from `OrangeyCtx`
+///
+/// # Examples
+///
+/// ```
+/// use orangey::SecureOrangeyCtx;
+///
+/// let mut rng = SecureOrangeyCtx::from_seed([7; 32]);
+/// let mut key = [0u8; 32];
+/// rng.fill_bytes(&mut key);
+/// let token = rng.string_alphanumeric(24);
+/// assert_eq!(token.len(), 24);
+/// ```
+pub struct SecureOrangeyCtx {
+    key: [u8; 32],
+    stream: u64,
+    // The block number of `block`, and the position of the next output in it
+    counter: u64,
+    index: usize,
+    block: [u64; BLOCK_OUTPUTS],
+}
+
+impl SecureOrangeyCtx {
+    /// Makes a generator whose ChaCha20 key is `seed`, on stream 0. The same seed always gives
+    /// the same outputs, so only use a known seed where that's the point
+    ///
+    /// # Examples
+    ///
+    /// The outputs are the ChaCha20 keystream, here checked against the test vectors of
+    /// section 2.3.2 of RFC 8439, whose 96-bit nonce lines up with the top half of the block
+    /// counter and the stream:
+    ///
+    /// ```
+    /// use orangey::SecureOrangeyCtx;
+    ///
+    /// let mut zeros = SecureOrangeyCtx::from_seed([0; 32]);
+    /// assert_eq!(zeros.rand().to_le_bytes(), [0x76, 0xb8, 0xe0, 0xad, 0xa0, 0xf1, 0x3d, 0x90]);
+    ///
+    /// let mut rfc = SecureOrangeyCtx::from_seed(core::array::from_fn(|i| i as u8));
+    /// rfc.set_stream(0x4a000000);
+    /// rfc.skip(8 * (1 | 0x09000000 << 32));
+    /// let mut keystream = [0; 16];
+    /// rfc.fill_bytes(&mut keystream);
+    /// assert_eq!(
+    ///     keystream,
+    ///     [
+    ///         0x10, 0xf1, 0xe7, 0xe4, 0xd1, 0x3b, 0x59, 0x15, 0x50, 0x0f, 0xdd, 0x1f, 0xa3, 0x20,
+    ///         0x71, 0xc4,
+    ///     ]
+    /// );
+    /// ```
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        let mut ctx = SecureOrangeyCtx {
+            key: seed,
+            stream: 0,
+            counter: 0,
+            index: 0,
+            block: [0; BLOCK_OUTPUTS],
+        };
+        ctx.refill();
+        ctx
+    }
+
+    /// Makes a generator seeded by the operating system, which is the way to get one that can't
+    /// be predicted
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system can't provide randomness. Use
+    /// `SecureOrangeyCtx::try_from_entropy` to handle that case instead
+    #[cfg(feature = "getrandom")]
+    pub fn from_entropy() -> Self {
+        Self::try_from_entropy().expect("the operating system failed to provide a seed")
+    }
+
+    /// Makes a generator seeded by the operating system, or returns the reason it couldn't get a
+    /// seed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::SecureOrangeyCtx;
+    ///
+    /// let mut a = SecureOrangeyCtx::try_from_entropy().unwrap();
+    /// let mut b = SecureOrangeyCtx::try_from_entropy().unwrap();
+    /// assert_ne!(a.rand_u128(), b.rand_u128());
+    /// ```
+    #[cfg(feature = "getrandom")]
+    pub fn try_from_entropy() -> Result<Self, getrandom::Error> {
+        Ok(Self::from_seed(crate::OsEntropy::try_seed()?))
+    }
+
+    /// Switches to the start of another of the key's 2^64 streams, which is the ChaCha20 nonce.
+    /// Streams from the same key don't overlap, so each can be handed to a different user of it
+    pub fn set_stream(&mut self, stream: u64) {
+        self.stream = stream;
+        self.counter = 0;
+        self.index = 0;
+        self.refill();
+    }
+
+    /// Returns which of the key's streams the generator is on
+    pub fn stream(&self) -> u64 {
+        self.stream
+    }
+
+    /// Returns a random 64-bit number
+    #[inline]
+    pub fn rand(&mut self) -> u64 {
+        if self.index == BLOCK_OUTPUTS {
+            self.counter = self.counter.wrapping_add(1);
+            self.index = 0;
+            self.refill();
+        }
+        self.index += 1;
+        self.block[self.index - 1]
+    }
+
+    /// Returns a random 32-bit number, the low half of one output, as `OrangeyEngine::next_u32` gives
+    pub fn rand_u32(&mut self) -> u32 {
+        self.rand() as u32
+    }
+
+    /// Runs the generator twice and returns a 128-bit random number, with the first output in the high bits
+    pub fn rand_u128(&mut self) -> u128 {
+        let high = self.rand() as u128;
+        (high << 64) | self.rand() as u128
+    }
+
+    /// Jumps `delta` values ahead in the rng stream. This takes the same time however far it goes
+    pub fn skip(&mut self, delta: u128) {
+        let (counter, index) = self.locate(delta);
+        if counter != self.counter {
+            self.counter = counter;
+            self.refill();
+        }
+        self.index = index;
+    }
+
+    /// Peeks at the `delta`-th value ahead in the rng stream, so `.peek(0)` is what `.rand()` returns next.
+    /// This doesn't modify the rng state
+    pub fn peek(&self, delta: u128) -> u64 {
+        let (counter, index) = self.locate(delta);
+        if counter == self.counter {
+            self.block[index]
+        } else {
+            chacha20(&self.key, counter, self.stream)[index]
+        }
+    }
+
+    /// Fills `buf` with random bytes, taken from the little-endian bytes of consecutive outputs.
+    /// These are the bytes of the ChaCha20 keystream
+    pub fn fill_bytes(&mut self, buf: &mut [u8]) {
+        for chunk in buf.chunks_mut(8) {
+            chunk.copy_from_slice(&self.rand().to_le_bytes()[..chunk.len()]);
+        }
+    }
+
+    /// Generates a number in the range given
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty. Use `.try_rand_range()` to handle that case instead
+    pub fn rand_range(&mut self, range: Range<u64>) -> u64 {
+        match self.try_rand_range(range) {
+            Ok(r) => r,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Generates a number in the range given, which may be any kind of range,
+    /// including the full `0..=u64::MAX`
+    pub fn try_rand_range<R: RangeBounds<u64>>(&mut self, range: R) -> Result<u64, RangeError> {
+        let (low, high) = RangeError::check_bounds(&range)?;
+        Ok(low + crate::bounded((high - low).wrapping_add(1), || self.rand()))
+    }
+
+    /// Generates a float in the range [0, 1) with uniform density
+    pub fn uniform_double(&mut self) -> f64 {
+        OrangeyEngine::uniform_double(self)
+    }
+
+    /// Picks an element of `slice` uniformly, or returns `None` if it is empty
+    pub fn choose<'a, T>(&mut self, slice: &'a [T]) -> Option<&'a T> {
+        if slice.is_empty() {
+            return None;
+        }
+        Some(&slice[self.rand_range(0..slice.len() as u64) as usize])
+    }
+
+    /// Shuffles `slice` into a uniformly random order
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        OrangeyEngine::shuffle(self, slice)
+    }
+
+    /// Generates a string of `len` characters picked uniformly from `0-9`, `A-Z` and `a-z`
+    #[cfg(feature = "alloc")]
+    pub fn string_alphanumeric(&mut self, len: usize) -> String {
+        (0..len)
+            .map(|_| ALPHANUMERIC[self.rand_range(0..ALPHANUMERIC.len() as u64) as usize] as char)
+            .collect()
+    }
+
+    /// Generates a string of `len` lowercase hexadecimal digits
+    #[cfg(feature = "alloc")]
+    pub fn string_hex(&mut self, len: usize) -> String {
+        const HEX: &[u8] = b"0123456789abcdef";
+        let mut hex = String::with_capacity(len);
+        while hex.len() < len {
+            let mut bits = self.rand();
+            for _ in 0..16.min(len - hex.len()) {
+                hex.push(HEX[(bits & 0xF) as usize] as char);
+                bits >>= 4;
+            }
+        }
+        hex
+    }
+
+    /// Generates a string of `len` characters picked uniformly from `alphabet`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alphabet` is empty and `len` is not zero
+    #[cfg(feature = "alloc")]
+    pub fn string_from_alphabet(&mut self, alphabet: &[char], len: usize) -> String {
+        assert!(
+            !alphabet.is_empty() || len == 0,
+            "cannot pick characters from an empty alphabet"
+        );
+        (0..len)
+            .map(|_| alphabet[self.rand_range(0..alphabet.len() as u64) as usize])
+            .collect()
+    }
+
+    /// Generates the bytes of a random (version 4) UUID, with the version and variant bits set.
+    /// This consumes exactly two outputs of the generator
+    pub fn uuid_v4(&mut self) -> [u8; 16] {
+        let mut bytes = self.rand_u128().to_be_bytes();
+        bytes[6] = (bytes[6] & 0x0F) | 0x40;
+        bytes[8] = (bytes[8] & 0x3F) | 0x80;
+        bytes
+    }
+
+    /// Generates a random UUID in its usual hyphenated lowercase form
+    #[cfg(feature = "alloc")]
+    pub fn uuid_v4_string(&mut self) -> String {
+        crate::format_uuid(self.uuid_v4())
+    }
+
+    /// Secure counterpart of [`OrangeyCtx::password`](crate::OrangeyCtx::password)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `policy` asks for no classes, or if `len` is too short to have one character of
+    /// each
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::{password::CharsetPolicy, SecureOrangeyCtx};
+    ///
+    /// // Seeded from `SecureOrangeyCtx::from_entropy` outside of examples
+    /// let mut rng = SecureOrangeyCtx::from_seed([42; 32]);
+    /// let password = rng.password(20, CharsetPolicy::ALL);
+    /// assert_eq!(password.len(), 20);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn password(&mut self, len: usize, policy: CharsetPolicy) -> String {
+        crate::password::password(self, len, policy)
+    }
+
+    /// Secure counterpart of [`OrangeyCtx::passphrase`](crate::OrangeyCtx::passphrase)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `wordlist` is empty and `n_words` is not zero
+    #[cfg(feature = "alloc")]
+    pub fn passphrase(&mut self, wordlist: &[&str], n_words: usize) -> String {
+        crate::password::passphrase(self, wordlist, n_words)
+    }
+
+    /// Finds the block and position in it of the `delta`-th next output. The block counter wraps
+    /// around after 2^64 blocks, like the cipher's
+    fn locate(&self, delta: u128) -> (u64, usize) {
+        let position = ((self.counter as u128) << 3)
+            .wrapping_add(self.index as u128)
+            .wrapping_add(delta);
+        ((position >> 3) as u64, (position & 7) as usize)
+    }
+
+    fn refill(&mut self) {
+        self.block = chacha20(&self.key, self.counter, self.stream);
+    }
+}
+
+/// Computes one block of keystream, packed two words to an output
+fn chacha20(key: &[u8; 32], counter: u64, stream: u64) -> [u64; BLOCK_OUTPUTS] {
+    let mut core = ChaCha20LegacyCore::new(key.into(), &stream.to_le_bytes().into());
+    core.set_block_pos(counter);
+    let mut block = Default::default();
+    core.write_keystream_block(&mut block);
+    core::array::from_fn(|i| u64::from_le_bytes(block[8 * i..8 * i + 8].try_into().unwrap()))
+}
+
+#[cfg(feature = "zeroize")]
+impl SecureOrangeyCtx {
+    /// Overwrites the key and the buffered keystream with zeros, like
+    /// [`OrangeyCtx::zeroize`](crate::OrangeyCtx::zeroize). With the `zeroize` feature this also
+    /// happens when the generator is dropped
+    pub fn zeroize(&mut self) {
+        crate::wipe(&mut self.key);
+        crate::wipe(&mut self.stream);
+        crate::wipe(&mut self.counter);
+        crate::wipe(&mut self.index);
+        crate::wipe(&mut self.block);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecureOrangeyCtx {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl core::fmt::Debug for SecureOrangeyCtx {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SecureOrangeyCtx").finish_non_exhaustive()
+    }
+}
+
+impl OrangeyEngine for SecureOrangeyCtx {
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rand()
+    }
+
+    fn skip(&mut self, delta: u128) {
+        SecureOrangeyCtx::skip(self, delta)
+    }
+
+    fn peek_raw(&self, delta: u128) -> u64 {
+        self.peek(delta)
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::RngCore for SecureOrangeyCtx {
+    /// The low half of one output, as [`OrangeyEngine::next_u32`] takes
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.rand_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rand()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        SecureOrangeyCtx::fill_bytes(self, dst)
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::CryptoRng for SecureOrangeyCtx {}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::SeedableRng for SecureOrangeyCtx {
+    /// The ChaCha20 key
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        SecureOrangeyCtx::from_seed(seed)
+    }
+}
@@ -0,0 +1,28 @@
+// Turns arbitrary bytes into seeds. Related inputs like "test-1" and "test-2" must still give
+// unrelated streams, which casting the bytes straight into `initstate` wouldn't
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Hashes `bytes` down with FNV-1a, then fills out a seed for `OrangeyCtx::from_seed` with SplitMix64
+pub(crate) fn expand(bytes: &[u8]) -> [u8; 32] {
+    let mut hash = FNV_OFFSET;
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    let mut seed = [0; 32];
+    for chunk in seed.chunks_exact_mut(8) {
+        chunk.copy_from_slice(&splitmix64(&mut hash).to_le_bytes());
+    }
+    seed
+}
+
+/// Steps a SplitMix64 generator, whose outputs are well mixed even when its states are close
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,72 @@
+//! Bulk generation that works on four positions of the stream at once. Each lane is jumped ahead
+//! with its own affine map, so the four 128-bit multiplies don't wait on each other and the
+//! compiler can interleave or vectorize them. The lanes are plain arrays rather than
+//! `core::simd`, which is still unstable, so this builds on stable Rust for every target and
+//! falls back to ordinary scalar code where there is nothing to gain
+//!
+//! The outputs come out in exactly the order `.rand()` would give them
+
+use crate::OrangeyCtx;
+
+const LANES: usize = 4;
+
+/// The maps that jump a state `1..=LANES` steps, as `(mult, plus)` pairs
+fn lane_maps(inc: u128) -> [(u128, u128); LANES] {
+    core::array::from_fn(|i| {
+        let plus = OrangeyCtx::advance(0, i as u128 + 1, OrangeyCtx::MUL, inc);
+        let mult = OrangeyCtx::advance(1, i as u128 + 1, OrangeyCtx::MUL, inc).wrapping_sub(plus);
+        (mult, plus)
+    })
+}
+
+impl OrangeyCtx {
+    /// Returns the next four results of `.rand()` at once, working them out side by side
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut orangey_ctx = OrangeyCtx::new();
+    /// let mut serial = OrangeyCtx::new();
+    /// assert_eq!(orangey_ctx.rand_x4(), [(); 4].map(|_| serial.rand()));
+    /// assert_eq!(orangey_ctx, serial);
+    /// ```
+    pub fn rand_x4(&mut self) -> [u64; 4] {
+        let maps = lane_maps(self.inc);
+        let states = maps.map(|(mult, plus)| mult.wrapping_mul(self.state).wrapping_add(plus));
+        self.state = states[LANES - 1];
+        states.map(Self::output)
+    }
+
+    /// Fills `buf` with random bytes, giving exactly the same bytes as `.fill_bytes()` and leaving
+    /// the generator in the same state. Four interleaved lanes each step four outputs at a time,
+    /// which pays off for buffers of more than a few hundred bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use orangey::OrangeyCtx;
+    ///
+    /// let mut fast = [0u8; 1000];
+    /// let mut slow = [0u8; 1000];
+    /// OrangeyCtx::new().fill_bytes_simd(&mut fast);
+    /// OrangeyCtx::new().fill_bytes(&mut slow);
+    /// assert_eq!(fast, slow);
+    /// ```
+    pub fn fill_bytes_simd(&mut self, buf: &mut [u8]) {
+        let maps = lane_maps(self.inc);
+        let (stride_mult, stride_plus) = maps[LANES - 1];
+        let mut lanes = maps.map(|(mult, plus)| mult.wrapping_mul(self.state).wrapping_add(plus));
+
+        let mut blocks = buf.chunks_exact_mut(8 * LANES);
+        for block in &mut blocks {
+            for (bytes, &state) in block.chunks_exact_mut(8).zip(&lanes) {
+                bytes.copy_from_slice(&Self::output(state).to_le_bytes());
+            }
+            self.state = lanes[LANES - 1];
+            lanes = lanes.map(|state| stride_mult.wrapping_mul(state).wrapping_add(stride_plus));
+        }
+        self.fill_bytes(blocks.into_remainder());
+    }
+}
@@ -0,0 +1,364 @@
+//! Known answers for each method: the first results it gives from a fixed seed. They pin the
+//! sequences of [`STREAM_VERSION`](crate::STREAM_VERSION), so a program that needs to reproduce
+//! old runs can check at startup that the build it links against still gives the same numbers
+//!
+//! Floats are compared by their bits. The vectors for samplers that call `ln`, `exp` or the trig
+//! functions were made with glibc's versions, and other math libraries, including the `libm`
+//! feature's and those of macOS and Windows, can round the last bit differently, which can also
+//! change an integer sampler's accept or reject decision. Those vectors are marked as not
+//! [`exact`](TestVector::exact), and [`verify`] leaves them out. The integer vectors and those of
+//! the plain uniforms are exact everywhere
+//!
+//! # Examples
+//!
+//! ```
+//! use orangey::test_vectors;
+//!
+//! if let Err(mismatch) = test_vectors::verify() {
+//!     panic!("saved runs can't be replayed: {}", mismatch);
+//! }
+//! ```
+
+use crate::{Orangey32Ctx, OrangeyCtx, OrangeyExtCtx};
+
+/// The number of results in each vector
+pub const LEN: usize = 8;
+
+/// The seed every vector starts from, given to `seed_from_str` or `seed_from_bytes`
+pub const SEED: &str = "orangey test vectors";
+
+/// The first [`LEN`] results of one method, each from its own generator seeded with [`SEED`]
+#[derive(Debug, Clone, Copy)]
+pub struct TestVector {
+    /// The method and parameters the vector draws from, like `"gamma(2.5, 1.0)"`
+    pub name: &'static str,
+    /// Whether every build gives these results. The others call into the math library, and only
+    /// match where it rounds the way glibc's does
+    pub exact: bool,
+    /// The expected results, with floats given by their bits and narrower integers widened
+    pub expected: [u64; LEN],
+    generate: fn() -> [u64; LEN],
+}
+
+impl TestVector {
+    /// Works out the results on this build
+    pub fn run(&self) -> [u64; LEN] {
+        (self.generate)()
+    }
+
+    /// Checks the results on this build against the expected ones
+    pub fn check(&self) -> Result<(), Mismatch> {
+        let actual = self.run();
+        match (0..LEN).find(|&i| actual[i] != self.expected[i]) {
+            Some(index) => Err(Mismatch {
+                vector: self.name,
+                index,
+                expected: self.expected[index],
+                actual: actual[index],
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The first result that differed from its known answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The name of the vector it belongs to
+    pub vector: &'static str,
+    /// Its position in the vector
+    pub index: usize,
+    /// The known answer
+    pub expected: u64,
+    /// What this build gave instead
+    pub actual: u64,
+}
+
+impl core::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "result {} of {} was {:#018x}, expected {:#018x}",
+            self.index, self.vector, self.actual, self.expected
+        )
+    }
+}
+
+impl core::error::Error for Mismatch {}
+
+/// Checks every exact vector in [`VECTORS`], returning the first mismatch. These hold on every
+/// platform, so this is safe to run at startup anywhere
+pub fn verify() -> Result<(), Mismatch> {
+    VECTORS
+        .iter()
+        .filter(|vector| vector.exact)
+        .try_for_each(TestVector::check)
+}
+
+fn from_seed(mut next: impl FnMut(&mut OrangeyCtx) -> u64) -> [u64; LEN] {
+    let mut ctx = OrangeyCtx::seed_from_str(SEED);
+    core::array::from_fn(|_| next(&mut ctx))
+}
+
+/// Every known-answer vector
+///
+/// # Examples
+///
+/// The vectors that are not exact hold where the math library is glibc's
+///
+/// ```
+/// use orangey::test_vectors::VECTORS;
+///
+/// if cfg!(all(target_os = "linux", target_env = "gnu")) {
+///     assert!(VECTORS.iter().all(|vector| vector.check().is_ok()));
+/// }
+/// assert!(VECTORS.iter().filter(|vector| vector.exact).all(|vector| vector.check().is_ok()));
+/// ```
+pub const VECTORS: &[TestVector] = &[
+    TestVector {
+        name: "rand",
+        exact: true,
+        expected: [
+            0xa134bd56165b5cb2,
+            0x83dff3c1b9b066bf,
+            0xdbd615cbd860666f,
+            0x7108f7b307e654a6,
+            0x66e73a91bf3036ba,
+            0xc17195b32d973911,
+            0xd29f2a06d12ba57a,
+            0x76d6a207780b8dd8,
+        ],
+        generate: || from_seed(|ctx| ctx.rand()),
+    },
+    TestVector {
+        name: "rand_u32",
+        exact: true,
+        expected: [
+            0x165b5cb2, 0xa134bd56, 0xb9b066bf, 0x83dff3c1, 0xd860666f, 0xdbd615cb, 0x07e654a6,
+            0x7108f7b3,
+        ],
+        generate: || from_seed(|ctx| ctx.rand_u32() as u64),
+    },
+    TestVector {
+        name: "rand_range(0..1000)",
+        exact: true,
+        expected: [629, 515, 858, 441, 401, 755, 822, 464],
+        generate: || from_seed(|ctx| ctx.rand_range(0..1000)),
+    },
+    TestVector {
+        name: "rand_bool(0.3)",
+        exact: true,
+        expected: [0, 1, 0, 0, 0, 0, 0, 0],
+        generate: || from_seed(|ctx| ctx.rand_bool(0.3) as u64),
+    },
+    TestVector {
+        name: "uniform_double",
+        exact: true,
+        expected: [
+            0x3fd2f558596d72c8,
+            0x3fefe7837360cd7e,
+            0x3fd8572f618199bc,
+            0x3fe1ef660fcca94c,
+            0x3fdcea46fcc0dae8,
+            0x3fb95b32d9739110,
+            0x3fee540da2574af4,
+            0x3fda881de02e3760,
+        ],
+        generate: || from_seed(|ctx| ctx.uniform_double().to_bits()),
+    },
+    TestVector {
+        name: "uniform_float",
+        exact: true,
+        expected: [
+            0x3f36b964, 0x3ec19afc, 0x3f40ccde, 0x3f4ca94c, 0x3ec0dae8, 0x3e39c888, 0x3eae95e8,
+            0x3db8dd80,
+        ],
+        generate: || from_seed(|ctx| ctx.uniform_float().to_bits() as u64),
+    },
+    TestVector {
+        name: "all_doubles",
+        exact: true,
+        expected: [
+            0x3fe4bd56165b5cb2,
+            0x3feff3c1b9b066bf,
+            0x3fe615cbd860666f,
+            0x3fd8f7b307e654a6,
+            0x3fd73a91bf3036ba,
+            0x3fe195b32d973911,
+            0x3fef2a06d12ba57a,
+            0x3fd6a207780b8dd8,
+        ],
+        generate: || from_seed(|ctx| ctx.all_doubles().to_bits()),
+    },
+    TestVector {
+        name: "gaussian",
+        exact: false,
+        expected: [
+            0xc00061f9eec7b9e8,
+            0x3ff08e6c0e3024ac,
+            0xbfbc3e2ba88202be,
+            0xbfed5de5eb73eade,
+            0x3fe31dcfca219de5,
+            0xbfbd2ebca73d15ef,
+            0x3fec111207c19a9d,
+            0xbff6810e2598564b,
+        ],
+        generate: || from_seed(|ctx| ctx.gaussian().to_bits()),
+    },
+    TestVector {
+        name: "gaussian_fast",
+        exact: false,
+        expected: [
+            0x3fd3e05cf65edeb3,
+            0x3fa11aa46c28b505,
+            0x3ff2f8d0512d5a71,
+            0xbfc327b726ac0aaf,
+            0xbfcc9d4c5b79bb55,
+            0x3ff5ed875659ce8b,
+            0x3ff0476912fb62bb,
+            0xbfb0823c39690e2b,
+        ],
+        generate: || from_seed(|ctx| ctx.gaussian_fast().to_bits()),
+    },
+    TestVector {
+        name: "exponential(2.0)",
+        exact: false,
+        expected: [
+            0x3fc67ba2b649fdc3,
+            0x4007404c2adf7e39,
+            0x3fcea0a2013a9d0b,
+            0x3fda4e4ae85224d2,
+            0x3fd33c527a2111ff,
+            0x3faab39bc9f402c9,
+            0x3ff79d85662d73e9,
+            0x3fd121e602a72458,
+        ],
+        generate: || from_seed(|ctx| ctx.exponential(2.0).to_bits()),
+    },
+    TestVector {
+        name: "exponential_fast",
+        exact: false,
+        expected: [
+            0x3fe6287bf11f24a8,
+            0x3fdfab79b3e4f799,
+            0x3ffa03fdc899d19a,
+            0x3fe15f3e61ac4eb2,
+            0x3fda160ba8398365,
+            0x400aa1669f98a214,
+            0x3ff6fe190d8e7660,
+            0x3fd4712866c85084,
+        ],
+        generate: || from_seed(|ctx| ctx.exponential_fast().to_bits()),
+    },
+    TestVector {
+        name: "gamma(2.5, 1.0)",
+        exact: false,
+        expected: [
+            0x400540f4ad5e8485,
+            0x4011b0cafd0c78b1,
+            0x3ffda9edd8e47440,
+            0x401024b5350cd7b7,
+            0x3ff3dea60d11a7f5,
+            0x3fe8610b027c6b16,
+            0x3fe76cb98a1f6124,
+            0x3ff04c83c1e98b43,
+        ],
+        generate: || from_seed(|ctx| ctx.gamma(2.5, 1.0).to_bits()),
+    },
+    TestVector {
+        name: "beta(2.0, 3.0)",
+        exact: false,
+        expected: [
+            0x3fd2a56594cce691,
+            0x3fcd4cef40d4c64a,
+            0x3fdcfb5ab956e9ea,
+            0x3fd04f304fa5bfd0,
+            0x3fc943af641cbdf1,
+            0x3fd30b2d60c59be2,
+            0x3fd6f79284d26946,
+            0x3fe11c13e4b5dc28,
+        ],
+        generate: || from_seed(|ctx| ctx.beta(2.0, 3.0).to_bits()),
+    },
+    TestVector {
+        name: "von_mises(0.0, 2.0)",
+        exact: false,
+        expected: [
+            0xbfd651d32877f4be,
+            0xbfe9b4fb8fc1d1f2,
+            0x40028080dc7097b5,
+            0x3fd57ea42a459f91,
+            0xbfee826140e930b8,
+            0x3fb9c086d1fa89a7,
+            0x3fe5ffef73c041ca,
+            0xbfc8a360026f25ac,
+        ],
+        generate: || from_seed(|ctx| ctx.von_mises(0.0, 2.0).to_bits()),
+    },
+    TestVector {
+        name: "poisson(4.0)",
+        exact: false,
+        expected: [5, 8, 3, 4, 2, 4, 6, 7],
+        generate: || from_seed(|ctx| ctx.poisson(4.0)),
+    },
+    TestVector {
+        name: "poisson(1000.0)",
+        exact: false,
+        expected: [989, 996, 1070, 1012, 997, 1011, 995, 1000],
+        generate: || from_seed(|ctx| ctx.poisson(1000.0)),
+    },
+    TestVector {
+        name: "binomial(100, 0.3)",
+        exact: false,
+        expected: [29, 24, 26, 37, 32, 26, 28, 28],
+        generate: || from_seed(|ctx| ctx.binomial(100, 0.3)),
+    },
+    TestVector {
+        name: "geometric(0.2)",
+        exact: false,
+        expected: [1, 26, 2, 3, 2, 0, 13, 2],
+        generate: || from_seed(|ctx| ctx.geometric(0.2)),
+    },
+    TestVector {
+        name: "hypergeometric(52, 4, 5)",
+        exact: false,
+        expected: [0, 2, 0, 0, 0, 0, 1, 0],
+        generate: || from_seed(|ctx| ctx.hypergeometric(52, 4, 5)),
+    },
+    TestVector {
+        name: "zipf(1000, 1.1)",
+        exact: false,
+        expected: [4, 7, 546, 333, 27, 8, 24, 7],
+        generate: || from_seed(|ctx| ctx.zipf(1000, 1.1)),
+    },
+    TestVector {
+        name: "Orangey32Ctx::rand",
+        exact: true,
+        expected: [
+            0x2b6c5e66, 0x51fb950e, 0xfa69a720, 0x8e4356b9, 0x1f96c639, 0xe0fd81f9, 0x6c6a9536,
+            0x28c81043,
+        ],
+        generate: || {
+            let mut ctx = Orangey32Ctx::seed_from_bytes(SEED.as_bytes());
+            core::array::from_fn(|_| ctx.rand() as u64)
+        },
+    },
+    TestVector {
+        name: "OrangeyExtCtx::rand",
+        exact: true,
+        expected: [
+            0xb7e92a57d205cb75,
+            0x23f128c6d5f78c5b,
+            0x4808a75248bc5266,
+            0x7b7555010349b8ba,
+            0x931854efb67f21c0,
+            0x2b61740f5e01e503,
+            0x6aa4d10373ec0414,
+            0x51df4c76f8b6b525,
+        ],
+        generate: || {
+            let mut ctx = OrangeyExtCtx::seed_from_bytes(SEED.as_bytes());
+            core::array::from_fn(|_| ctx.rand())
+        },
+    },
+];
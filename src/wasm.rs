@@ -0,0 +1,110 @@
+//! JavaScript bindings through wasm-bindgen, so a browser game can make exactly the same numbers
+//! from a seed as its native server does. The `Orangey` class wraps an [`OrangeyCtx`], and each of
+//! its methods consumes the same outputs as the Rust method it's named after
+//!
+//! 64-bit values reach JavaScript as `BigInt`s. `randRange` takes and returns plain numbers, so
+//! its bounds are limited to 32 bits
+//!
+//! To build the module, ask for a C crate type for the wasm target, then generate the JavaScript
+//! glue with the `wasm-bindgen` tool:
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/orangey.wasm
+//! ```
+//!
+//! ```text
+//! import { Orangey } from "./orangey.js";
+//!
+//! const rng = Orangey.seed("level-3");
+//! const roll = rng.randRange(1, 7);
+//! const saved = rng.saveHex();
+//! ```
+
+use crate::OrangeyCtx;
+use alloc::string::String;
+use wasm_bindgen::prelude::*;
+
+/// A generator for JavaScript, seeded and stepped the same way as [`OrangeyCtx`]
+///
+/// # Examples
+///
+/// ```
+/// use orangey::{wasm::Orangey, OrangeyCtx};
+///
+/// let mut rng = Orangey::seed("shared world");
+/// let mut native = OrangeyCtx::seed_from_str("shared world");
+/// assert_eq!(rng.rand(), native.rand());
+/// assert_eq!(rng.rand_range(1, 7) as u64, native.rand_range(1..7));
+/// assert_eq!(rng.save_hex(), native.save_hex());
+/// ```
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Orangey {
+    ctx: OrangeyCtx,
+}
+
+#[wasm_bindgen]
+impl Orangey {
+    /// Makes a generator with the same fixed seed as [`OrangeyCtx::new`]
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Orangey {
+        Orangey {
+            ctx: OrangeyCtx::new(),
+        }
+    }
+
+    /// Makes a generator seeded from any string, like [`OrangeyCtx::seed_from_str`]
+    pub fn seed(seed: &str) -> Orangey {
+        Orangey {
+            ctx: OrangeyCtx::seed_from_str(seed),
+        }
+    }
+
+    /// Returns a random 64-bit number
+    pub fn rand(&mut self) -> u64 {
+        self.ctx.rand()
+    }
+
+    /// Returns a number from `start` up to but not including `end`, like
+    /// [`OrangeyCtx::rand_range`]
+    ///
+    /// # Panics
+    ///
+    /// Panics, which throws in JavaScript, if the range is empty
+    #[wasm_bindgen(js_name = randRange)]
+    pub fn rand_range(&mut self, start: u32, end: u32) -> u32 {
+        self.ctx.rand_range(start as u64..end as u64) as u32
+    }
+
+    /// Returns a float with standard gaussian density, like [`OrangeyCtx::gaussian`]
+    pub fn gaussian(&mut self) -> f64 {
+        self.ctx.gaussian()
+    }
+
+    /// Jumps `delta` values ahead in the stream, like [`OrangeyCtx::skip`]
+    pub fn skip(&mut self, delta: u64) {
+        self.ctx.skip(delta as u128)
+    }
+
+    /// Returns the generator's position as a string, like [`OrangeyCtx::save_hex`]
+    #[wasm_bindgen(js_name = saveHex)]
+    pub fn save_hex(&self) -> String {
+        self.ctx.save_hex()
+    }
+
+    /// Makes a generator at the position saved by `saveHex`, throwing if `saved` isn't one, like
+    /// [`OrangeyCtx::restore_hex`]
+    #[wasm_bindgen(js_name = restoreHex)]
+    pub fn restore_hex(saved: &str) -> Result<Orangey, JsError> {
+        OrangeyCtx::restore_hex(saved)
+            .map(|ctx| Orangey { ctx })
+            .map_err(|e| JsError::new(&alloc::format!("{}", e)))
+    }
+}
+
+impl Default for Orangey {
+    fn default() -> Self {
+        Self::new()
+    }
+}